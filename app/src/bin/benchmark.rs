@@ -41,10 +41,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     profile.port = config.port;
     profile.database = Some(config.database.clone());
 
-    let mut manager = ConnectionManager::new(MysqlConnectionBackend);
+    let mut manager = ConnectionManager::new(MysqlConnectionBackend::default());
     let connect_latency = manager.connect(profile.clone()).await.map_err(io_other)?;
 
-    let data_backend = MysqlDataBackend::from_profile(&profile);
+    let data_backend = MysqlDataBackend::from_profile(&profile).map_err(io_other)?;
     if config.seed_rows > 0 {
         ensure_seed_data(&data_backend, config.seed_rows).await?;
     }
@@ -80,6 +80,7 @@ pub(crate) async fn query_scalar_u64(backend: &MysqlDataBackend, sql: &str) -> i
         .values
         .first()
         .ok_or_else(|| io_other("query returned no columns"))?;
+    let value = value.to_string();
     value
         .parse::<u64>()
         .map_err(|error| io_other(format!("failed to parse scalar value `{value}`: {error}")))
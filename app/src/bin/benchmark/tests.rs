@@ -280,7 +280,8 @@ async fn benchmark_helpers_work_against_mysql() {
     }
 
     let database = "myr_bench_cov";
-    let admin_backend = MysqlDataBackend::from_profile(&integration_profile(None));
+    let admin_backend =
+        MysqlDataBackend::from_profile(&integration_profile(None)).expect("backend should build");
     execute_sql(
         &admin_backend,
         &format!("CREATE DATABASE IF NOT EXISTS `{database}`"),
@@ -289,7 +290,8 @@ async fn benchmark_helpers_work_against_mysql() {
     .expect("create db");
     admin_backend.disconnect().await.expect("disconnect admin");
 
-    let backend = MysqlDataBackend::from_profile(&integration_profile(Some(database)));
+    let backend = MysqlDataBackend::from_profile(&integration_profile(Some(database)))
+        .expect("backend should build");
     execute_sql(&backend, "DROP TABLE IF EXISTS events")
         .await
         .expect("drop table");
@@ -322,7 +324,8 @@ async fn query_scalar_reports_parse_errors() {
     }
 
     let database = "myr_bench_cov";
-    let admin_backend = MysqlDataBackend::from_profile(&integration_profile(None));
+    let admin_backend =
+        MysqlDataBackend::from_profile(&integration_profile(None)).expect("backend should build");
     execute_sql(
         &admin_backend,
         &format!("CREATE DATABASE IF NOT EXISTS `{database}`"),
@@ -331,7 +334,8 @@ async fn query_scalar_reports_parse_errors() {
     .expect("create db");
     admin_backend.disconnect().await.expect("disconnect admin");
 
-    let backend = MysqlDataBackend::from_profile(&integration_profile(Some(database)));
+    let backend = MysqlDataBackend::from_profile(&integration_profile(Some(database)))
+        .expect("backend should build");
     let err = query_scalar_u64(&backend, "SELECT 'not-an-int'")
         .await
         .expect_err("parse should fail");
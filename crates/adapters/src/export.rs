@@ -4,7 +4,8 @@ use std::path::Path;
 
 use flate2::write::GzEncoder;
 use flate2::Compression as GzipCompression;
-use serde_json::{json, Map, Value};
+use myr_core::query_runner::{QueryValue, ResultColumn};
+use serde_json::{Map, Number, Value};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -31,22 +32,49 @@ pub enum JsonExportFormat {
     JsonLines,
 }
 
+// Scripts written against the all-string output keep it unless they ask for typed values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValueEncoding {
+    Typed,
+    #[default]
+    StringCompat,
+}
+
 pub fn export_rows_to_csv(
     path: &Path,
     headers: &[String],
-    rows: &[Vec<String>],
+    rows: impl IntoIterator<Item = impl AsRef<[QueryValue]>>,
 ) -> Result<usize, ExportError> {
-    export_rows_to_csv_with_options(path, headers, rows, ExportCompression::None)
+    export_rows_to_csv_with_options(
+        path,
+        headers,
+        rows,
+        ExportCompression::None,
+        ValueEncoding::Typed,
+        None,
+    )
 }
 
 pub fn export_rows_to_csv_with_options(
     path: &Path,
     headers: &[String],
-    rows: &[Vec<String>],
+    rows: impl IntoIterator<Item = impl AsRef<[QueryValue]>>,
     compression: ExportCompression,
+    encoding: ValueEncoding,
+    schema: Option<&[ResultColumn]>,
 ) -> Result<usize, ExportError> {
     let mut writer = OutputWriter::create(path, compression)?;
 
+    if let Some(columns) = schema {
+        let header = serde_json::to_string(&schema_header_json(columns))?;
+        writer
+            .write_all(format!("# schema: {header}\n").as_bytes())
+            .map_err(|source| ExportError::Write {
+                path: path.display().to_string(),
+                source,
+            })?;
+    }
+
     writer
         .write_all(
             headers
@@ -67,14 +95,16 @@ pub fn export_rows_to_csv_with_options(
             source,
         })?;
 
+    let mut row_count = 0;
     for row in rows {
+        let row = row.as_ref();
         let mut values = Vec::with_capacity(headers.len());
         for column_index in 0..headers.len() {
-            let value = row
-                .get(column_index)
-                .map(std::string::String::as_str)
-                .unwrap_or("");
-            values.push(csv_escape(value));
+            values.push(
+                row.get(column_index)
+                    .map(|value| csv_field(value, encoding))
+                    .unwrap_or_default(),
+            );
         }
 
         writer
@@ -89,16 +119,17 @@ pub fn export_rows_to_csv_with_options(
                 path: path.display().to_string(),
                 source,
             })?;
+        row_count += 1;
     }
 
     writer.finish(path)?;
-    Ok(rows.len())
+    Ok(row_count)
 }
 
 pub fn export_rows_to_json(
     path: &Path,
     headers: &[String],
-    rows: &[Vec<String>],
+    rows: impl IntoIterator<Item = impl AsRef<[QueryValue]>>,
 ) -> Result<usize, ExportError> {
     export_rows_to_json_with_options(
         path,
@@ -106,28 +137,48 @@ pub fn export_rows_to_json(
         rows,
         JsonExportFormat::Array,
         ExportCompression::None,
+        ValueEncoding::Typed,
+        None,
     )
 }
 
 pub fn export_rows_to_json_with_options(
     path: &Path,
     headers: &[String],
-    rows: &[Vec<String>],
+    rows: impl IntoIterator<Item = impl AsRef<[QueryValue]>>,
     format: JsonExportFormat,
     compression: ExportCompression,
+    encoding: ValueEncoding,
+    schema: Option<&[ResultColumn]>,
 ) -> Result<usize, ExportError> {
     let mut writer = OutputWriter::create(path, compression)?;
+    let mut row_count = 0;
 
     match format {
         JsonExportFormat::Array => {
+            if let Some(columns) = schema {
+                writer
+                    .write_all(b"{\"schema\":")
+                    .map_err(|source| ExportError::Write {
+                        path: path.display().to_string(),
+                        source,
+                    })?;
+                serde_json::to_writer(&mut writer, &schema_header_json(columns))?;
+                writer
+                    .write_all(b",\"rows\":")
+                    .map_err(|source| ExportError::Write {
+                        path: path.display().to_string(),
+                        source,
+                    })?;
+            }
             writer
                 .write_all(b"[")
                 .map_err(|source| ExportError::Write {
                     path: path.display().to_string(),
                     source,
                 })?;
-            for (index, row) in rows.iter().enumerate() {
-                if index > 0 {
+            for row in rows {
+                if row_count > 0 {
                     writer
                         .write_all(b",")
                         .map_err(|source| ExportError::Write {
@@ -135,19 +186,31 @@ pub fn export_rows_to_json_with_options(
                             source,
                         })?;
                 }
-                let object = row_as_json_object(headers, row);
+                let object = row_as_json_object(headers, row.as_ref(), encoding);
                 serde_json::to_writer(&mut writer, &Value::Object(object))?;
+                row_count += 1;
             }
             writer
-                .write_all(b"]\n")
+                .write_all(if schema.is_some() { b"]}\n" } else { b"]\n" })
                 .map_err(|source| ExportError::Write {
                     path: path.display().to_string(),
                     source,
                 })?;
         }
         JsonExportFormat::JsonLines => {
+            if let Some(columns) = schema {
+                let mut object = Map::with_capacity(1);
+                object.insert("schema".to_string(), schema_header_json(columns));
+                serde_json::to_writer(&mut writer, &Value::Object(object))?;
+                writer
+                    .write_all(b"\n")
+                    .map_err(|source| ExportError::Write {
+                        path: path.display().to_string(),
+                        source,
+                    })?;
+            }
             for row in rows {
-                let object = row_as_json_object(headers, row);
+                let object = row_as_json_object(headers, row.as_ref(), encoding);
                 serde_json::to_writer(&mut writer, &Value::Object(object))?;
                 writer
                     .write_all(b"\n")
@@ -155,25 +218,112 @@ pub fn export_rows_to_json_with_options(
                         path: path.display().to_string(),
                         source,
                     })?;
+                row_count += 1;
             }
         }
     }
 
     writer.finish(path)?;
-    Ok(rows.len())
+    Ok(row_count)
 }
 
-fn row_as_json_object(headers: &[String], row: &[String]) -> Map<String, Value> {
+#[must_use]
+pub fn row_as_json_object(
+    headers: &[String],
+    row: &[QueryValue],
+    encoding: ValueEncoding,
+) -> Map<String, Value> {
     let mut object = Map::with_capacity(headers.len());
     for (column_index, header) in headers.iter().enumerate() {
         let value = row
             .get(column_index)
-            .map_or(Value::Null, |value| json!(value));
+            .map_or(Value::Null, |value| query_value_to_json(value, encoding));
         object.insert(header.clone(), value);
     }
     object
 }
 
+#[must_use]
+pub fn query_value_to_json(value: &QueryValue, encoding: ValueEncoding) -> Value {
+    if encoding == ValueEncoding::StringCompat {
+        return Value::String(value.to_compat_string());
+    }
+
+    match value {
+        QueryValue::Null => Value::Null,
+        QueryValue::Int(value) => Value::from(*value),
+        QueryValue::UInt(value) => Value::from(*value),
+        QueryValue::Float(value) => {
+            Number::from_f64(*value).map_or_else(|| Value::String(value.to_string()), Value::Number)
+        }
+        QueryValue::Json(raw) => {
+            serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.clone()))
+        }
+        QueryValue::Bytes(bytes) => match std::str::from_utf8(bytes) {
+            Ok(text) => Value::String(text.to_string()),
+            Err(_) => Value::String(hex_literal(bytes)),
+        },
+        QueryValue::Text(text) | QueryValue::Decimal(text) => Value::String(text.clone()),
+        QueryValue::Date { .. }
+        | QueryValue::DateTime { .. }
+        | QueryValue::Time { .. }
+        | QueryValue::Truncated { .. } => Value::String(value.to_string()),
+    }
+}
+
+#[must_use]
+pub fn schema_header_json(columns: &[ResultColumn]) -> Value {
+    Value::Array(
+        columns
+            .iter()
+            .map(|column| {
+                let mut object = Map::with_capacity(11);
+                object.insert("name".to_string(), Value::from(column.name.clone()));
+                object.insert("type".to_string(), Value::from(column.sql_type.name()));
+                object.insert("length".to_string(), Value::from(column.length));
+                object.insert("decimals".to_string(), Value::from(column.decimals));
+                object.insert("not_null".to_string(), Value::from(column.not_null));
+                object.insert("primary_key".to_string(), Value::from(column.primary_key));
+                object.insert("unsigned".to_string(), Value::from(column.unsigned));
+                object.insert("binary".to_string(), Value::from(column.binary));
+                object.insert("charset".to_string(), Value::from(column.charset));
+                object.insert(
+                    "origin_table".to_string(),
+                    column.origin_table.clone().map_or(Value::Null, Value::from),
+                );
+                object.insert(
+                    "origin_column".to_string(),
+                    column
+                        .origin_column
+                        .clone()
+                        .map_or(Value::Null, Value::from),
+                );
+                Value::Object(object)
+            })
+            .collect(),
+    )
+}
+
+fn csv_field(value: &QueryValue, encoding: ValueEncoding) -> String {
+    match (value, encoding) {
+        (QueryValue::Null, ValueEncoding::Typed) => String::new(),
+        (QueryValue::Text(text), ValueEncoding::Typed) if text.is_empty() => "\"\"".to_string(),
+        (QueryValue::Bytes(bytes), ValueEncoding::Typed) if std::str::from_utf8(bytes).is_err() => {
+            hex_literal(bytes)
+        }
+        _ => csv_escape(&value.to_compat_string()),
+    }
+}
+
+fn hex_literal(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(2 + bytes.len() * 2);
+    output.push_str("0x");
+    for byte in bytes {
+        output.push_str(&format!("{byte:02X}"));
+    }
+    output
+}
+
 fn csv_escape(value: &str) -> String {
     if value.contains([',', '"', '\n']) {
         format!("\"{}\"", value.replace('"', "\"\""))
@@ -244,21 +394,26 @@ mod tests {
     use std::io::Read;
 
     use flate2::read::GzDecoder;
+    use myr_core::query_runner::{QueryValue, ResultColumn, SqlType};
     use tempfile::TempDir;
 
     use super::{
         export_rows_to_csv, export_rows_to_csv_with_options, export_rows_to_json,
-        export_rows_to_json_with_options, ExportCompression, JsonExportFormat,
+        export_rows_to_json_with_options, ExportCompression, JsonExportFormat, ValueEncoding,
     };
 
+    fn text_row(values: &[&str]) -> Vec<QueryValue> {
+        values.iter().copied().map(QueryValue::from).collect()
+    }
+
     #[test]
     fn exports_rows_to_csv_with_header_and_escaping() {
         let temp_dir = TempDir::new().expect("failed to create temp dir");
         let path = temp_dir.path().join("result.csv");
         let headers = vec!["id".to_string(), "name".to_string()];
         let rows = vec![
-            vec!["1".to_string(), "alpha".to_string()],
-            vec!["2".to_string(), "quote \"name\"".to_string()],
+            text_row(&["1", "alpha"]),
+            text_row(&["2", "quote \"name\""]),
         ];
 
         let written = export_rows_to_csv(&path, &headers, &rows).expect("csv export failed");
@@ -273,7 +428,7 @@ mod tests {
         let temp_dir = TempDir::new().expect("failed to create temp dir");
         let path = temp_dir.path().join("result.json");
         let headers = vec!["id".to_string(), "value".to_string()];
-        let rows = vec![vec!["10".to_string(), "ok".to_string()]];
+        let rows = vec![text_row(&["10", "ok"])];
 
         let written = export_rows_to_json(&path, &headers, &rows).expect("json export failed");
         assert_eq!(written, 1);
@@ -288,11 +443,17 @@ mod tests {
         let temp_dir = TempDir::new().expect("failed to create temp dir");
         let path = temp_dir.path().join("result.csv.gz");
         let headers = vec!["id".to_string(), "name".to_string()];
-        let rows = vec![vec!["1".to_string(), "alpha".to_string()]];
+        let rows = vec![text_row(&["1", "alpha"])];
 
-        let written =
-            export_rows_to_csv_with_options(&path, &headers, &rows, ExportCompression::Gzip)
-                .expect("gzip csv export failed");
+        let written = export_rows_to_csv_with_options(
+            &path,
+            &headers,
+            &rows,
+            ExportCompression::Gzip,
+            ValueEncoding::Typed,
+            None,
+        )
+        .expect("gzip csv export failed");
         assert_eq!(written, 1);
 
         let file = fs::File::open(path).expect("open gzip file");
@@ -310,10 +471,7 @@ mod tests {
         let temp_dir = TempDir::new().expect("failed to create temp dir");
         let path = temp_dir.path().join("result.jsonl.gz");
         let headers = vec!["id".to_string(), "value".to_string()];
-        let rows = vec![
-            vec!["10".to_string(), "ok".to_string()],
-            vec!["11".to_string(), "next".to_string()],
-        ];
+        let rows = vec![text_row(&["10", "ok"]), text_row(&["11", "next"])];
 
         let written = export_rows_to_json_with_options(
             &path,
@@ -321,6 +479,8 @@ mod tests {
             &rows,
             JsonExportFormat::JsonLines,
             ExportCompression::Gzip,
+            ValueEncoding::Typed,
+            None,
         )
         .expect("jsonl gzip export failed");
         assert_eq!(written, 2);
@@ -340,4 +500,134 @@ mod tests {
         assert_eq!(first["id"], "10");
         assert_eq!(second["value"], "next");
     }
+
+    #[test]
+    fn typed_csv_keeps_null_distinct_from_empty_and_null_text() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let path = temp_dir.path().join("typed.csv");
+        let headers = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let rows = vec![vec![
+            QueryValue::Null,
+            QueryValue::from(""),
+            QueryValue::from("NULL"),
+        ]];
+
+        export_rows_to_csv(&path, &headers, &rows).expect("csv export failed");
+        let output = fs::read_to_string(path).expect("failed to read csv output");
+        assert_eq!(output.lines().nth(1), Some(",\"\",NULL"));
+    }
+
+    #[test]
+    fn typed_json_emits_native_numbers_nulls_and_documents() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let path = temp_dir.path().join("typed.json");
+        let headers = vec![
+            "id".to_string(),
+            "price".to_string(),
+            "note".to_string(),
+            "doc".to_string(),
+        ];
+        let rows = vec![vec![
+            QueryValue::Int(7),
+            QueryValue::Decimal("10.50".to_string()),
+            QueryValue::Null,
+            QueryValue::Json("{\"k\":[1,2]}".to_string()),
+        ]];
+
+        export_rows_to_json(&path, &headers, &rows).expect("json export failed");
+        let output = fs::read_to_string(path).expect("failed to read json output");
+        let parsed: serde_json::Value = serde_json::from_str(&output).expect("invalid json");
+        assert_eq!(parsed[0]["id"], 7);
+        assert_eq!(parsed[0]["price"], "10.50");
+        assert!(parsed[0]["note"].is_null());
+        assert_eq!(parsed[0]["doc"]["k"][1], 2);
+    }
+
+    #[test]
+    fn string_compat_encoding_matches_legacy_output() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let path = temp_dir.path().join("compat.json");
+        let headers = vec!["id".to_string(), "note".to_string()];
+        let rows = vec![vec![QueryValue::Int(7), QueryValue::Null]];
+
+        export_rows_to_json_with_options(
+            &path,
+            &headers,
+            &rows,
+            JsonExportFormat::Array,
+            ExportCompression::None,
+            ValueEncoding::StringCompat,
+            None,
+        )
+        .expect("json export failed");
+        let output = fs::read_to_string(path).expect("failed to read json output");
+        let parsed: serde_json::Value = serde_json::from_str(&output).expect("invalid json");
+        assert_eq!(parsed[0]["id"], "7");
+        assert_eq!(parsed[0]["note"], "NULL");
+    }
+    #[test]
+    fn schema_header_is_emitted_for_csv_and_json_formats() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let headers = vec!["id".to_string(), "name".to_string()];
+        let rows = vec![vec![QueryValue::Int(1), QueryValue::from("alpha")]];
+        let mut id = ResultColumn::new("id", SqlType::BigInt);
+        id.primary_key = true;
+        id.not_null = true;
+        let schema = vec![id, ResultColumn::new("name", SqlType::VarChar)];
+
+        let csv_path = temp_dir.path().join("schema.csv");
+        export_rows_to_csv_with_options(
+            &csv_path,
+            &headers,
+            &rows,
+            ExportCompression::None,
+            ValueEncoding::Typed,
+            Some(&schema),
+        )
+        .expect("csv export failed");
+        let csv = fs::read_to_string(csv_path).expect("failed to read csv output");
+        let mut lines = csv.lines();
+        let schema_line = lines
+            .next()
+            .and_then(|line| line.strip_prefix("# schema: "))
+            .expect("schema comment line");
+        let parsed: serde_json::Value = serde_json::from_str(schema_line).expect("schema json");
+        assert_eq!(parsed[0]["type"], "BIGINT");
+        assert_eq!(parsed[0]["primary_key"], true);
+        assert_eq!(lines.next(), Some("id,name"));
+
+        let json_path = temp_dir.path().join("schema.json");
+        export_rows_to_json_with_options(
+            &json_path,
+            &headers,
+            &rows,
+            JsonExportFormat::Array,
+            ExportCompression::None,
+            ValueEncoding::Typed,
+            Some(&schema),
+        )
+        .expect("json export failed");
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(json_path).expect("read json"))
+                .expect("invalid json");
+        assert_eq!(json["schema"][1]["type"], "VARCHAR");
+        assert_eq!(json["rows"][0]["id"], 1);
+
+        let jsonl_path = temp_dir.path().join("schema.jsonl");
+        export_rows_to_json_with_options(
+            &jsonl_path,
+            &headers,
+            &rows,
+            JsonExportFormat::JsonLines,
+            ExportCompression::None,
+            ValueEncoding::Typed,
+            Some(&schema),
+        )
+        .expect("jsonl export failed");
+        let jsonl = fs::read_to_string(jsonl_path).expect("read jsonl");
+        let first: serde_json::Value =
+            serde_json::from_str(jsonl.lines().next().expect("schema line")).expect("json");
+        assert_eq!(first["schema"][0]["name"], "id");
+        assert_eq!(jsonl.lines().count(), 2);
+    }
 }
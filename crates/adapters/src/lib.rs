@@ -1,5 +1,7 @@
 pub mod export;
 pub mod mysql;
+pub mod password;
+pub mod ssh_tunnel;
 
 #[must_use]
 pub fn adapter_name() -> &'static str {
@@ -1,55 +1,341 @@
+use std::fmt;
+use std::io;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, PoisonError};
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use futures_util::StreamExt;
 use myr_core::connection_manager::{BackendError, ConnectionBackend};
 use myr_core::profiles::{ConnectionProfile, PasswordSource, TlsMode};
-use myr_core::query_runner::{QueryBackend, QueryBackendError, QueryRow, QueryRowStream};
+use myr_core::query_runner::{
+    QueryBackend, QueryBackendError, QueryRow, QueryRowStream, QueryValue, QueryWarning,
+    ResultColumn, ServerCancellation, SqlType, StatementStatus,
+};
+use myr_core::retry_policy::{
+    CR_CONNECTION_ERROR, CR_CONN_HOST_ERROR, CR_SERVER_GONE_ERROR, CR_SERVER_LOST,
+};
+use myr_core::safe_mode::assess_sql_safety;
 use myr_core::schema_cache::{
     ColumnSchema, DatabaseSchema, ForeignKeySchema, SchemaBackend, SchemaBackendError,
     SchemaCatalog, TableSchema,
 };
-use mysql_async::prelude::{Query, Queryable};
+use myr_core::server_capabilities::ServerCapabilities;
+use myr_core::sql_generator::{with_max_execution_time_hint, with_server_time_limit};
+use mysql_async::consts::{ColumnFlags, ColumnType};
+use mysql_async::prelude::{Protocol, Queryable};
 use mysql_async::{
-    ClientIdentity, Conn, OptsBuilder, Pool, ResultSetStream, Row, SslOpts, TextProtocol, Value,
+    ClientIdentity, Column, Conn, DriverError, IoError, Opts, OptsBuilder, Params, Pool,
+    QueryResult, Row, SslOpts, Value,
 };
+use tokio::sync::{mpsc, Mutex, OwnedMutexGuard};
+
+use crate::password::{password_from_command, password_from_file, PasswordError};
+use crate::ssh_tunnel::tunnel_for_profile;
+
+const BINARY_CHARSET: u16 = 63;
+const STREAM_EVENT_CAPACITY: usize = 256;
+const REPLICA_LAG_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+// What a profile's password source gave for one connect. Sources like `password_command`
+// block and can be slow, so they run once per connect and every pool and connection opened
+// for it shares the result. Pools keep using it for the connections they open later, so a
+// short-lived token is only fetched again on the next connect.
+#[derive(Clone, Default)]
+pub struct ResolvedPassword(Option<String>);
+
+impl ResolvedPassword {
+    pub fn resolve_blocking(profile: &ConnectionProfile) -> Result<Self, BackendError> {
+        resolve_password(profile)
+            .map(Self)
+            .map_err(|error| BackendError::new(error.to_string()))
+    }
+
+    // Runs on the blocking pool so a slow command does not stall the async runtime.
+    pub async fn resolve(profile: &ConnectionProfile) -> Result<Self, BackendError> {
+        let profile = profile.clone();
+        tokio::task::spawn_blocking(move || Self::resolve_blocking(&profile))
+            .await
+            .map_err(|error| BackendError::new(format!("password lookup failed: {error}")))?
+    }
+}
+
+impl fmt::Debug for ResolvedPassword {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("ResolvedPassword(..)")
+    }
+}
 
 #[derive(Debug, Clone, Default)]
-pub struct MysqlConnectionBackend;
+pub struct MysqlConnectionBackend {
+    // Resolved by the caller for this connect; otherwise each connect resolves it.
+    password: Option<ResolvedPassword>,
+}
+
+impl MysqlConnectionBackend {
+    #[must_use]
+    pub fn with_password(password: ResolvedPassword) -> Self {
+        Self {
+            password: Some(password),
+        }
+    }
+}
 
 #[async_trait]
 impl ConnectionBackend for MysqlConnectionBackend {
     type Connection = Conn;
 
     async fn connect(&self, profile: &ConnectionProfile) -> Result<Self::Connection, BackendError> {
-        Conn::new(opts_from_profile(profile))
-            .await
-            .map_err(to_connection_error)
+        let password = match &self.password {
+            Some(password) => password.clone(),
+            None => ResolvedPassword::resolve(profile).await?,
+        };
+        // `init_sql` runs one statement at a time here so a failure can name the statement.
+        let opts = opts_from_profile(profile, &password)?.setup(Vec::<String>::new());
+        let mut conn = Conn::new(opts).await.map_err(|error| {
+            // A failed SSH jump only shows up as a dropped socket on the MySQL side.
+            tunnel_for_profile(profile)
+                .ok()
+                .flatten()
+                .and_then(|tunnel| tunnel.last_error())
+                .map_or_else(|| to_connection_error(error), BackendError::new)
+        })?;
+
+        let statements = profile.init_statements().collect::<Vec<_>>();
+        for (index, statement) in statements.iter().enumerate() {
+            if let Err(error) = conn.query_drop(*statement).await {
+                let code = mysql_error_code(&error);
+                let _ = conn.disconnect().await;
+                return Err(BackendError::new(format!(
+                    "init_sql statement {} of {} failed (`{statement}`): {error}",
+                    index + 1,
+                    statements.len()
+                ))
+                .with_code(code));
+            }
+        }
+        Ok(conn)
     }
 
     async fn ping(&self, connection: &mut Self::Connection) -> Result<(), BackendError> {
         connection.ping().await.map_err(to_connection_error)
     }
 
+    // `VERSION()` rather than the handshake version: only the full string says MariaDB.
+    async fn server_capabilities(
+        &self,
+        connection: &mut Self::Connection,
+    ) -> Result<ServerCapabilities, BackendError> {
+        let version = connection
+            .query_first::<String, _>("SELECT VERSION()")
+            .await
+            .map_err(to_connection_error)?
+            .unwrap_or_default();
+        ServerCapabilities::from_version_string(&version)
+            .ok_or_else(|| BackendError::new(format!("unrecognised server version `{version}`")))
+    }
+
     async fn disconnect(&self, connection: Self::Connection) -> Result<(), BackendError> {
         connection.disconnect().await.map_err(to_connection_error)
     }
 }
 
+#[derive(Debug, Default)]
+struct PinnedSession {
+    conn: Option<Conn>,
+    established: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryEndpoint {
+    Primary,
+    Replica(String),
+}
+
+impl fmt::Display for QueryEndpoint {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Primary => formatter.write_str("primary"),
+            Self::Replica(label) => write!(formatter, "replica {label}"),
+        }
+    }
+}
+
+// When a replica was last checked, and why reads skip it (`None` when usable).
+type ReplicaCheck = (Instant, Option<String>);
+
+#[derive(Debug, Clone)]
+struct ReplicaPool {
+    label: String,
+    pool: Pool,
+    opts: Opts,
+    // Only locked to read or store a result, never across the check itself.
+    last_check: Arc<std::sync::Mutex<Option<ReplicaCheck>>>,
+}
+
+impl ReplicaPool {
+    fn last_check(&self) -> Option<ReplicaCheck> {
+        self.last_check
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn record_check(&self, skip_reason: Option<String>) {
+        *self
+            .last_check
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some((Instant::now(), skip_reason));
+    }
+
+    fn clear_check(&self) {
+        *self
+            .last_check
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MysqlDataBackend {
     pool: Pool,
+    opts: Opts,
+    replicas: Vec<ReplicaPool>,
+    next_replica: Arc<AtomicUsize>,
+    max_replica_lag: Option<Duration>,
+    connect_timeout: Duration,
+    last_endpoint: Arc<std::sync::Mutex<Option<QueryEndpoint>>>,
+    fetch_warnings: bool,
+    // Keeps text-protocol values as the server's text instead of parsing them by column type.
+    server_text_values: bool,
+    pinned_session: Option<Arc<Mutex<PinnedSession>>>,
+    max_execution_time: Option<Duration>,
+    server: Option<ServerCapabilities>,
 }
 
 impl MysqlDataBackend {
+    pub fn from_profile(profile: &ConnectionProfile) -> Result<Self, BackendError> {
+        Self::from_resolved(profile, &ResolvedPassword::resolve_blocking(profile)?)
+    }
+
+    // Replicas share the primary's credentials, so one resolved password feeds every pool.
+    pub fn from_resolved(
+        profile: &ConnectionProfile,
+        password: &ResolvedPassword,
+    ) -> Result<Self, BackendError> {
+        let replicas = profile
+            .replicas
+            .iter()
+            .map(|replica| {
+                let replica = profile.replica_profile(replica);
+                let opts = Opts::from(opts_from_profile(&replica, password)?);
+                Ok(ReplicaPool {
+                    label: format!("{}:{}", replica.host, replica.port),
+                    pool: Pool::new(opts.clone()),
+                    opts,
+                    last_check: Arc::default(),
+                })
+            })
+            .collect::<Result<Vec<_>, BackendError>>()?;
+        let opts = Opts::from(opts_from_profile(profile, password)?);
+        Ok(Self {
+            pool: Pool::new(opts.clone()),
+            opts,
+            replicas,
+            next_replica: Arc::default(),
+            max_replica_lag: profile.max_replica_lag(),
+            connect_timeout: profile.connect_timeout(),
+            last_endpoint: Arc::default(),
+            fetch_warnings: profile.fetch_warnings,
+            server_text_values: false,
+            pinned_session: profile.pinned_session.then(Default::default),
+            max_execution_time: profile
+                .max_execution_time_hint
+                .then(|| profile.query_timeout())
+                .flatten(),
+            server: None,
+        })
+    }
+
     #[must_use]
-    pub fn from_profile(profile: &ConnectionProfile) -> Self {
-        Self {
-            pool: Pool::new(opts_from_profile(profile)),
+    pub fn with_server_capabilities(mut self, server: Option<ServerCapabilities>) -> Self {
+        self.server = server;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_execution_time(mut self, max_execution_time: Option<Duration>) -> Self {
+        self.max_execution_time = max_execution_time;
+        self
+    }
+
+    #[must_use]
+    pub fn with_fetch_warnings(mut self, fetch_warnings: bool) -> Self {
+        self.fetch_warnings = fetch_warnings;
+        self
+    }
+
+    #[must_use]
+    pub fn with_server_text_values(mut self, server_text_values: bool) -> Self {
+        self.server_text_values = server_text_values;
+        self
+    }
+
+    #[must_use]
+    pub fn with_pinned_session(mut self, pinned_session: bool) -> Self {
+        self.pinned_session = pinned_session.then(Default::default);
+        self
+    }
+
+    #[must_use]
+    pub fn pinned_session(&self) -> bool {
+        self.pinned_session.is_some()
+    }
+
+    #[must_use]
+    pub fn has_replicas(&self) -> bool {
+        !self.replicas.is_empty()
+    }
+
+    #[must_use]
+    pub fn last_endpoint(&self) -> Option<QueryEndpoint> {
+        self.last_endpoint
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    // A pinned session is pinged on its own connection so an idle transaction outlives
+    // `wait_timeout`; a dead one is dropped and the next query reports the lost session.
+    pub async fn ping(&self) -> Result<Duration, BackendError> {
+        let started_at = Instant::now();
+        if let Some(session) = &self.pinned_session {
+            let mut session = session.lock().await;
+            if let Some(conn) = session.conn.as_mut() {
+                if let Err(error) = conn.ping().await {
+                    session.conn = None;
+                    return Err(to_connection_error(error));
+                }
+                return Ok(started_at.elapsed());
+            }
         }
+
+        let mut conn = self.pool.get_conn().await.map_err(to_connection_error)?;
+        conn.ping().await.map_err(to_connection_error)?;
+        Ok(started_at.elapsed())
     }
 
     pub async fn disconnect(&self) -> Result<(), mysql_async::Error> {
+        if let Some(session) = &self.pinned_session {
+            let pinned = session.lock().await.conn.take();
+            if let Some(conn) = pinned {
+                conn.disconnect().await?;
+            }
+        }
+        for replica in &self.replicas {
+            replica.pool.clone().disconnect().await?;
+        }
         self.pool.clone().disconnect().await
     }
 }
@@ -149,25 +435,50 @@ impl SchemaBackend for MysqlDataBackend {
     }
 }
 
+#[derive(Debug)]
+enum QueryRequest {
+    Text(String),
+    Prepared { sql: String, params: Vec<Value> },
+}
+
+#[derive(Debug)]
+enum StreamEvent {
+    Columns(Vec<ResultColumn>),
+    Row(QueryRow),
+    ResultSetCompleted(StatementStatus),
+    Completed(StatementStatus),
+    Failed(QueryBackendError),
+}
+
 #[derive(Debug)]
 pub struct MysqlStreamingRowStream {
-    stream: Option<ResultSetStream<'static, 'static, 'static, Row, TextProtocol>>,
-    cancelled: bool,
+    events: Option<mpsc::Receiver<StreamEvent>>,
     column_names: Vec<String>,
+    column_metadata: Vec<ResultColumn>,
+    status: Option<StatementStatus>,
+    result_set_finished: bool,
+    // Where KILL QUERY connects: the endpoint that runs the statement.
+    kill_opts: Opts,
+    connect_timeout: Duration,
+    connection_id: u32,
+    query_running: Arc<Mutex<bool>>,
+    session_state_lost: bool,
 }
 
 impl MysqlStreamingRowStream {
-    fn new(stream: ResultSetStream<'static, 'static, 'static, Row, TextProtocol>) -> Self {
-        let column_names = stream
-            .columns_ref()
+    #[must_use]
+    pub fn connection_id(&self) -> u32 {
+        self.connection_id
+    }
+
+    fn begin_result_set(&mut self, column_metadata: Vec<ResultColumn>) {
+        self.column_names = column_metadata
             .iter()
-            .map(|column| column.name_str().into_owned())
+            .map(|column| column.name.clone())
             .collect();
-        Self {
-            stream: Some(stream),
-            cancelled: false,
-            column_names,
-        }
+        self.column_metadata = column_metadata;
+        self.status = None;
+        self.result_set_finished = false;
     }
 }
 
@@ -177,28 +488,91 @@ impl QueryRowStream for MysqlStreamingRowStream {
         Some(&self.column_names)
     }
 
+    fn column_metadata(&self) -> Option<&[ResultColumn]> {
+        Some(&self.column_metadata)
+    }
+
+    fn statement_status(&self) -> Option<&StatementStatus> {
+        self.status.as_ref()
+    }
+
+    fn session_state_lost(&self) -> bool {
+        self.session_state_lost
+    }
+
     async fn next_row(&mut self) -> Result<Option<QueryRow>, QueryBackendError> {
-        if self.cancelled {
+        if self.result_set_finished {
             return Ok(None);
         }
-        let Some(stream) = self.stream.as_mut() else {
-            return Ok(None);
+        loop {
+            let Some(events) = self.events.as_mut() else {
+                return Ok(None);
+            };
+            match events.recv().await {
+                Some(StreamEvent::Row(row)) => return Ok(Some(row)),
+                Some(StreamEvent::Columns(columns)) => self.begin_result_set(columns),
+                Some(StreamEvent::ResultSetCompleted(status)) => {
+                    self.status = Some(status);
+                    self.result_set_finished = true;
+                    return Ok(None);
+                }
+                Some(StreamEvent::Completed(status)) => {
+                    self.status = Some(status);
+                    self.events = None;
+                    return Ok(None);
+                }
+                Some(StreamEvent::Failed(error)) => {
+                    self.events = None;
+                    return Err(error);
+                }
+                None => {
+                    self.events = None;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    async fn next_result_set(&mut self) -> Result<bool, QueryBackendError> {
+        while self.next_row().await?.is_some() {}
+        let Some(events) = self.events.as_mut() else {
+            return Ok(false);
         };
 
-        match stream.next().await {
-            Some(Ok(row)) => row_to_query_row(row).map(Some),
-            Some(Err(error)) => Err(to_query_error(error)),
-            None => {
-                self.stream = None;
-                Ok(None)
+        match events.recv().await {
+            Some(StreamEvent::Columns(columns)) => {
+                self.begin_result_set(columns);
+                Ok(true)
+            }
+            Some(StreamEvent::Failed(error)) => {
+                self.events = None;
+                Err(error)
+            }
+            Some(
+                StreamEvent::Row(_)
+                | StreamEvent::ResultSetCompleted(_)
+                | StreamEvent::Completed(_),
+            )
+            | None => {
+                self.events = None;
+                Ok(false)
             }
         }
     }
 
-    async fn cancel(&mut self) -> Result<(), QueryBackendError> {
-        self.cancelled = true;
-        self.stream = None;
-        Ok(())
+    async fn cancel(&mut self) -> Result<ServerCancellation, QueryBackendError> {
+        self.events = None;
+        // Holding the flag keeps the driver from returning the connection to the pool mid-kill.
+        let query_running = self.query_running.lock().await;
+        if !*query_running {
+            return Ok(ServerCancellation::ClientOnly);
+        }
+        Ok(kill_query(
+            self.kill_opts.clone(),
+            self.connect_timeout,
+            self.connection_id,
+        )
+        .await)
     }
 }
 
@@ -207,59 +581,477 @@ impl QueryBackend for MysqlDataBackend {
     type Stream = MysqlStreamingRowStream;
 
     async fn start_query(&self, sql: &str) -> Result<Self::Stream, QueryBackendError> {
-        let stream = sql
-            .to_string()
-            .stream::<Row, _>(self.pool.clone())
+        self.start_request(sql, QueryRequest::Text(self.server_limited_sql(sql)))
+            .await
+    }
+
+    async fn start_prepared(
+        &self,
+        sql: &str,
+        params: &[QueryValue],
+    ) -> Result<Self::Stream, QueryBackendError> {
+        self.start_request(
+            sql,
+            QueryRequest::Prepared {
+                sql: self.server_limited_sql(sql),
+                params: params.iter().map(query_value_to_mysql_value).collect(),
+            },
+        )
+        .await
+    }
+}
+
+impl MysqlDataBackend {
+    fn server_limited_sql(&self, sql: &str) -> String {
+        // Without a detected server the MySQL hint is the safe default: MariaDB
+        // ignores it as a comment.
+        self.max_execution_time
+            .and_then(|timeout| match &self.server {
+                Some(server) => with_server_time_limit(sql, timeout, server),
+                None => with_max_execution_time_hint(sql, timeout),
+            })
+            .unwrap_or_else(|| sql.to_string())
+    }
+
+    // Routing looks at the caller's SQL: the MariaDB time limit wraps reads in
+    // `SET STATEMENT ... FOR`, which would otherwise classify every read as a session change.
+    async fn start_request(
+        &self,
+        caller_sql: &str,
+        request: QueryRequest,
+    ) -> Result<MysqlStreamingRowStream, QueryBackendError> {
+        // A pinned session holds the open transaction, so it always stays on the primary.
+        let (conn, kill_opts, endpoint, pinned, session_state_lost) = match &self.pinned_session {
+            Some(session) => {
+                let mut pinned = Arc::clone(session).lock_owned().await;
+                let (conn, session_state_lost) =
+                    checkout_pinned_conn(&self.pool, &mut pinned).await?;
+                (
+                    conn,
+                    self.opts.clone(),
+                    QueryEndpoint::Primary,
+                    Some(pinned),
+                    session_state_lost,
+                )
+            }
+            None => {
+                let (conn, kill_opts, endpoint) = self.routed_conn(caller_sql).await?;
+                (conn, kill_opts, endpoint, None, false)
+            }
+        };
+        *self
+            .last_endpoint
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(endpoint);
+        let connection_id = conn.id();
+        let query_running = Arc::new(Mutex::new(true));
+        let (sender, events) = mpsc::channel(STREAM_EVENT_CAPACITY);
+        tokio::spawn(drive_query(
+            conn,
+            request,
+            self.fetch_warnings,
+            self.server_text_values,
+            sender,
+            Arc::clone(&query_running),
+            pinned,
+        ));
+
+        Ok(MysqlStreamingRowStream {
+            events: Some(events),
+            column_names: Vec::new(),
+            column_metadata: Vec::new(),
+            status: None,
+            result_set_finished: false,
+            kill_opts,
+            connect_timeout: self.connect_timeout,
+            connection_id,
+            query_running,
+            session_state_lost,
+        })
+    }
+}
+
+impl MysqlDataBackend {
+    // Reads take the replicas in turn, skipping any that lag or refuse a connection;
+    // everything else, and any read no replica can take, runs on the primary.
+    async fn routed_conn(
+        &self,
+        sql: &str,
+    ) -> Result<(Conn, Opts, QueryEndpoint), QueryBackendError> {
+        if self.has_replicas() && routes_to_replica(sql) {
+            let start = self.next_replica.fetch_add(1, Ordering::Relaxed);
+            for offset in 0..self.replicas.len() {
+                let replica = &self.replicas[(start + offset) % self.replicas.len()];
+                if let Ok(conn) = self.replica_conn(replica).await {
+                    let endpoint = QueryEndpoint::Replica(replica.label.clone());
+                    return Ok((conn, replica.opts.clone(), endpoint));
+                }
+            }
+        }
+        let conn = self.pool.get_conn().await.map_err(to_query_error)?;
+        Ok((conn, self.opts.clone(), QueryEndpoint::Primary))
+    }
+
+    // Both the lag probe and the checkout are bounded by the connect timeout, so an
+    // unreachable replica costs one timeout per check interval rather than stalling reads.
+    async fn replica_conn(&self, replica: &ReplicaPool) -> Result<Conn, String> {
+        if let Some(reason) = self.replica_skip_reason(replica).await {
+            return Err(reason);
+        }
+        let checkout = tokio::time::timeout(self.connect_timeout, replica.pool.get_conn()).await;
+        let reason = match checkout {
+            Ok(Ok(conn)) => return Ok(conn),
+            Ok(Err(error)) => format!("connect failed: {error}"),
+            Err(_) => format!("connect timed out after {:.1?}", self.connect_timeout),
+        };
+        replica.record_check(Some(reason.clone()));
+        Err(reason)
+    }
+
+    async fn replica_skip_reason(&self, replica: &ReplicaPool) -> Option<String> {
+        if let Some((checked_at, reason)) = replica.last_check() {
+            if checked_at.elapsed() < REPLICA_LAG_CHECK_INTERVAL {
+                return reason;
+            }
+        }
+        let Some(max_lag) = self.max_replica_lag else {
+            replica.clear_check();
+            return None;
+        };
+        let reason =
+            match tokio::time::timeout(self.connect_timeout, replica_lag(&replica.pool)).await {
+                Ok(Ok(lag)) if lag <= max_lag => None,
+                Ok(Ok(lag)) => Some(format!(
+                    "{}s behind, limit {}s",
+                    lag.as_secs(),
+                    max_lag.as_secs()
+                )),
+                Ok(Err(reason)) => Some(reason),
+                Err(_) => Some(format!(
+                    "lag check timed out after {:.1?}",
+                    self.connect_timeout
+                )),
+            };
+        replica.record_check(reason.clone());
+        reason
+    }
+
+    // Checks every replica now, the way a routed read would, for `myr-app doctor`.
+    pub async fn check_replicas(&self) -> Vec<(String, Option<String>)> {
+        let mut checks = Vec::with_capacity(self.replicas.len());
+        for replica in &self.replicas {
+            replica.clear_check();
+            let reason = self.replica_conn(replica).await.err();
+            checks.push((replica.label.clone(), reason));
+        }
+        checks
+    }
+
+    // Replicas that reads currently skip, with the reason from their last check.
+    #[must_use]
+    pub fn skipped_replicas(&self) -> Vec<String> {
+        self.replicas
+            .iter()
+            .filter_map(|replica| {
+                let (_, reason) = replica.last_check()?;
+                Some(format!("{} ({})", replica.label, reason?))
+            })
+            .collect()
+    }
+}
+
+// Locking reads and `INTO OUTFILE` are not read-only, so they stay on the primary too.
+fn routes_to_replica(sql: &str) -> bool {
+    assess_sql_safety(sql).is_safe_read_only()
+}
+
+// MySQL 8.0.22 renamed the statement and its lag column; MariaDB and older MySQL only know
+// the old names. A stopped replica reports NULL and is skipped as not replicating.
+async fn replica_lag(pool: &Pool) -> Result<Duration, String> {
+    let mut conn = pool
+        .get_conn()
+        .await
+        .map_err(|error| format!("connect failed: {error}"))?;
+    let mut last_error = String::new();
+    for statement in ["SHOW REPLICA STATUS", "SHOW SLAVE STATUS"] {
+        let status = match conn.query_first::<Row, _>(statement).await {
+            Ok(status) => status,
+            // Without the REPLICATION CLIENT privilege both statements fail.
+            Err(error) => {
+                last_error = format!("{statement} failed: {error}");
+                continue;
+            }
+        };
+        return status
+            .and_then(|status| {
+                ["Seconds_Behind_Source", "Seconds_Behind_Master"]
+                    .into_iter()
+                    .find_map(|column| status.get_opt::<Option<u64>, _>(column))
+            })
+            .and_then(Result::ok)
+            .flatten()
+            .map(Duration::from_secs)
+            .ok_or_else(|| "not replicating".to_string());
+    }
+    Err(last_error)
+}
+
+async fn drive_query(
+    mut conn: Conn,
+    request: QueryRequest,
+    fetch_warnings: bool,
+    server_text_values: bool,
+    events: mpsc::Sender<StreamEvent>,
+    query_running: Arc<Mutex<bool>>,
+    pinned: Option<OwnedMutexGuard<PinnedSession>>,
+) {
+    if let Err(error) = stream_query(
+        &mut conn,
+        request,
+        fetch_warnings,
+        server_text_values,
+        &events,
+    )
+    .await
+    {
+        let _ = events.send(StreamEvent::Failed(error)).await;
+    }
+    *query_running.lock().await = false;
+    if let Some(mut session) = pinned {
+        session.conn = Some(conn);
+    }
+}
+
+async fn checkout_pinned_conn(
+    pool: &Pool,
+    session: &mut PinnedSession,
+) -> Result<(Conn, bool), QueryBackendError> {
+    if let Some(mut conn) = session.conn.take() {
+        if conn.ping().await.is_ok() {
+            return Ok((conn, false));
+        }
+    }
+    let conn = pool.get_conn().await.map_err(to_query_error)?;
+    // Any session variables, current database or temporary tables lived on the old connection.
+    let session_state_lost = session.established;
+    session.established = true;
+    Ok((conn, session_state_lost))
+}
+
+// A dedicated connection rather than the pool: users cancel exactly when the pool is
+// exhausted or the server stops answering, and neither may leave the kill waiting.
+async fn kill_query(
+    opts: Opts,
+    connect_timeout: Duration,
+    connection_id: u32,
+) -> ServerCancellation {
+    let kill = async {
+        let mut conn = Conn::new(opts).await?;
+        let killed = conn.query_drop(format!("KILL QUERY {connection_id}")).await;
+        let _ = conn.disconnect().await;
+        killed
+    };
+    let message = match tokio::time::timeout(connect_timeout, kill).await {
+        Ok(Ok(())) => return ServerCancellation::Killed { connection_id },
+        Ok(Err(error)) => error.to_string(),
+        Err(_) => format!("timed out after {connect_timeout:.1?}"),
+    };
+    ServerCancellation::KillFailed {
+        connection_id,
+        message,
+    }
+}
+
+async fn stream_query(
+    conn: &mut Conn,
+    request: QueryRequest,
+    fetch_warnings: bool,
+    server_text_values: bool,
+    events: &mpsc::Sender<StreamEvent>,
+) -> Result<(), QueryBackendError> {
+    let receiver_open = match request {
+        QueryRequest::Text(sql) => {
+            let result = conn.query_iter(sql).await.map_err(to_query_error)?;
+            stream_result_sets(result, server_text_values, events).await?
+        }
+        QueryRequest::Prepared { sql, params } => {
+            let result = conn
+                .exec_iter(sql.as_str(), Params::Positional(params))
+                .await
+                .map_err(to_query_error)?;
+            stream_result_sets(result, server_text_values, events).await?
+        }
+    };
+    if !receiver_open {
+        return Ok(());
+    }
+
+    let mut status = statement_status(
+        conn.affected_rows(),
+        conn.last_insert_id(),
+        &conn.info(),
+        conn.get_warnings(),
+    );
+    if fetch_warnings && status.warning_count > 0 {
+        status.warnings = conn
+            .query_map(
+                "SHOW WARNINGS",
+                |(level, code, message): (String, u32, String)| QueryWarning {
+                    level,
+                    code,
+                    message,
+                },
+            )
             .await
             .map_err(to_query_error)?;
-        Ok(MysqlStreamingRowStream::new(stream))
     }
+
+    let _ = events.send(StreamEvent::Completed(status)).await;
+    Ok(())
 }
 
-fn opts_from_profile(profile: &ConnectionProfile) -> OptsBuilder {
+async fn stream_result_sets<P: Protocol + Unpin>(
+    mut result: QueryResult<'_, 'static, P>,
+    server_text_values: bool,
+    events: &mpsc::Sender<StreamEvent>,
+) -> Result<bool, QueryBackendError> {
+    loop {
+        let Some(mut rows) = result.stream::<Row>().await.map_err(to_query_error)? else {
+            break;
+        };
+        let columns = rows
+            .columns_ref()
+            .iter()
+            .map(result_column_from_mysql)
+            .collect::<Vec<_>>();
+        // The OK packet captured with the stream only describes row-less result sets.
+        let set_status = if columns.is_empty() {
+            statement_status(
+                rows.affected_rows(),
+                rows.last_insert_id(),
+                &rows.info(),
+                rows.get_warnings(),
+            )
+        } else {
+            StatementStatus::default()
+        };
+        if events.send(StreamEvent::Columns(columns)).await.is_err() {
+            return Ok(false);
+        }
+
+        while let Some(row) = rows.next().await {
+            let row = row_to_query_row(row.map_err(to_query_error)?, server_text_values)?;
+            if events.send(StreamEvent::Row(row)).await.is_err() {
+                return Ok(false);
+            }
+        }
+        drop(rows);
+
+        if result.is_empty() {
+            break;
+        }
+        if events
+            .send(StreamEvent::ResultSetCompleted(set_status))
+            .await
+            .is_err()
+        {
+            return Ok(false);
+        }
+    }
+    result.drop_result().await.map_err(to_query_error)?;
+    Ok(true)
+}
+
+fn statement_status(
+    affected_rows: u64,
+    last_insert_id: Option<u64>,
+    info: &str,
+    warning_count: u16,
+) -> StatementStatus {
+    StatementStatus {
+        affected_rows,
+        last_insert_id,
+        info: (!info.is_empty()).then(|| info.to_string()),
+        warning_count,
+        warnings: Vec::new(),
+    }
+}
+
+fn opts_from_profile(
+    profile: &ConnectionProfile,
+    password: &ResolvedPassword,
+) -> Result<OptsBuilder, BackendError> {
+    let tunnel =
+        tunnel_for_profile(profile).map_err(|error| BackendError::new(error.to_string()))?;
+    let (host, port) = tunnel.as_ref().map_or_else(
+        || (profile.host.clone(), profile.port),
+        |tunnel| {
+            let local_addr = tunnel.local_addr();
+            (local_addr.ip().to_string(), local_addr.port())
+        },
+    );
     let mut builder = OptsBuilder::default()
-        .ip_or_hostname(profile.host.clone())
-        .tcp_port(profile.port)
+        .ip_or_hostname(host)
+        .tcp_port(port)
         .user(Some(profile.user.clone()));
 
-    if let Some(password) = resolve_password(profile) {
-        builder = builder.pass(Some(password));
+    if let Some(socket_path) = profile.socket_path() {
+        builder = builder.socket(Some(socket_path));
+    }
+
+    if let Some(password) = &password.0 {
+        builder = builder.pass(Some(password.clone()));
     }
 
     if let Some(database) = &profile.database {
         builder = builder.db_name(Some(database.clone()));
     }
 
-    if let Some(ssl_opts) = ssl_opts_from_profile(profile) {
+    // Setup statements also rerun after the pool resets a returned connection, which
+    // would otherwise drop the session variables they set.
+    let init_sql = profile.init_statements().collect::<Vec<_>>();
+    if !init_sql.is_empty() {
+        builder = builder.setup(init_sql);
+    }
+
+    if let Some(mut ssl_opts) = ssl_opts_from_profile(profile) {
+        // Through a tunnel the socket points at loopback, but the certificate still names
+        // the database host.
+        if tunnel.is_some() && ssl_opts.tls_hostname_override().is_none() {
+            ssl_opts = ssl_opts.with_danger_tls_hostname_override(Some(profile.host.clone()));
+        }
         builder = builder.ssl_opts(ssl_opts);
     }
 
-    if matches!(profile.tls_mode, TlsMode::Disabled) {
+    // A loopback tunnel must never be swapped for a local server's unix socket.
+    if matches!(profile.tls_mode, TlsMode::Disabled) || tunnel.is_some() {
         builder = builder.prefer_socket(false);
     }
 
-    builder
+    Ok(builder)
 }
 
-fn resolve_password(profile: &ConnectionProfile) -> Option<String> {
-    let env_password = std::env::var("MYR_DB_PASSWORD")
+fn resolve_password(profile: &ConnectionProfile) -> Result<Option<String>, PasswordError> {
+    let env_password = std::env::var(profile.password_env_var())
         .ok()
         .filter(|pw| !pw.is_empty());
 
     match profile.password_source {
-        PasswordSource::EnvVar => env_password,
+        PasswordSource::EnvVar => Ok(env_password),
         PasswordSource::Keyring => {
             if let Some(password) = load_keyring_password(profile) {
-                return Some(password);
+                return Ok(Some(password));
             }
 
             if let Some(password) = env_password {
                 store_keyring_password(profile, &password);
-                return Some(password);
+                return Ok(Some(password));
             }
 
-            None
+            Ok(None)
         }
+        PasswordSource::Command => password_from_command(profile).map(Some),
+        PasswordSource::File => password_from_file(profile).map(Some),
     }
 }
 
@@ -353,48 +1145,319 @@ fn keyring_entry(profile: &ConnectionProfile) -> Option<keyring::Entry> {
     keyring::Entry::new(service, account).ok()
 }
 
-fn row_to_query_row(row: Row) -> Result<QueryRow, QueryBackendError> {
-    row_values_to_strings(row.unwrap_raw()).map(QueryRow::new)
+fn result_column_from_mysql(column: &Column) -> ResultColumn {
+    let flags = column.flags();
+    let binary = column.character_set() == BINARY_CHARSET;
+    let non_empty =
+        |value: std::borrow::Cow<'_, str>| (!value.is_empty()).then(|| value.into_owned());
+
+    ResultColumn {
+        name: column.name_str().into_owned(),
+        sql_type: sql_type_from_mysql(column.column_type(), flags, binary),
+        length: column.column_length(),
+        decimals: column.decimals(),
+        not_null: flags.contains(ColumnFlags::NOT_NULL_FLAG),
+        primary_key: flags.contains(ColumnFlags::PRI_KEY_FLAG),
+        unsigned: flags.contains(ColumnFlags::UNSIGNED_FLAG),
+        binary: binary || flags.contains(ColumnFlags::BINARY_FLAG),
+        charset: column.character_set(),
+        origin_table: non_empty(column.org_table_str()),
+        origin_column: non_empty(column.org_name_str()),
+    }
+}
+
+fn sql_type_from_mysql(column_type: ColumnType, flags: ColumnFlags, binary: bool) -> SqlType {
+    match column_type {
+        ColumnType::MYSQL_TYPE_TINY => SqlType::TinyInt,
+        ColumnType::MYSQL_TYPE_SHORT => SqlType::SmallInt,
+        ColumnType::MYSQL_TYPE_INT24 => SqlType::MediumInt,
+        ColumnType::MYSQL_TYPE_LONG => SqlType::Int,
+        ColumnType::MYSQL_TYPE_LONGLONG => SqlType::BigInt,
+        ColumnType::MYSQL_TYPE_DECIMAL | ColumnType::MYSQL_TYPE_NEWDECIMAL => SqlType::Decimal,
+        ColumnType::MYSQL_TYPE_FLOAT => SqlType::Float,
+        ColumnType::MYSQL_TYPE_DOUBLE => SqlType::Double,
+        ColumnType::MYSQL_TYPE_BIT => SqlType::Bit,
+        ColumnType::MYSQL_TYPE_YEAR => SqlType::Year,
+        ColumnType::MYSQL_TYPE_DATE | ColumnType::MYSQL_TYPE_NEWDATE => SqlType::Date,
+        ColumnType::MYSQL_TYPE_TIME | ColumnType::MYSQL_TYPE_TIME2 => SqlType::Time,
+        ColumnType::MYSQL_TYPE_DATETIME | ColumnType::MYSQL_TYPE_DATETIME2 => SqlType::DateTime,
+        ColumnType::MYSQL_TYPE_TIMESTAMP | ColumnType::MYSQL_TYPE_TIMESTAMP2 => SqlType::Timestamp,
+        ColumnType::MYSQL_TYPE_ENUM => SqlType::Enum,
+        ColumnType::MYSQL_TYPE_SET => SqlType::Set,
+        ColumnType::MYSQL_TYPE_STRING if flags.contains(ColumnFlags::ENUM_FLAG) => SqlType::Enum,
+        ColumnType::MYSQL_TYPE_STRING if flags.contains(ColumnFlags::SET_FLAG) => SqlType::Set,
+        ColumnType::MYSQL_TYPE_STRING if binary => SqlType::Binary,
+        ColumnType::MYSQL_TYPE_STRING => SqlType::Char,
+        ColumnType::MYSQL_TYPE_VARCHAR | ColumnType::MYSQL_TYPE_VAR_STRING if binary => {
+            SqlType::VarBinary
+        }
+        ColumnType::MYSQL_TYPE_VARCHAR | ColumnType::MYSQL_TYPE_VAR_STRING => SqlType::VarChar,
+        ColumnType::MYSQL_TYPE_TINY_BLOB
+        | ColumnType::MYSQL_TYPE_MEDIUM_BLOB
+        | ColumnType::MYSQL_TYPE_LONG_BLOB
+        | ColumnType::MYSQL_TYPE_BLOB
+            if binary =>
+        {
+            SqlType::Blob
+        }
+        ColumnType::MYSQL_TYPE_TINY_BLOB
+        | ColumnType::MYSQL_TYPE_MEDIUM_BLOB
+        | ColumnType::MYSQL_TYPE_LONG_BLOB
+        | ColumnType::MYSQL_TYPE_BLOB => SqlType::Text,
+        ColumnType::MYSQL_TYPE_JSON => SqlType::Json,
+        ColumnType::MYSQL_TYPE_GEOMETRY => SqlType::Geometry,
+        ColumnType::MYSQL_TYPE_NULL => SqlType::Null,
+        _ => SqlType::Unknown,
+    }
+}
+
+fn row_to_query_row(row: Row, server_text_values: bool) -> Result<QueryRow, QueryBackendError> {
+    let columns = row.columns();
+    row_values_to_query_values(row.unwrap_raw(), &columns, server_text_values)
+        .map(|values| QueryRow { values })
 }
 
-fn row_values_to_strings(values: Vec<Option<Value>>) -> Result<Vec<String>, QueryBackendError> {
+// With `server_text_values`, text-protocol values stay exactly as the server sent them,
+// which is what the legacy all-string output printed.
+fn row_values_to_query_values(
+    values: Vec<Option<Value>>,
+    columns: &[Column],
+    server_text_values: bool,
+) -> Result<Vec<QueryValue>, QueryBackendError> {
     values
         .into_iter()
         .enumerate()
         .map(|(index, value)| {
-            value.map(mysql_value_to_string).ok_or_else(|| {
+            let value = value.ok_or_else(|| {
                 QueryBackendError::new(format!(
                     "row decoding failed: missing value at column index {index}"
                 ))
+            })?;
+            Ok(match (value, columns.get(index)) {
+                (Value::Bytes(bytes), _) if server_text_values => {
+                    QueryValue::Text(String::from_utf8_lossy(&bytes).into_owned())
+                }
+                (value, Some(column)) => mysql_value_to_query_value(value, column),
+                (value, None) => mysql_value_to_query_value(
+                    value,
+                    &Column::new(ColumnType::MYSQL_TYPE_VAR_STRING),
+                ),
             })
         })
         .collect()
 }
 
-fn mysql_value_to_string(value: Value) -> String {
+fn mysql_value_to_query_value(value: Value, column: &Column) -> QueryValue {
+    let column_type = column.column_type();
     match value {
-        Value::NULL => "NULL".to_string(),
-        Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
-        Value::Int(value) => value.to_string(),
-        Value::UInt(value) => value.to_string(),
-        Value::Float(value) => value.to_string(),
-        Value::Double(value) => value.to_string(),
-        Value::Date(year, month, day, hour, minute, second, micros) => format!(
-            "{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02}.{:06}",
-            micros
+        Value::NULL => QueryValue::Null,
+        Value::Int(value) => QueryValue::Int(value),
+        Value::UInt(value) => QueryValue::UInt(value),
+        Value::Float(value) => QueryValue::Float(f64::from(value)),
+        Value::Double(value) => QueryValue::Float(value),
+        Value::Date(year, month, day, hour, minute, second, micros) => {
+            if column_type == ColumnType::MYSQL_TYPE_DATE {
+                QueryValue::Date { year, month, day }
+            } else {
+                QueryValue::DateTime {
+                    year,
+                    month,
+                    day,
+                    hour,
+                    minute,
+                    second,
+                    micros,
+                }
+            }
+        }
+        Value::Time(negative, days, hours, minutes, seconds, micros) => QueryValue::Time {
+            negative,
+            days,
+            hours,
+            minutes,
+            seconds,
+            micros,
+        },
+        Value::Bytes(bytes) => text_protocol_bytes_to_query_value(bytes, column),
+    }
+}
+
+fn query_value_to_mysql_value(value: &QueryValue) -> Value {
+    match value {
+        QueryValue::Null => Value::NULL,
+        QueryValue::Int(value) => Value::Int(*value),
+        QueryValue::UInt(value) => Value::UInt(*value),
+        QueryValue::Float(value) => Value::Double(*value),
+        QueryValue::Decimal(text)
+        | QueryValue::Text(text)
+        | QueryValue::Json(text)
+        | QueryValue::Truncated { preview: text, .. } => Value::Bytes(text.as_bytes().to_vec()),
+        QueryValue::Bytes(bytes) => Value::Bytes(bytes.clone()),
+        QueryValue::Date { year, month, day } => Value::Date(*year, *month, *day, 0, 0, 0, 0),
+        QueryValue::DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            micros,
+        } => Value::Date(*year, *month, *day, *hour, *minute, *second, *micros),
+        QueryValue::Time {
+            negative,
+            days,
+            hours,
+            minutes,
+            seconds,
+            micros,
+        } => Value::Time(*negative, *days, *hours, *minutes, *seconds, *micros),
+    }
+}
+
+fn text_protocol_bytes_to_query_value(bytes: Vec<u8>, column: &Column) -> QueryValue {
+    let column_type = column.column_type();
+    let unsigned = column.flags().contains(ColumnFlags::UNSIGNED_FLAG);
+    let parsed = match column_type {
+        ColumnType::MYSQL_TYPE_TINY
+        | ColumnType::MYSQL_TYPE_SHORT
+        | ColumnType::MYSQL_TYPE_INT24
+        | ColumnType::MYSQL_TYPE_LONG
+        | ColumnType::MYSQL_TYPE_LONGLONG
+        | ColumnType::MYSQL_TYPE_YEAR => {
+            let text = String::from_utf8_lossy(&bytes);
+            if unsigned {
+                text.parse::<u64>().ok().map(QueryValue::UInt)
+            } else {
+                text.parse::<i64>().ok().map(QueryValue::Int)
+            }
+        }
+        ColumnType::MYSQL_TYPE_FLOAT | ColumnType::MYSQL_TYPE_DOUBLE => {
+            String::from_utf8_lossy(&bytes)
+                .parse::<f64>()
+                .ok()
+                .map(QueryValue::Float)
+        }
+        ColumnType::MYSQL_TYPE_DECIMAL | ColumnType::MYSQL_TYPE_NEWDECIMAL => Some(
+            QueryValue::Decimal(String::from_utf8_lossy(&bytes).into_owned()),
         ),
-        Value::Time(is_negative, days, hours, minutes, seconds, micros) => {
-            let sign = if is_negative { "-" } else { "" };
-            format!(
-                "{sign}{days:03} {hours:02}:{minutes:02}:{seconds:02}.{:06}",
-                micros
-            )
+        ColumnType::MYSQL_TYPE_DATE | ColumnType::MYSQL_TYPE_NEWDATE => {
+            parse_date_text(&String::from_utf8_lossy(&bytes))
+        }
+        ColumnType::MYSQL_TYPE_DATETIME
+        | ColumnType::MYSQL_TYPE_DATETIME2
+        | ColumnType::MYSQL_TYPE_TIMESTAMP
+        | ColumnType::MYSQL_TYPE_TIMESTAMP2 => {
+            parse_datetime_text(&String::from_utf8_lossy(&bytes))
+        }
+        ColumnType::MYSQL_TYPE_TIME | ColumnType::MYSQL_TYPE_TIME2 => {
+            parse_time_text(&String::from_utf8_lossy(&bytes))
+        }
+        ColumnType::MYSQL_TYPE_JSON => Some(QueryValue::Json(
+            String::from_utf8_lossy(&bytes).into_owned(),
+        )),
+        ColumnType::MYSQL_TYPE_BIT | ColumnType::MYSQL_TYPE_GEOMETRY => {
+            return QueryValue::Bytes(bytes);
+        }
+        _ => None,
+    };
+
+    parsed.unwrap_or_else(|| {
+        if column.character_set() == BINARY_CHARSET {
+            QueryValue::Bytes(bytes)
+        } else {
+            match String::from_utf8(bytes) {
+                Ok(text) => QueryValue::Text(text),
+                Err(error) => QueryValue::Bytes(error.into_bytes()),
+            }
+        }
+    })
+}
+
+fn parse_date_text(text: &str) -> Option<QueryValue> {
+    let mut parts = text.trim().splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Some(QueryValue::Date { year, month, day })
+}
+
+fn parse_datetime_text(text: &str) -> Option<QueryValue> {
+    let (date, time) = text.trim().split_once(' ')?;
+    let QueryValue::Date { year, month, day } = parse_date_text(date)? else {
+        return None;
+    };
+    let QueryValue::Time {
+        negative: false,
+        days: 0,
+        hours,
+        minutes,
+        seconds,
+        micros,
+    } = parse_time_text(time)?
+    else {
+        return None;
+    };
+    Some(QueryValue::DateTime {
+        year,
+        month,
+        day,
+        hour: hours,
+        minute: minutes,
+        second: seconds,
+        micros,
+    })
+}
+
+fn parse_time_text(text: &str) -> Option<QueryValue> {
+    let text = text.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (clock, fraction) = match text.split_once('.') {
+        Some((clock, fraction)) => (clock, Some(fraction)),
+        None => (text, None),
+    };
+    let mut parts = clock.splitn(3, ':');
+    let total_hours = parts.next()?.parse::<u32>().ok()?;
+    let minutes = parts.next()?.parse().ok()?;
+    let seconds = parts.next()?.parse().ok()?;
+    let micros = match fraction {
+        Some(fraction) if !fraction.is_empty() && fraction.len() <= 6 => {
+            let padded = format!("{fraction:0<6}");
+            padded.parse().ok()?
         }
+        Some(_) => return None,
+        None => 0,
+    };
+    Some(QueryValue::Time {
+        negative,
+        days: total_hours / 24,
+        hours: u8::try_from(total_hours % 24).ok()?,
+        minutes,
+        seconds,
+        micros,
+    })
+}
+
+// Retry decisions key off the error number, so transport failures are reported with
+// the `CR_*` code libmysqlclient would use instead of only their message text.
+fn mysql_error_code(error: &mysql_async::Error) -> Option<u16> {
+    match error {
+        mysql_async::Error::Server(error) => Some(error.code),
+        mysql_async::Error::Io(IoError::Io(error)) => Some(match error.kind() {
+            io::ErrorKind::ConnectionRefused => CR_CONN_HOST_ERROR,
+            io::ErrorKind::NotFound => CR_CONNECTION_ERROR,
+            _ => CR_SERVER_LOST,
+        }),
+        mysql_async::Error::Driver(DriverError::ConnectionClosed) => Some(CR_SERVER_LOST),
+        mysql_async::Error::Driver(DriverError::PoolDisconnected) => Some(CR_SERVER_GONE_ERROR),
+        _ => None,
     }
 }
 
 fn to_connection_error(error: mysql_async::Error) -> BackendError {
-    BackendError::new(error.to_string())
+    BackendError::new(error.to_string()).with_code(mysql_error_code(&error))
 }
 
 fn to_schema_error(error: mysql_async::Error) -> SchemaBackendError {
@@ -402,36 +1465,213 @@ fn to_schema_error(error: mysql_async::Error) -> SchemaBackendError {
 }
 
 fn to_query_error(error: mysql_async::Error) -> QueryBackendError {
-    QueryBackendError::new(error.to_string())
+    QueryBackendError::new(error.to_string()).with_code(mysql_error_code(&error))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::io;
+    use std::time::Duration;
     #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    use myr_core::profiles::{ConnectionProfile, TlsMode};
-    use mysql_async::Value;
+    use myr_core::profiles::{ConnectionProfile, PasswordSource, ReplicaEndpoint, TlsMode};
+    use myr_core::query_runner::{QueryBackend, QueryValue, ServerCancellation};
+    use myr_core::retry_policy::{CR_CONN_HOST_ERROR, CR_SERVER_GONE_ERROR, CR_SERVER_LOST};
+    use mysql_async::consts::{ColumnFlags, ColumnType};
+    use mysql_async::{Column, DriverError, Opts, ServerError, Value};
+
+    use myr_core::query_runner::SqlType;
+    use myr_core::server_capabilities::ServerCapabilities;
 
     use super::{
-        client_identity_from_profile, mysql_value_to_string, opts_from_profile,
-        profile_requests_tls, row_values_to_strings,
+        client_identity_from_profile, kill_query, mysql_error_code, mysql_value_to_query_value,
+        opts_from_profile, profile_requests_tls, query_value_to_mysql_value,
+        result_column_from_mysql, routes_to_replica, row_values_to_query_values, to_query_error,
+        MysqlDataBackend, QueryEndpoint, ResolvedPassword, BINARY_CHARSET,
     };
 
     #[test]
-    fn value_conversion_is_human_readable() {
-        assert_eq!(mysql_value_to_string(Value::NULL), "NULL");
+    fn column_metadata_maps_type_flags_charset_and_origin() {
+        let column = Column::new(ColumnType::MYSQL_TYPE_LONGLONG)
+            .with_name(b"user_id")
+            .with_org_name(b"id")
+            .with_org_table(b"users")
+            .with_flags(
+                ColumnFlags::NOT_NULL_FLAG | ColumnFlags::PRI_KEY_FLAG | ColumnFlags::UNSIGNED_FLAG,
+            )
+            .with_character_set(BINARY_CHARSET)
+            .with_column_length(20);
+
+        let mapped = result_column_from_mysql(&column);
+        assert_eq!(mapped.name, "user_id");
+        assert_eq!(mapped.sql_type, SqlType::BigInt);
+        assert_eq!(mapped.length, 20);
+        assert!(mapped.not_null && mapped.primary_key && mapped.unsigned && mapped.binary);
+        assert_eq!(mapped.origin_table.as_deref(), Some("users"));
+        assert_eq!(mapped.origin_column.as_deref(), Some("id"));
+
+        let expression = Column::new(ColumnType::MYSQL_TYPE_VAR_STRING)
+            .with_name(b"label")
+            .with_character_set(255);
+        let mapped = result_column_from_mysql(&expression);
+        assert_eq!(mapped.sql_type, SqlType::VarChar);
+        assert!(!mapped.binary && !mapped.not_null);
+        assert_eq!(mapped.origin_table, None);
+    }
+
+    #[test]
+    fn text_protocol_values_are_typed_by_column_metadata() {
+        let bigint = Column::new(ColumnType::MYSQL_TYPE_LONGLONG);
+        let unsigned =
+            Column::new(ColumnType::MYSQL_TYPE_LONGLONG).with_flags(ColumnFlags::UNSIGNED_FLAG);
+        let decimal = Column::new(ColumnType::MYSQL_TYPE_NEWDECIMAL);
+        let text = Column::new(ColumnType::MYSQL_TYPE_VAR_STRING);
+
+        assert_eq!(
+            mysql_value_to_query_value(Value::Bytes(b"-8".to_vec()), &bigint),
+            QueryValue::Int(-8)
+        );
+        assert_eq!(
+            mysql_value_to_query_value(Value::Bytes(b"18446744073709551615".to_vec()), &unsigned),
+            QueryValue::UInt(u64::MAX)
+        );
+        assert_eq!(
+            mysql_value_to_query_value(Value::Bytes(b"10.50".to_vec()), &decimal),
+            QueryValue::Decimal("10.50".to_string())
+        );
         assert_eq!(
-            mysql_value_to_string(Value::Bytes(b"hello".to_vec())),
-            "hello".to_string()
+            mysql_value_to_query_value(Value::Bytes(b"hello".to_vec()), &text),
+            QueryValue::Text("hello".to_string())
+        );
+        assert_eq!(
+            mysql_value_to_query_value(Value::NULL, &text),
+            QueryValue::Null
+        );
+    }
+
+    #[test]
+    fn text_protocol_temporal_values_are_parsed() {
+        let date = Column::new(ColumnType::MYSQL_TYPE_DATE);
+        let datetime = Column::new(ColumnType::MYSQL_TYPE_DATETIME);
+        let time = Column::new(ColumnType::MYSQL_TYPE_TIME);
+
+        assert_eq!(
+            mysql_value_to_query_value(Value::Bytes(b"2024-02-29".to_vec()), &date),
+            QueryValue::Date {
+                year: 2024,
+                month: 2,
+                day: 29
+            }
+        );
+        assert_eq!(
+            mysql_value_to_query_value(
+                Value::Bytes(b"2024-02-29 13:14:15.250000".to_vec()),
+                &datetime
+            ),
+            QueryValue::DateTime {
+                year: 2024,
+                month: 2,
+                day: 29,
+                hour: 13,
+                minute: 14,
+                second: 15,
+                micros: 250_000
+            }
+        );
+        let parsed_time = mysql_value_to_query_value(Value::Bytes(b"-838:59:59".to_vec()), &time);
+        assert_eq!(parsed_time.to_string(), "-838:59:59");
+    }
+
+    // Golden values: the server's text for each type, which the all-string output printed
+    // verbatim before values were typed.
+    #[test]
+    fn server_text_values_reproduce_the_legacy_string_output() {
+        let golden: [(ColumnType, &str); 12] = [
+            (
+                ColumnType::MYSQL_TYPE_DATETIME,
+                "2024-02-29 13:14:15.000000",
+            ),
+            (ColumnType::MYSQL_TYPE_DATETIME, "2024-02-29 13:14:15"),
+            (ColumnType::MYSQL_TYPE_TIMESTAMP, "2024-02-29 13:14:15.120"),
+            (ColumnType::MYSQL_TYPE_DATE, "2024-02-29"),
+            (ColumnType::MYSQL_TYPE_TIME, "26:00:00"),
+            (ColumnType::MYSQL_TYPE_TIME, "-838:59:59.000000"),
+            (ColumnType::MYSQL_TYPE_DOUBLE, "1e100"),
+            (ColumnType::MYSQL_TYPE_FLOAT, "0.1"),
+            (ColumnType::MYSQL_TYPE_NEWDECIMAL, "10.50"),
+            (ColumnType::MYSQL_TYPE_LONGLONG, "-8"),
+            (ColumnType::MYSQL_TYPE_JSON, r#"{"a": 1}"#),
+            (ColumnType::MYSQL_TYPE_VAR_STRING, "café"),
+        ];
+        let columns = golden
+            .iter()
+            .map(|(column_type, _)| Column::new(*column_type))
+            .collect::<Vec<_>>();
+        let values = golden
+            .iter()
+            .map(|(_, text)| Some(Value::Bytes(text.as_bytes().to_vec())))
+            .chain([Some(Value::NULL)])
+            .collect::<Vec<_>>();
+
+        let legacy = row_values_to_query_values(values.clone(), &columns, true)
+            .expect("row should decode")
+            .iter()
+            .map(QueryValue::to_compat_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            legacy,
+            golden
+                .iter()
+                .map(|(_, text)| (*text).to_string())
+                .chain(["NULL".to_string()])
+                .collect::<Vec<_>>()
+        );
+
+        let typed = row_values_to_query_values(values, &columns, false).expect("row should decode");
+        assert_eq!(typed[0].to_compat_string(), "2024-02-29 13:14:15");
+        assert_eq!(typed[9], QueryValue::Int(-8));
+    }
+
+    #[test]
+    fn binary_columns_stay_bytes_and_unparseable_values_fall_back_to_text() {
+        let blob = Column::new(ColumnType::MYSQL_TYPE_BLOB).with_character_set(BINARY_CHARSET);
+        let date = Column::new(ColumnType::MYSQL_TYPE_DATE);
+
+        assert_eq!(
+            mysql_value_to_query_value(Value::Bytes(vec![0, 159, 146, 150]), &blob),
+            QueryValue::Bytes(vec![0, 159, 146, 150])
+        );
+        assert_eq!(
+            mysql_value_to_query_value(Value::Bytes(b"not-a-date".to_vec()), &date),
+            QueryValue::Text("not-a-date".to_string())
+        );
+    }
+
+    #[test]
+    fn prepared_statement_parameters_map_to_binary_protocol_values() {
+        assert_eq!(query_value_to_mysql_value(&QueryValue::Null), Value::NULL);
+        assert_eq!(
+            query_value_to_mysql_value(&QueryValue::Int(-3)),
+            Value::Int(-3)
+        );
+        assert_eq!(
+            query_value_to_mysql_value(&QueryValue::from("a@example.com")),
+            Value::Bytes(b"a@example.com".to_vec())
+        );
+        assert_eq!(
+            query_value_to_mysql_value(&QueryValue::Date {
+                year: 2024,
+                month: 2,
+                day: 29
+            }),
+            Value::Date(2024, 2, 29, 0, 0, 0, 0)
         );
-        assert_eq!(mysql_value_to_string(Value::Int(-8)), "-8");
-        assert_eq!(mysql_value_to_string(Value::UInt(8)), "8");
     }
 
     #[test]
     fn row_value_mapping_reports_missing_columns_without_panicking() {
-        let error = row_values_to_strings(vec![Some(Value::Int(1)), None])
+        let error = row_values_to_query_values(vec![Some(Value::Int(1)), None], &[], false)
             .expect_err("missing row values should return an error");
         assert_eq!(
             error.to_string(),
@@ -445,8 +1685,216 @@ mod tests {
         profile.port = 3307;
         profile.database = Some("app".to_string());
 
-        let _opts = opts_from_profile(&profile);
-        // Construction is the assertion here; mysql_async exposes limited stable introspection.
+        let opts = Opts::from(
+            opts_from_profile(&profile, &ResolvedPassword::default())
+                .expect("direct profiles need no tunnel"),
+        );
+        assert_eq!(opts.ip_or_hostname(), "127.0.0.1");
+        assert_eq!(opts.tcp_port(), 3307);
+        assert_eq!(opts.db_name(), Some("app"));
+        assert_eq!(opts.socket(), None);
+        assert!(opts.setup().is_empty());
+    }
+
+    #[test]
+    fn init_sql_runs_as_setup_on_every_pooled_connection() {
+        let mut profile = ConnectionProfile::new("prod", "127.0.0.1", "app");
+        profile.init_sql = vec![
+            "SET time_zone = '+00:00'".to_string(),
+            " ".to_string(),
+            "SET SESSION TRANSACTION READ ONLY".to_string(),
+        ];
+
+        let opts = Opts::from(
+            opts_from_profile(&profile, &ResolvedPassword::default())
+                .expect("direct profiles need no tunnel"),
+        );
+        assert_eq!(
+            opts.setup(),
+            [
+                "SET time_zone = '+00:00'".to_string(),
+                "SET SESSION TRANSACTION READ ONLY".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn password_sources_feed_the_connection_options() {
+        let mut profile = ConnectionProfile::new("vault", "127.0.0.1", "app");
+        profile.password_source = PasswordSource::Command;
+        profile.password_command = Some("echo from-command".to_string());
+        let password =
+            ResolvedPassword::resolve_blocking(&profile).expect("command should succeed");
+        let opts = Opts::from(opts_from_profile(&profile, &password).expect("direct profile"));
+        assert_eq!(opts.pass(), Some("from-command"));
+
+        profile.password_source = PasswordSource::File;
+        profile.password_file = Some("/nonexistent/myr/db.pass".to_string());
+        let error =
+            ResolvedPassword::resolve_blocking(&profile).expect_err("missing password file");
+        assert!(error
+            .to_string()
+            .contains("failed to read password file /nonexistent/myr/db.pass"));
+    }
+
+    #[test]
+    fn only_non_locking_reads_route_to_replicas() {
+        assert!(routes_to_replica("SELECT * FROM orders"));
+        assert!(routes_to_replica("show tables"));
+        assert!(!routes_to_replica("UPDATE orders SET state = 'paid'"));
+        assert!(!routes_to_replica("SELECT * FROM orders FOR UPDATE"));
+        assert!(!routes_to_replica(
+            "select * from orders lock in share mode"
+        ));
+        assert!(!routes_to_replica("SELECT 1; DELETE FROM orders"));
+    }
+
+    #[tokio::test]
+    async fn kill_query_gives_up_after_the_connect_timeout() {
+        // Accepts the TCP connection but never sends a handshake.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let mut profile = ConnectionProfile::new("stuck", "127.0.0.1", "app");
+        profile.port = listener.local_addr().expect("local addr").port();
+        profile.tls_mode = TlsMode::Disabled;
+        let opts = Opts::from(
+            opts_from_profile(&profile, &ResolvedPassword::default()).expect("direct profile"),
+        );
+
+        assert_eq!(
+            kill_query(opts, Duration::from_millis(50), 42).await,
+            ServerCancellation::KillFailed {
+                connection_id: 42,
+                message: "timed out after 50.0ms".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn unresponsive_replica_is_skipped_after_the_connect_timeout() {
+        // Accepts the TCP connection but never sends a handshake.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let port = listener.local_addr().expect("local addr").port();
+        let mut profile = ConnectionProfile::new("shop", "127.0.0.1", "app");
+        profile.tls_mode = TlsMode::Disabled;
+        profile.connect_timeout_secs = Some(1);
+        profile.max_replica_lag_secs = Some(5);
+        profile.replicas = vec![ReplicaEndpoint::new("127.0.0.1", Some(port))];
+        let backend = MysqlDataBackend::from_profile(&profile).expect("backend");
+
+        let label = format!("127.0.0.1:{port}");
+        assert_eq!(
+            backend.check_replicas().await,
+            [(
+                label.clone(),
+                Some("lag check timed out after 1.0s".to_string())
+            )]
+        );
+        assert_eq!(
+            backend.skipped_replicas(),
+            [format!("{label} (lag check timed out after 1.0s)")]
+        );
+    }
+
+    #[tokio::test]
+    async fn mariadb_time_limit_does_not_keep_reads_off_the_replicas() {
+        // The replica accepts but never handshakes, and nothing listens on the primary.
+        let replica = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let replica_port = replica.local_addr().expect("local addr").port();
+        let primary_port = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|closed| closed.local_addr())
+            .expect("free port")
+            .port();
+        let mut profile = ConnectionProfile::new("shop", "127.0.0.1", "app");
+        profile.port = primary_port;
+        profile.tls_mode = TlsMode::Disabled;
+        profile.connect_timeout_secs = Some(1);
+        profile.replicas = vec![ReplicaEndpoint::new("127.0.0.1", Some(replica_port))];
+        let backend = MysqlDataBackend::from_profile(&profile)
+            .expect("backend")
+            .with_server_capabilities(ServerCapabilities::from_version_string(
+                "10.11.6-MariaDB-log",
+            ))
+            .with_max_execution_time(Some(Duration::from_millis(2_500)));
+        assert!(backend
+            .server_limited_sql("SELECT 1")
+            .starts_with("SET STATEMENT max_statement_time="));
+
+        let started =
+            tokio::time::timeout(Duration::from_secs(10), backend.start_query("SELECT 1"))
+                .await
+                .expect("query should give up");
+        assert!(started.is_err(), "nothing should serve the query");
+        assert_eq!(
+            backend.skipped_replicas(),
+            [format!(
+                "127.0.0.1:{replica_port} (connect timed out after 1.0s)"
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn replicas_get_their_own_pools_labelled_by_endpoint() {
+        let mut profile = ConnectionProfile::new("shop", "db-primary", "app");
+        let plain = MysqlDataBackend::from_profile(&profile).expect("backend");
+        assert!(!plain.has_replicas());
+
+        profile.replicas = vec![
+            ReplicaEndpoint::new("db-r1", None),
+            ReplicaEndpoint::new("db-r2", Some(3307)),
+        ];
+        let backend = MysqlDataBackend::from_profile(&profile).expect("backend");
+        assert!(backend.has_replicas());
+        assert_eq!(
+            backend
+                .replicas
+                .iter()
+                .map(|replica| replica.label.as_str())
+                .collect::<Vec<_>>(),
+            ["db-r1:3306", "db-r2:3307"]
+        );
+        assert_eq!(backend.last_endpoint(), None);
+        assert_eq!(
+            QueryEndpoint::Replica("db-r1:3306".to_string()).to_string(),
+            "replica db-r1:3306"
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn password_command_runs_once_for_the_primary_and_its_replicas() {
+        let dir = tempfile::TempDir::new().expect("temp dir");
+        let counter = dir.path().join("runs");
+        let mut profile = ConnectionProfile::new("vault", "db-primary", "app");
+        profile.password_source = PasswordSource::Command;
+        profile.password_command = Some(format!("echo run >> '{}'; echo token", counter.display()));
+        profile.replicas = vec![
+            ReplicaEndpoint::new("db-r1", None),
+            ReplicaEndpoint::new("db-r2", None),
+        ];
+
+        let password = ResolvedPassword::resolve(&profile)
+            .await
+            .expect("command should succeed");
+        MysqlDataBackend::from_resolved(&profile, &password).expect("backend");
+        MysqlDataBackend::from_profile(&profile).expect("backend");
+
+        let runs = std::fs::read_to_string(&counter).expect("command ran");
+        assert_eq!(runs.lines().count(), 2);
+    }
+
+    #[test]
+    fn socket_profiles_connect_through_the_socket_path() {
+        let mut profile = ConnectionProfile::new("local", "localhost", "root");
+        profile.socket_path = Some("/var/run/mysqld/mysqld.sock".to_string());
+        profile.ssh_host = Some("bastion.example.com".to_string());
+
+        let opts = Opts::from(
+            opts_from_profile(&profile, &ResolvedPassword::default())
+                .expect("socket profiles need no tunnel"),
+        );
+        assert_eq!(opts.socket(), Some("/var/run/mysqld/mysqld.sock"));
+        assert_eq!(opts.ip_or_hostname(), "localhost");
     }
 
     #[test]
@@ -477,6 +1925,26 @@ mod tests {
         assert!(client_identity_from_profile(&profile).is_some());
     }
 
+    #[test]
+    fn error_codes_cover_server_and_transport_failures() {
+        let server = mysql_async::Error::Server(ServerError {
+            code: 1213,
+            message: "Deadlock found when trying to get lock".to_string(),
+            state: "40001".to_string(),
+        });
+        assert_eq!(mysql_error_code(&server), Some(1213));
+
+        let refused = mysql_async::Error::from(io::Error::from(io::ErrorKind::ConnectionRefused));
+        assert_eq!(mysql_error_code(&refused), Some(CR_CONN_HOST_ERROR));
+        let reset = mysql_async::Error::from(io::Error::from(io::ErrorKind::ConnectionReset));
+        assert_eq!(mysql_error_code(&reset), Some(CR_SERVER_LOST));
+
+        let pool = mysql_async::Error::Driver(DriverError::PoolDisconnected);
+        assert_eq!(to_query_error(pool).code(), Some(CR_SERVER_GONE_ERROR));
+        let mixed = mysql_async::Error::Driver(DriverError::MixedParams);
+        assert_eq!(mysql_error_code(&mixed), None);
+    }
+
     #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
     fn keyring_smoke_enabled() -> bool {
         matches!(
@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use myr_core::profiles::ConnectionProfile;
+use thiserror::Error;
+
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Error)]
+pub enum PasswordError {
+    #[error("password_source = \"{source_name}\" needs `{setting}` in the profile")]
+    MissingSetting {
+        source_name: &'static str,
+        setting: &'static str,
+    },
+    #[error("failed to start password command `{command}`: {source}")]
+    Spawn {
+        command: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("password command `{command}` failed: {message}")]
+    CommandFailed { command: String, message: String },
+    #[error("password command `{command}` did not finish within {timeout:.1?}")]
+    CommandTimeout { command: String, timeout: Duration },
+    #[error("failed to read password file {path}: {source}")]
+    FileRead {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error(
+        "password file {path} is accessible by group or others (mode {mode:03o}); restrict it with `chmod 600`"
+    )]
+    FilePermissions { path: PathBuf, mode: u32 },
+    #[error("password file {path} is empty")]
+    EmptyFile { path: PathBuf },
+}
+
+// Runs `password_command` through the shell and takes the first line of its stdout, the
+// convention `pass`, `op read` and `vault kv get -field=...` all follow.
+pub fn password_from_command(profile: &ConnectionProfile) -> Result<String, PasswordError> {
+    static CACHE: OnceLock<Mutex<HashMap<String, (String, Instant)>>> = OnceLock::new();
+
+    let command =
+        non_empty(profile.password_command.as_deref()).ok_or(PasswordError::MissingSetting {
+            source_name: "command",
+            setting: "password_command",
+        })?;
+    let ttl = profile.password_cache_ttl();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(ttl) = ttl {
+        let cached = cache.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((password, fetched_at)) = cached.get(command) {
+            if fetched_at.elapsed() < ttl {
+                return Ok(password.clone());
+            }
+        }
+    }
+
+    let password = run_password_command(command, profile.password_command_timeout())?;
+    if ttl.is_some() {
+        cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(command.to_string(), (password.clone(), Instant::now()));
+    }
+    Ok(password)
+}
+
+// Refuses files other users could read, the same rule ssh applies to private keys.
+pub fn password_from_file(profile: &ConnectionProfile) -> Result<String, PasswordError> {
+    let raw_path =
+        non_empty(profile.password_file.as_deref()).ok_or(PasswordError::MissingSetting {
+            source_name: "file",
+            setting: "password_file",
+        })?;
+    let path = expand_home(raw_path);
+    let read_error = |source| PasswordError::FileRead {
+        path: path.clone(),
+        source,
+    };
+    let metadata = fs::metadata(&path).map_err(read_error)?;
+    check_private_mode(&path, &metadata)?;
+
+    let contents = fs::read_to_string(&path).map_err(read_error)?;
+    first_line(&contents).ok_or(PasswordError::EmptyFile { path })
+}
+
+fn run_password_command(command: &str, timeout: Duration) -> Result<String, PasswordError> {
+    let spawn_error = |source| PasswordError::Spawn {
+        command: command.to_string(),
+        source,
+    };
+    let mut child = shell(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(spawn_error)?;
+    // Drained on their own threads so a chatty command cannot fill a pipe and stall.
+    let stdout = child.stdout.take().map(drain);
+    let stderr = child.stderr.take().map(drain);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(spawn_error)? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(PasswordError::CommandTimeout {
+                command: command.to_string(),
+                timeout,
+            });
+        }
+        thread::sleep(COMMAND_POLL_INTERVAL);
+    };
+
+    let (stdout, stderr) = (collect(stdout, deadline), collect(stderr, deadline));
+    let failed = |message: String| PasswordError::CommandFailed {
+        command: command.to_string(),
+        message,
+    };
+    if !status.success() {
+        return Err(failed(failure_message(status, &stderr)));
+    }
+    first_line(&stdout).ok_or_else(|| failed("it printed no password".to_string()))
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.args(["/C", command]);
+    shell
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.args(["-c", command]);
+    shell
+}
+
+fn drain(mut reader: impl Read + Send + 'static) -> Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut chunk = [0; 4096];
+        while let Ok(read @ 1..) = reader.read(&mut chunk) {
+            if sender.send(chunk[..read].to_vec()).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+// A background process started by the command can hold the pipe open after the command
+// itself exits, so output is only gathered until the deadline.
+fn collect(output: Option<Receiver<Vec<u8>>>, deadline: Instant) -> String {
+    let mut bytes = Vec::new();
+    if let Some(output) = output {
+        while let Ok(chunk) =
+            output.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        {
+            bytes.extend(chunk);
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn failure_message(status: ExitStatus, stderr: &str) -> String {
+    stderr
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map_or_else(|| format!("exited with {status}"), str::to_string)
+}
+
+fn first_line(contents: &str) -> Option<String> {
+    contents
+        .lines()
+        .next()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(unix)]
+fn check_private_mode(path: &Path, metadata: &fs::Metadata) -> Result<(), PasswordError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = metadata.permissions().mode() & 0o777;
+    if mode & 0o077 != 0 {
+        return Err(PasswordError::FilePermissions {
+            path: path.to_path_buf(),
+            mode,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_private_mode(_path: &Path, _metadata: &fs::Metadata) -> Result<(), PasswordError> {
+    Ok(())
+}
+
+// The file is opened here rather than by a shell, so `~/` is expanded by hand.
+fn expand_home(raw: &str) -> PathBuf {
+    match raw.strip_prefix("~/") {
+        Some(rest) => std::env::var_os("HOME")
+            .map_or_else(|| PathBuf::from(raw), |home| PathBuf::from(home).join(rest)),
+        None => PathBuf::from(raw),
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::time::{Duration, Instant};
+
+    use myr_core::profiles::{ConnectionProfile, PasswordSource};
+    use tempfile::TempDir;
+
+    use super::{password_from_command, password_from_file, PasswordError};
+
+    fn command_profile(command: &str) -> ConnectionProfile {
+        let mut profile = ConnectionProfile::new("vault", "db.internal", "app");
+        profile.password_source = PasswordSource::Command;
+        profile.password_command = Some(command.to_string());
+        profile
+    }
+
+    #[test]
+    fn password_command_takes_the_first_stdout_line() {
+        let profile = command_profile("printf 's3cret pass\\nurl: db.internal\\n'");
+        assert_eq!(
+            password_from_command(&profile).expect("command should succeed"),
+            "s3cret pass"
+        );
+
+        let failing = command_profile("echo 'vault: permission denied' >&2; exit 2");
+        let error = password_from_command(&failing).expect_err("failing command");
+        assert_eq!(
+            error.to_string(),
+            "password command `echo 'vault: permission denied' >&2; exit 2` failed: vault: permission denied"
+        );
+
+        let silent = command_profile("true");
+        assert!(matches!(
+            password_from_command(&silent),
+            Err(PasswordError::CommandFailed { .. })
+        ));
+
+        let mut slow = command_profile("sleep 5");
+        slow.password_command_timeout_secs = Some(1);
+        assert!(matches!(
+            password_from_command(&slow),
+            Err(PasswordError::CommandTimeout { .. })
+        ));
+
+        let mut lingering = command_profile("sleep 5 & echo token");
+        lingering.password_command_timeout_secs = Some(1);
+        let started = Instant::now();
+        assert_eq!(
+            password_from_command(&lingering).expect("command exited"),
+            "token"
+        );
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn cached_password_command_runs_once_within_the_ttl() {
+        let dir = TempDir::new().expect("temp dir");
+        let counter = dir.path().join("runs");
+        let mut profile = command_profile(&format!(
+            "echo run >> '{}'; echo token-$(wc -l < '{}' | tr -d ' ')",
+            counter.display(),
+            counter.display()
+        ));
+        profile.password_cache_secs = Some(60);
+
+        assert_eq!(
+            password_from_command(&profile).expect("first run"),
+            "token-1"
+        );
+        assert_eq!(password_from_command(&profile).expect("cached"), "token-1");
+
+        profile.password_cache_secs = None;
+        assert_eq!(
+            password_from_command(&profile).expect("uncached"),
+            "token-2"
+        );
+    }
+
+    #[test]
+    fn password_file_must_be_private() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("db.pass");
+        fs::write(&path, "from-file\n").expect("write password file");
+        let mut profile = ConnectionProfile::new("file", "db.internal", "app");
+        profile.password_source = PasswordSource::File;
+        profile.password_file = Some(path.display().to_string());
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).expect("chmod");
+        assert!(matches!(
+            password_from_file(&profile),
+            Err(PasswordError::FilePermissions { mode: 0o644, .. })
+        ));
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).expect("chmod");
+        assert_eq!(
+            password_from_file(&profile).expect("private file"),
+            "from-file"
+        );
+
+        profile.password_file = None;
+        assert_eq!(
+            password_from_file(&profile)
+                .expect_err("path is required")
+                .to_string(),
+            "password_source = \"file\" needs `password_file` in the profile"
+        );
+    }
+}
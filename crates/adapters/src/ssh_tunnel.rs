@@ -0,0 +1,526 @@
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use myr_core::profiles::{ConnectionProfile, ConnectionTransport, SshKnownHosts};
+use thiserror::Error;
+
+const SSH_PROGRAM_ENV: &str = "MYR_SSH_PROGRAM";
+const DEFAULT_SSH_PROGRAM: &str = "ssh";
+
+#[derive(Debug, Error)]
+pub enum SshTunnelError {
+    #[error("failed to bind local SSH tunnel port: {0}")]
+    Bind(#[source] io::Error),
+    #[error("failed to start `{program}` for the SSH jump: {source}")]
+    Spawn {
+        program: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("SSH jump via {jump_host} failed: {message}")]
+    Jump { jump_host: String, message: String },
+    #[error("SSH jump via {jump_host} relayed nothing from {target} within {timeout:.1?}")]
+    Timeout {
+        jump_host: String,
+        target: String,
+        timeout: Duration,
+    },
+    #[error("SSH tunnel relay failed: {0}")]
+    Io(#[from] io::Error),
+    // A failure recorded by the relay thread of an earlier connection.
+    #[error("{0}")]
+    Relay(String),
+}
+
+// One `ssh -W` process per forwarded connection keeps the tunnel stateless: a dropped
+// bastion session only fails the pooled connection that used it, and the next checkout
+// simply starts a new one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SshCommand {
+    program: String,
+    args: Vec<String>,
+    jump_host: String,
+    target: String,
+}
+
+impl SshCommand {
+    #[must_use]
+    pub fn for_profile(profile: &ConnectionProfile) -> Option<Self> {
+        let ConnectionTransport::SshJump { jump_host, .. } = profile.transport() else {
+            return None;
+        };
+        let jump_host = jump_host.to_string();
+        let program = std::env::var(SSH_PROGRAM_ENV)
+            .ok()
+            .filter(|program| !program.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_SSH_PROGRAM.to_string());
+        let target = if profile.host.contains(':') {
+            format!("[{}]:{}", profile.host, profile.port)
+        } else {
+            format!("{}:{}", profile.host, profile.port)
+        };
+
+        let mut args = vec![
+            "-o".to_string(),
+            "BatchMode=yes".to_string(),
+            "-o".to_string(),
+            format!("ConnectTimeout={}", profile.connect_timeout().as_secs()),
+            "-o".to_string(),
+            format!(
+                "StrictHostKeyChecking={}",
+                match profile.ssh_known_hosts {
+                    SshKnownHosts::Strict => "yes",
+                    SshKnownHosts::AcceptNew => "accept-new",
+                    SshKnownHosts::Off => "no",
+                }
+            ),
+        ];
+        if profile.ssh_known_hosts == SshKnownHosts::Off {
+            args.extend(["-o".to_string(), "UserKnownHostsFile=/dev/null".to_string()]);
+        }
+        if profile.ssh_disable_agent {
+            args.extend(["-o".to_string(), "IdentityAgent=none".to_string()]);
+        }
+        if let Some(key_path) = non_empty(profile.ssh_key_path.as_deref()) {
+            args.extend([
+                "-i".to_string(),
+                key_path.to_string(),
+                "-o".to_string(),
+                "IdentitiesOnly=yes".to_string(),
+            ]);
+        }
+        if let Some(user) = non_empty(profile.ssh_user.as_deref()) {
+            args.extend(["-l".to_string(), user.to_string()]);
+        }
+        // `--` ends option parsing, so a jump host such as `-oProxyCommand=...` is only
+        // ever a destination and never an option that runs a local command.
+        args.extend([
+            "-p".to_string(),
+            profile.ssh_port().to_string(),
+            "-W".to_string(),
+            target.clone(),
+            "--".to_string(),
+            jump_host.clone(),
+        ]);
+
+        Some(Self {
+            program,
+            args,
+            jump_host,
+            target,
+        })
+    }
+
+    #[must_use]
+    pub fn with_program(mut self, program: impl Into<String>) -> Self {
+        self.program = program.into();
+        self
+    }
+
+    #[must_use]
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    #[must_use]
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    #[must_use]
+    pub fn jump_host(&self) -> &str {
+        &self.jump_host
+    }
+
+    fn jump_error(&self, message: impl Into<String>) -> SshTunnelError {
+        SshTunnelError::Jump {
+            jump_host: self.jump_host.clone(),
+            message: message.into(),
+        }
+    }
+}
+
+// A loopback listener that relays every accepted connection through its own `ssh -W`
+// process. The accept loop stops once the tunnel is dropped.
+#[derive(Debug)]
+pub struct SshTunnel {
+    command: SshCommand,
+    local_addr: SocketAddr,
+    last_error: Arc<Mutex<Option<String>>>,
+    closed: Arc<AtomicBool>,
+}
+
+impl SshTunnel {
+    pub fn open(command: SshCommand) -> Result<Self, SshTunnelError> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).map_err(SshTunnelError::Bind)?;
+        let local_addr = listener.local_addr().map_err(SshTunnelError::Bind)?;
+        let last_error = Arc::new(Mutex::new(None));
+        let closed = Arc::new(AtomicBool::new(false));
+
+        let accept_command = command.clone();
+        let accept_errors = Arc::clone(&last_error);
+        let accept_closed = Arc::clone(&closed);
+        thread::spawn(move || {
+            for client in listener.incoming() {
+                if accept_closed.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(client) = client else {
+                    continue;
+                };
+                let command = accept_command.clone();
+                let errors = Arc::clone(&accept_errors);
+                thread::spawn(move || {
+                    let outcome = relay(&command, &client);
+                    *errors.lock().unwrap_or_else(PoisonError::into_inner) =
+                        outcome.err().map(|error| error.to_string());
+                    // Only close the client once the outcome is recorded, so a failed jump
+                    // is visible to the caller by the time its connection sees EOF.
+                    let _ = client.shutdown(Shutdown::Both);
+                });
+            }
+        });
+
+        Ok(Self {
+            command,
+            local_addr,
+            last_error,
+            closed,
+        })
+    }
+
+    #[must_use]
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    #[must_use]
+    pub fn command(&self) -> &SshCommand {
+        &self.command
+    }
+
+    #[must_use]
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    // The MySQL server speaks first, so one relayed byte proves the bastion accepted us
+    // and could reach the database port.
+    pub fn check(&self, timeout: Duration) -> Result<Duration, SshTunnelError> {
+        let started_at = Instant::now();
+        let mut stream = TcpStream::connect(self.local_addr)?;
+        stream.set_read_timeout(Some(timeout))?;
+        let mut greeting = [0_u8; 1];
+        match stream.read(&mut greeting) {
+            Ok(0) => Err(self.last_error().map_or_else(
+                || {
+                    self.command
+                        .jump_error("connection closed before the server greeting")
+                },
+                SshTunnelError::Relay,
+            )),
+            Ok(_) => Ok(started_at.elapsed()),
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                Err(SshTunnelError::Timeout {
+                    jump_host: self.command.jump_host.clone(),
+                    target: self.command.target.clone(),
+                    timeout,
+                })
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::SeqCst);
+        // Wake the blocking accept so the listener thread can observe the flag.
+        let _ = TcpStream::connect(self.local_addr);
+    }
+}
+
+// Tunnels are shared per SSH command line for the life of the process, so the connect
+// check, the query pool and the schema pool of one profile all reuse a single listener.
+pub fn tunnel_for_profile(
+    profile: &ConnectionProfile,
+) -> Result<Option<Arc<SshTunnel>>, SshTunnelError> {
+    static TUNNELS: OnceLock<Mutex<HashMap<SshCommand, Arc<SshTunnel>>>> = OnceLock::new();
+
+    let Some(command) = SshCommand::for_profile(profile) else {
+        return Ok(None);
+    };
+    let mut tunnels = TUNNELS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(tunnel) = tunnels.get(&command) {
+        return Ok(Some(Arc::clone(tunnel)));
+    }
+
+    let tunnel = Arc::new(SshTunnel::open(command.clone())?);
+    tunnels.insert(command, Arc::clone(&tunnel));
+    Ok(Some(tunnel))
+}
+
+fn relay(command: &SshCommand, client: &TcpStream) -> Result<(), SshTunnelError> {
+    let mut child = Command::new(&command.program)
+        .args(&command.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|source| SshTunnelError::Spawn {
+            program: command.program.clone(),
+            source,
+        })?;
+    let (Some(mut ssh_stdin), Some(mut ssh_stdout)) = (child.stdin.take(), child.stdout.take())
+    else {
+        let _ = child.kill();
+        return Err(command.jump_error("ssh stdio was not captured"));
+    };
+
+    let mut upstream = client.try_clone()?;
+    let uploader = thread::spawn(move || pump(&mut upstream, &mut ssh_stdin));
+    let mut downstream = client;
+    pump(&mut ssh_stdout, &mut downstream);
+
+    // Unblock the uploader so ssh sees EOF on stdin and exits.
+    let _ = client.shutdown(Shutdown::Read);
+    let _ = uploader.join();
+    let output = child.wait_with_output()?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map_or_else(
+            || format!("ssh exited with {}", output.status),
+            str::to_string,
+        );
+    Err(command.jump_error(message))
+}
+
+// Forwards each read as soon as it arrives; the MySQL protocol is request/response, so
+// nothing may sit in a buffer waiting for more bytes.
+fn pump(reader: &mut impl Read, writer: &mut impl Write) {
+    let mut buffer = [0_u8; 16 * 1024];
+    loop {
+        match reader.read(&mut buffer) {
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Ok(0) | Err(_) => return,
+            Ok(read) => {
+                if writer
+                    .write_all(&buffer[..read])
+                    .and_then(|()| writer.flush())
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use myr_core::profiles::{ConnectionProfile, SshKnownHosts};
+
+    use super::{SshCommand, SshTunnelError};
+
+    fn jump_profile() -> ConnectionProfile {
+        let mut profile = ConnectionProfile::new("prod", "db.internal", "app");
+        profile.ssh_host = Some("bastion.example.com".to_string());
+        profile
+    }
+
+    #[test]
+    fn direct_profiles_have_no_ssh_command() {
+        let profile = ConnectionProfile::new("local", "127.0.0.1", "root");
+        assert!(SshCommand::for_profile(&profile).is_none());
+    }
+
+    #[test]
+    fn ssh_command_forwards_stdio_to_the_database_through_the_jump_host() {
+        let mut profile = jump_profile();
+        profile.port = 3307;
+        profile.ssh_port = Some(2222);
+        profile.ssh_user = Some("ops".to_string());
+        profile.ssh_key_path = Some("/home/ops/.ssh/id_ed25519".to_string());
+        profile.ssh_known_hosts = SshKnownHosts::Off;
+        profile.ssh_disable_agent = true;
+
+        let command = SshCommand::for_profile(&profile)
+            .expect("jump profile should build a command")
+            .with_program("ssh");
+        assert_eq!(
+            command.args().join(" "),
+            "-o BatchMode=yes -o ConnectTimeout=8 -o StrictHostKeyChecking=no \
+             -o UserKnownHostsFile=/dev/null -o IdentityAgent=none \
+             -i /home/ops/.ssh/id_ed25519 -o IdentitiesOnly=yes -l ops -p 2222 \
+             -W db.internal:3307 -- bastion.example.com"
+        );
+    }
+
+    #[test]
+    fn jump_hosts_that_look_like_options_stay_destinations() {
+        let mut profile = jump_profile();
+        profile.ssh_host = Some("-oProxyCommand=touch /tmp/owned".to_string());
+
+        let command = SshCommand::for_profile(&profile).expect("command");
+        assert_eq!(
+            command.args()[command.args().len() - 2..],
+            [
+                "--".to_string(),
+                "-oProxyCommand=touch /tmp/owned".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn socket_profiles_skip_the_jump_host() {
+        let mut profile = jump_profile();
+        profile.socket_path = Some("/tmp/mysql.sock".to_string());
+        assert!(SshCommand::for_profile(&profile).is_none());
+    }
+
+    #[test]
+    fn ssh_command_brackets_ipv6_targets() {
+        let mut profile = jump_profile();
+        profile.host = "fd00::10".to_string();
+        let command = SshCommand::for_profile(&profile).expect("command");
+        assert!(command.args().contains(&"[fd00::10]:3306".to_string()));
+        assert!(command
+            .args()
+            .contains(&"StrictHostKeyChecking=yes".to_string()));
+    }
+
+    #[cfg(unix)]
+    mod stand_in {
+        use std::fs;
+        use std::io::{Read, Write};
+        use std::net::TcpStream;
+        use std::os::unix::fs::PermissionsExt;
+        use std::path::Path;
+
+        use tempfile::TempDir;
+
+        use super::{jump_profile, Duration, SshCommand, SshTunnelError};
+        use crate::ssh_tunnel::SshTunnel;
+
+        // Plays the part of `ssh -W`: records its arguments, sends a fake server greeting
+        // and then echoes the client's bytes back.
+        fn write_stand_in(dir: &Path, body: &str) -> String {
+            let path = dir.join("ssh-stand-in");
+            fs::write(&path, format!("#!/bin/sh\n{body}\n")).expect("write stand-in");
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).expect("chmod");
+            path.display().to_string()
+        }
+
+        #[test]
+        fn tunnel_relays_bytes_through_the_ssh_process() {
+            let dir = TempDir::new().expect("temp dir");
+            let args_path = dir.path().join("args");
+            let program = write_stand_in(
+                dir.path(),
+                &format!(
+                    "echo \"$@\" > '{}'\nprintf 'greeting'\nexec cat",
+                    args_path.display()
+                ),
+            );
+            let command = SshCommand::for_profile(&jump_profile())
+                .expect("command")
+                .with_program(program);
+            let tunnel = SshTunnel::open(command).expect("tunnel should open");
+
+            tunnel
+                .check(Duration::from_secs(5))
+                .expect("stand-in greeting should pass the check");
+
+            let mut stream = TcpStream::connect(tunnel.local_addr()).expect("connect");
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .expect("read timeout");
+            let mut greeting = [0_u8; 8];
+            stream.read_exact(&mut greeting).expect("greeting");
+            assert_eq!(&greeting, b"greeting");
+            stream.write_all(b"ping").expect("write");
+            let mut echoed = [0_u8; 4];
+            stream.read_exact(&mut echoed).expect("echo");
+            assert_eq!(&echoed, b"ping");
+
+            let recorded = fs::read_to_string(&args_path).expect("stand-in args");
+            assert!(recorded
+                .trim_end()
+                .ends_with("-W db.internal:3306 -- bastion.example.com"));
+        }
+
+        #[test]
+        fn failed_jump_reports_the_last_ssh_error_line() {
+            let dir = TempDir::new().expect("temp dir");
+            let program = write_stand_in(
+                dir.path(),
+                "echo 'Warning: something noisy' >&2\n\
+                 echo 'ops@bastion.example.com: Permission denied (publickey).' >&2\n\
+                 exit 255",
+            );
+            let command = SshCommand::for_profile(&jump_profile())
+                .expect("command")
+                .with_program(program);
+            let tunnel = SshTunnel::open(command).expect("tunnel should open");
+
+            let error = tunnel
+                .check(Duration::from_secs(5))
+                .expect_err("failed jump should fail the check");
+            assert!(matches!(error, SshTunnelError::Relay(_)));
+            assert_eq!(
+                error.to_string(),
+                "SSH jump via bastion.example.com failed: \
+                 ops@bastion.example.com: Permission denied (publickey)."
+            );
+            assert_eq!(
+                tunnel.last_error().as_deref(),
+                Some(error.to_string().as_str())
+            );
+        }
+
+        #[test]
+        fn silent_jump_times_out() {
+            let dir = TempDir::new().expect("temp dir");
+            let program = write_stand_in(dir.path(), "exec cat");
+            let command = SshCommand::for_profile(&jump_profile())
+                .expect("command")
+                .with_program(program);
+            let tunnel = SshTunnel::open(command).expect("tunnel should open");
+
+            let error = tunnel
+                .check(Duration::from_millis(200))
+                .expect_err("a silent relay should time out");
+            assert!(matches!(error, SshTunnelError::Timeout { .. }));
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use myr_adapters::mysql::{MysqlConnectionBackend, MysqlDataBackend};
+use myr_adapters::ssh_tunnel::tunnel_for_profile;
 use myr_core::connection_manager::ConnectionBackend;
-use myr_core::profiles::ConnectionProfile;
-use myr_core::query_runner::{QueryBackend, QueryRowStream};
+use myr_core::profiles::{ConnectionProfile, SshKnownHosts};
+use myr_core::query_runner::{
+    QueryBackend, QueryRowStream, QueryValue, ServerCancellation, StatementStatus,
+};
 use myr_core::schema_cache::SchemaBackend;
 
 fn mysql_integration_enabled() -> bool {
@@ -25,7 +28,25 @@ fn integration_profile(database: Option<&str>) -> ConnectionProfile {
     profile
 }
 
-async fn execute_sql(backend: &MysqlDataBackend, sql: &str) {
+// Points at any sshd that can reach the integration database, e.g. a throwaway
+// `linuxserver/openssh-server` container next to the MySQL one.
+fn ssh_jump_profile() -> Option<ConnectionProfile> {
+    let ssh_host = std::env::var("MYR_TEST_SSH_HOST").ok()?;
+    let mut profile = integration_profile(None);
+    if let Ok(db_host) = std::env::var("MYR_TEST_SSH_DB_HOST") {
+        profile.host = db_host;
+    }
+    profile.ssh_host = Some(ssh_host);
+    profile.ssh_port = std::env::var("MYR_TEST_SSH_PORT")
+        .ok()
+        .and_then(|raw| raw.parse::<u16>().ok());
+    profile.ssh_user = std::env::var("MYR_TEST_SSH_USER").ok();
+    profile.ssh_key_path = std::env::var("MYR_TEST_SSH_KEY").ok();
+    profile.ssh_known_hosts = SshKnownHosts::Off;
+    Some(profile)
+}
+
+async fn execute_sql(backend: &MysqlDataBackend, sql: &str) -> Option<StatementStatus> {
     let mut stream = backend.start_query(sql).await.expect("query should start");
     while stream
         .next_row()
@@ -33,6 +54,55 @@ async fn execute_sql(backend: &MysqlDataBackend, sql: &str) {
         .expect("query stream should advance")
         .is_some()
     {}
+    stream.statement_status().cloned()
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn mysql_backend_connects_through_ssh_jump() {
+    if !mysql_integration_enabled() {
+        return;
+    }
+    let Some(profile) = ssh_jump_profile() else {
+        return;
+    };
+
+    let tunnel = tunnel_for_profile(&profile)
+        .expect("tunnel should open")
+        .expect("jump profile should use a tunnel");
+    tunnel
+        .check(profile.connect_timeout())
+        .expect("ssh jump should relay the server greeting");
+
+    let connection_backend = MysqlConnectionBackend::default();
+    let mut connection = connection_backend
+        .connect(&profile)
+        .await
+        .expect("connect through the jump host should succeed");
+    connection_backend
+        .ping(&mut connection)
+        .await
+        .expect("ping through the jump host should succeed");
+    connection_backend
+        .disconnect(connection)
+        .await
+        .expect("disconnect should succeed");
+
+    let backend = MysqlDataBackend::from_profile(&profile).expect("backend should build");
+    let mut stream = backend
+        .start_query("SELECT 1 AS tunneled")
+        .await
+        .expect("query through the jump host should start");
+    let row = stream
+        .next_row()
+        .await
+        .expect("tunneled query should stream")
+        .expect("row expected");
+    assert_eq!(row.values[0], QueryValue::Int(1));
+    drop(stream);
+    backend
+        .disconnect()
+        .await
+        .expect("backend disconnect should succeed");
 }
 
 #[tokio::test(flavor = "current_thread")]
@@ -43,7 +113,8 @@ async fn mysql_backend_connection_schema_and_query_paths() {
 
     let database = "myr_adapters_cov";
 
-    let admin_backend = MysqlDataBackend::from_profile(&integration_profile(None));
+    let admin_backend =
+        MysqlDataBackend::from_profile(&integration_profile(None)).expect("backend should build");
     execute_sql(
         &admin_backend,
         &format!("CREATE DATABASE IF NOT EXISTS `{database}`"),
@@ -56,7 +127,7 @@ async fn mysql_backend_connection_schema_and_query_paths() {
 
     let profile = integration_profile(Some(database));
 
-    let connection_backend = MysqlConnectionBackend;
+    let connection_backend = MysqlConnectionBackend::default();
     let mut connection = connection_backend
         .connect(&profile)
         .await
@@ -70,7 +141,8 @@ async fn mysql_backend_connection_schema_and_query_paths() {
         .await
         .expect("disconnect should succeed");
 
-    let backend = MysqlDataBackend::from_profile(&profile);
+    let backend = MysqlDataBackend::from_profile(&profile).expect("backend should build");
+    backend.ping().await.expect("pool ping should succeed");
     execute_sql(&backend, "DROP TABLE IF EXISTS integration_users").await;
     execute_sql(
         &backend,
@@ -81,12 +153,15 @@ async fn mysql_backend_connection_schema_and_query_paths() {
          )",
     )
     .await;
-    execute_sql(
+    let insert_status = execute_sql(
         &backend,
         "INSERT INTO integration_users (id, email, age) VALUES \
          (1, 'a@example.com', 22), (2, 'b@example.com', NULL)",
     )
-    .await;
+    .await
+    .expect("insert should report statement status");
+    assert_eq!(insert_status.affected_rows, 2);
+    assert_eq!(insert_status.warning_count, 0);
 
     let schema = backend
         .fetch_schema()
@@ -125,12 +200,32 @@ async fn mysql_backend_connection_schema_and_query_paths() {
         .await
         .expect("stream should end cleanly");
 
-    assert_eq!(row_1.values[0], "1");
-    assert_eq!(row_1.values[1], "a@example.com");
-    assert_eq!(row_2.values[0], "2");
-    assert_eq!(row_2.values[2], "NULL");
+    assert_eq!(row_1.values[0], QueryValue::Int(1));
+    assert_eq!(row_1.values[1], QueryValue::from("a@example.com"));
+    assert_eq!(row_2.values[0], QueryValue::Int(2));
+    assert_eq!(row_2.values[2], QueryValue::Null);
     assert!(end.is_none());
 
+    let mut prepared_stream = backend
+        .start_prepared(
+            "SELECT email, age FROM integration_users WHERE id = ? OR email = ?",
+            &[QueryValue::Int(2), QueryValue::Null],
+        )
+        .await
+        .expect("prepared stream should start");
+    let prepared_row = prepared_stream
+        .next_row()
+        .await
+        .expect("prepared stream should read row")
+        .expect("prepared row expected");
+    assert_eq!(prepared_row.values[0], QueryValue::from("b@example.com"));
+    assert_eq!(prepared_row.values[1], QueryValue::Null);
+    assert!(prepared_stream
+        .next_row()
+        .await
+        .expect("prepared stream should end cleanly")
+        .is_none());
+
     let mut cancellable_stream = backend
         .start_query("SELECT id FROM integration_users ORDER BY id")
         .await
@@ -145,6 +240,84 @@ async fn mysql_backend_connection_schema_and_query_paths() {
         .expect("cancelled stream should return none");
     assert!(cancelled_end.is_none());
 
+    let mut sleeping_stream = backend
+        .start_query("SELECT SLEEP(5)")
+        .await
+        .expect("sleep query should start");
+    let connection_id = sleeping_stream.connection_id();
+    let server_cancellation = sleeping_stream
+        .cancel()
+        .await
+        .expect("cancel should report an outcome");
+    assert_eq!(
+        server_cancellation,
+        ServerCancellation::Killed { connection_id }
+    );
+
+    let mut batch_stream = backend
+        .start_query(
+            "SELECT id FROM integration_users ORDER BY id; \
+             SELECT COUNT(*) AS total FROM integration_users",
+        )
+        .await
+        .expect("batch stream should start");
+    while batch_stream
+        .next_row()
+        .await
+        .expect("first result set should stream")
+        .is_some()
+    {}
+    assert_eq!(batch_stream.column_names(), Some(&["id".to_string()][..]));
+    assert!(batch_stream
+        .next_result_set()
+        .await
+        .expect("second result set should start"));
+    assert_eq!(
+        batch_stream.column_names(),
+        Some(&["total".to_string()][..])
+    );
+    let total = batch_stream
+        .next_row()
+        .await
+        .expect("second result set should stream")
+        .expect("count row expected");
+    assert_eq!(total.values[0], QueryValue::Int(2));
+    assert!(!batch_stream
+        .next_result_set()
+        .await
+        .expect("batch should finish cleanly"));
+
+    let pinned_backend = MysqlDataBackend::from_profile(&profile)
+        .expect("backend should build")
+        .with_pinned_session(true);
+    execute_sql(&pinned_backend, "SET @myr_pinned = 41").await;
+    pinned_backend
+        .ping()
+        .await
+        .expect("keepalive ping should reach the pinned session");
+    let mut pinned_stream = pinned_backend
+        .start_query("SELECT @myr_pinned + 1 AS answer")
+        .await
+        .expect("pinned query should start");
+    let answer = pinned_stream
+        .next_row()
+        .await
+        .expect("pinned query should stream")
+        .expect("answer row expected");
+    assert_eq!(answer.values[0], QueryValue::Int(42));
+    assert!(!pinned_stream.session_state_lost());
+    while pinned_stream
+        .next_row()
+        .await
+        .expect("pinned query should finish")
+        .is_some()
+    {}
+    drop(pinned_stream);
+    pinned_backend
+        .disconnect()
+        .await
+        .expect("pinned backend disconnect should succeed");
+
     execute_sql(&backend, "DROP TABLE IF EXISTS integration_users").await;
     backend
         .disconnect()
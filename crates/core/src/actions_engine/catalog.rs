@@ -1,3 +1,5 @@
+use crate::server_capabilities::ServerCapabilities;
+
 use super::enablement::action_enabled;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -14,6 +16,7 @@ pub enum ActionId {
     RunCurrentQuery,
     ApplyLimit200,
     ExplainQuery,
+    ExplainAnalyzeQuery,
     BuildFilterSortQuery,
     InsertSelectSnippet,
     InsertJoinSnippet,
@@ -30,6 +33,12 @@ pub enum ActionId {
     CopyRow,
     SearchResults,
     FocusQueryEditor,
+    NextResultSet,
+    BeginTransaction,
+    CommitTransaction,
+    RollbackTransaction,
+    NextConnection,
+    CloseConnection,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -60,6 +69,10 @@ pub struct ActionContext {
     pub pagination_enabled: bool,
     pub can_page_next: bool,
     pub can_page_previous: bool,
+    pub has_multiple_result_sets: bool,
+    pub transaction_open: bool,
+    pub open_connections: usize,
+    pub server: Option<ServerCapabilities>,
 }
 
 impl Default for ActionContext {
@@ -75,6 +88,10 @@ impl Default for ActionContext {
             pagination_enabled: false,
             can_page_next: false,
             can_page_previous: false,
+            has_multiple_result_sets: false,
+            transaction_open: false,
+            open_connections: 0,
+            server: None,
         }
     }
 }
@@ -100,7 +117,7 @@ pub struct ActionDefinition {
     pub description: &'static str,
 }
 
-const ACTIONS: [ActionDefinition; 28] = [
+const ACTIONS: [ActionDefinition; 35] = [
     ActionDefinition {
         id: ActionId::PreviewTable,
         title: "Preview table",
@@ -161,6 +178,11 @@ const ACTIONS: [ActionDefinition; 28] = [
         title: "Explain query",
         description: "Run EXPLAIN preflight for the current query",
     },
+    ActionDefinition {
+        id: ActionId::ExplainAnalyzeQuery,
+        title: "Explain analyze query",
+        description: "Run the current SELECT and report measured plan timings",
+    },
     ActionDefinition {
         id: ActionId::BuildFilterSortQuery,
         title: "Build filter/sort query",
@@ -241,6 +263,36 @@ const ACTIONS: [ActionDefinition; 28] = [
         title: "Go to query editor",
         description: "Switch to query editor view",
     },
+    ActionDefinition {
+        id: ActionId::NextResultSet,
+        title: "Next result set",
+        description: "Show the next result set from a procedure call or multi-statement batch",
+    },
+    ActionDefinition {
+        id: ActionId::BeginTransaction,
+        title: "Begin transaction",
+        description: "Start a transaction on a pinned session",
+    },
+    ActionDefinition {
+        id: ActionId::CommitTransaction,
+        title: "Commit transaction",
+        description: "Commit the open transaction",
+    },
+    ActionDefinition {
+        id: ActionId::RollbackTransaction,
+        title: "Rollback transaction",
+        description: "Roll back the open transaction",
+    },
+    ActionDefinition {
+        id: ActionId::NextConnection,
+        title: "Next connection",
+        description: "Switch to the next open connection tab",
+    },
+    ActionDefinition {
+        id: ActionId::CloseConnection,
+        title: "Close connection",
+        description: "Close the active connection and its workspace",
+    },
 ];
 
 #[derive(Debug, Default)]
@@ -47,6 +47,14 @@ pub(super) fn action_enabled(action_id: ActionId, context: &ActionContext) -> bo
                     .as_deref()
                     .is_some_and(|query| suggest_explain_query(query).is_some())
         }
+        ActionId::ExplainAnalyzeQuery => {
+            !context.query_running
+                && context
+                    .query_text
+                    .as_deref()
+                    .zip(context.server.as_ref())
+                    .is_some_and(|(query, server)| server.explain_analyze_sql(query).is_some())
+        }
         ActionId::BuildFilterSortQuery => {
             !context.query_running
                 && context.selection.table.is_some()
@@ -82,5 +90,12 @@ pub(super) fn action_enabled(action_id: ActionId, context: &ActionContext) -> bo
                 && !context.query_running
         }
         ActionId::FocusQueryEditor => context.view != AppView::QueryEditor,
+        ActionId::NextResultSet => context.has_multiple_result_sets && !context.query_running,
+        ActionId::BeginTransaction => !context.transaction_open && !context.query_running,
+        ActionId::CommitTransaction | ActionId::RollbackTransaction => {
+            context.transaction_open && !context.query_running
+        }
+        ActionId::NextConnection => context.open_connections > 1 && !context.query_running,
+        ActionId::CloseConnection => context.open_connections > 0 && !context.query_running,
     }
 }
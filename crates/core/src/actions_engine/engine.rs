@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::safe_mode::TransactionBoundary;
 use crate::sql_generator::{
     count_estimate_sql, describe_table_sql, filtered_sorted_preview_sql, preview_select_sql,
     show_create_table_sql, show_index_sql,
@@ -78,6 +79,18 @@ impl ActionsEngine {
             ActionId::RunHealthDiagnostics => ActionInvocation::RunHealthDiagnostics,
             ActionId::PreviousPage => ActionInvocation::PaginatePrevious,
             ActionId::NextPage => ActionInvocation::PaginateNext,
+            ActionId::NextResultSet => ActionInvocation::ShowNextResultSet,
+            ActionId::NextConnection => ActionInvocation::NextConnection,
+            ActionId::CloseConnection => ActionInvocation::CloseConnection,
+            ActionId::BeginTransaction => {
+                ActionInvocation::TransactionControl(TransactionBoundary::Begin)
+            }
+            ActionId::CommitTransaction => {
+                ActionInvocation::TransactionControl(TransactionBoundary::Commit)
+            }
+            ActionId::RollbackTransaction => {
+                ActionInvocation::TransactionControl(TransactionBoundary::Rollback)
+            }
             ActionId::DescribeTable => {
                 let target = context_selected_target(context)?;
                 ActionInvocation::RunSql(describe_table_sql(&target))
@@ -121,6 +134,18 @@ impl ActionsEngine {
                     suggest_explain_query(query).ok_or(ActionEngineError::NoExplainSuggestion)?;
                 ActionInvocation::RunSql(explain)
             }
+            ActionId::ExplainAnalyzeQuery => {
+                let query = context
+                    .query_text
+                    .as_deref()
+                    .ok_or(ActionEngineError::MissingQueryText)?;
+                let explain = context
+                    .server
+                    .as_ref()
+                    .and_then(|server| server.explain_analyze_sql(query))
+                    .ok_or(ActionEngineError::NoExplainAnalyzeSuggestion)?;
+                ActionInvocation::RunSql(explain)
+            }
             ActionId::BuildFilterSortQuery => {
                 let target = context_selected_target(context)?;
                 let column = context_selected_column(context)?;
@@ -1,5 +1,6 @@
 use thiserror::Error;
 
+use crate::safe_mode::TransactionBoundary;
 use crate::sql_generator::SqlGenerationError;
 
 use super::{ActionId, AppView};
@@ -33,6 +34,10 @@ pub enum ActionInvocation {
     RunHealthDiagnostics,
     PaginatePrevious,
     PaginateNext,
+    ShowNextResultSet,
+    TransactionControl(TransactionBoundary),
+    NextConnection,
+    CloseConnection,
     ReplaceQueryEditorText(String),
     InsertQueryEditorText(String),
     CancelQuery,
@@ -61,6 +66,8 @@ pub enum ActionEngineError {
     NoLimitSuggestion,
     #[error("no EXPLAIN suggestion is available for this query")]
     NoExplainSuggestion,
+    #[error("EXPLAIN ANALYZE needs a single SELECT and a server that supports it")]
+    NoExplainAnalyzeSuggestion,
     #[error("failed to generate SQL: {0}")]
     SqlGeneration(#[from] SqlGenerationError),
 }
@@ -33,6 +33,18 @@ pub(super) fn action_base_score(action_id: ActionId, context: &ActionContext) ->
                 0
             }
         }
+        ActionId::ExplainAnalyzeQuery => {
+            if context
+                .query_text
+                .as_deref()
+                .zip(context.server.as_ref())
+                .is_some_and(|(query, server)| server.explain_analyze_sql(query).is_some())
+            {
+                905
+            } else {
+                0
+            }
+        }
         ActionId::BuildFilterSortQuery => {
             if context.selection.table.is_some()
                 && context.selection.database.is_some()
@@ -223,5 +235,28 @@ pub(super) fn action_base_score(action_id: ActionId, context: &ActionContext) ->
                 0
             }
         }
+        ActionId::NextResultSet => {
+            if context.has_multiple_result_sets && context.view == AppView::Results {
+                850
+            } else {
+                0
+            }
+        }
+        ActionId::BeginTransaction => 0,
+        ActionId::CommitTransaction => {
+            if context.transaction_open {
+                880
+            } else {
+                0
+            }
+        }
+        ActionId::RollbackTransaction => {
+            if context.transaction_open {
+                870
+            } else {
+                0
+            }
+        }
+        ActionId::NextConnection | ActionId::CloseConnection => 0,
     }
 }
@@ -1,7 +1,9 @@
 use super::{
-    suggest_explain_query, suggest_preview_limit, ActionContext, ActionId, ActionInvocation,
-    ActionsEngine, AppView, ExportFormat, SchemaSelection,
+    suggest_explain_query, suggest_preview_limit, ActionContext, ActionEngineError, ActionId,
+    ActionInvocation, ActionsEngine, AppView, ExportFormat, SchemaSelection,
 };
+use crate::safe_mode::TransactionBoundary;
+use crate::server_capabilities::{ServerCapabilities, ServerFlavor, ServerVersion};
 
 fn schema_context() -> ActionContext {
     ActionContext {
@@ -19,6 +21,10 @@ fn schema_context() -> ActionContext {
         pagination_enabled: false,
         can_page_next: false,
         can_page_previous: false,
+        has_multiple_result_sets: false,
+        transaction_open: false,
+        open_connections: 0,
+        server: None,
     }
 }
 
@@ -118,6 +124,41 @@ fn explain_action_generates_preflight_sql() {
     );
 }
 
+#[test]
+fn explain_analyze_is_only_enabled_when_the_server_supports_it() {
+    let mut engine = ActionsEngine::new();
+    let mut context = ActionContext::default()
+        .with_view(AppView::QueryEditor)
+        .with_query("SELECT * FROM users");
+    assert_eq!(
+        engine.invoke(ActionId::ExplainAnalyzeQuery, &context),
+        Err(ActionEngineError::ActionDisabled(
+            ActionId::ExplainAnalyzeQuery
+        ))
+    );
+
+    context.server = Some(ServerCapabilities::new(
+        ServerFlavor::MariaDb,
+        ServerVersion::new(10, 11, 6),
+    ));
+    assert_eq!(
+        engine.invoke(ActionId::ExplainAnalyzeQuery, &context),
+        Ok(ActionInvocation::RunSql(
+            "ANALYZE SELECT * FROM users".to_string()
+        ))
+    );
+
+    context.server = Some(ServerCapabilities::new(
+        ServerFlavor::MySql,
+        ServerVersion::new(5, 7, 44),
+    ));
+    assert!(!engine
+        .registry()
+        .enabled_actions(&context)
+        .iter()
+        .any(|action| action.id == ActionId::ExplainAnalyzeQuery));
+}
+
 #[test]
 fn filter_sort_builder_action_generates_server_side_query() {
     let mut engine = ActionsEngine::new();
@@ -136,6 +177,10 @@ fn filter_sort_builder_action_generates_server_side_query() {
         pagination_enabled: false,
         can_page_next: false,
         can_page_previous: false,
+        has_multiple_result_sets: false,
+        transaction_open: false,
+        open_connections: 0,
+        server: None,
     };
 
     let invocation = engine
@@ -172,6 +217,86 @@ fn snippet_actions_insert_editor_templates() {
     ));
 }
 
+#[test]
+fn next_result_set_requires_multiple_result_sets() {
+    let mut engine = ActionsEngine::new();
+    let mut context = ActionContext::default().with_view(AppView::Results);
+    context.has_results = true;
+
+    assert!(engine.invoke(ActionId::NextResultSet, &context).is_err());
+
+    context.has_multiple_result_sets = true;
+    let invocation = engine
+        .invoke(ActionId::NextResultSet, &context)
+        .expect("next result set should be enabled");
+    assert_eq!(invocation, ActionInvocation::ShowNextResultSet);
+    assert_eq!(
+        engine.rank_top_n(&context, 1)[0].id,
+        ActionId::NextResultSet
+    );
+}
+
+#[test]
+fn transaction_actions_follow_the_open_transaction() {
+    let mut engine = ActionsEngine::new();
+    let mut context = ActionContext::default().with_view(AppView::QueryEditor);
+
+    assert_eq!(
+        engine
+            .invoke(ActionId::BeginTransaction, &context)
+            .expect("begin should be enabled without an open transaction"),
+        ActionInvocation::TransactionControl(TransactionBoundary::Begin)
+    );
+    assert!(engine
+        .invoke(ActionId::CommitTransaction, &context)
+        .is_err());
+
+    context.transaction_open = true;
+    assert!(engine.invoke(ActionId::BeginTransaction, &context).is_err());
+    let top = engine.rank_top_n(&context, 2);
+    assert_eq!(top[0].id, ActionId::CommitTransaction);
+    assert_eq!(top[1].id, ActionId::RollbackTransaction);
+    assert_eq!(
+        engine
+            .invoke(ActionId::RollbackTransaction, &context)
+            .expect("rollback should be enabled with an open transaction"),
+        ActionInvocation::TransactionControl(TransactionBoundary::Rollback)
+    );
+
+    context.query_running = true;
+    assert!(engine
+        .invoke(ActionId::CommitTransaction, &context)
+        .is_err());
+}
+
+#[test]
+fn connection_tab_actions_follow_the_open_connections() {
+    let mut engine = ActionsEngine::new();
+    let mut context = ActionContext::default().with_view(AppView::SchemaExplorer);
+
+    assert!(engine.invoke(ActionId::CloseConnection, &context).is_err());
+
+    context.open_connections = 1;
+    assert!(engine.invoke(ActionId::NextConnection, &context).is_err());
+    assert_eq!(
+        engine
+            .invoke(ActionId::CloseConnection, &context)
+            .expect("close should be enabled with an open connection"),
+        ActionInvocation::CloseConnection
+    );
+
+    context.open_connections = 2;
+    assert_eq!(
+        engine
+            .invoke(ActionId::NextConnection, &context)
+            .expect("next connection should be enabled with two tabs"),
+        ActionInvocation::NextConnection
+    );
+
+    context.query_running = true;
+    assert!(engine.invoke(ActionId::NextConnection, &context).is_err());
+}
+
 #[test]
 fn pagination_actions_are_available_in_results_context() {
     let mut engine = ActionsEngine::new();
@@ -190,6 +315,10 @@ fn pagination_actions_are_available_in_results_context() {
         pagination_enabled: true,
         can_page_next: true,
         can_page_previous: true,
+        has_multiple_result_sets: false,
+        transaction_open: false,
+        open_connections: 0,
+        server: None,
     };
 
     let next = engine
@@ -221,6 +350,10 @@ fn relationship_and_bookmark_actions_are_invokable_with_context_flags() {
         pagination_enabled: false,
         can_page_next: false,
         can_page_previous: false,
+        has_multiple_result_sets: false,
+        transaction_open: false,
+        open_connections: 0,
+        server: None,
     };
 
     let jump = engine
@@ -280,6 +413,10 @@ fn export_variant_actions_resolve_to_expected_formats() {
         pagination_enabled: false,
         can_page_next: false,
         can_page_previous: false,
+        has_multiple_result_sets: false,
+        transaction_open: false,
+        open_connections: 0,
+        server: None,
     };
 
     assert_eq!(
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::profiles::{default_profiles_path, ProfilesError};
+use crate::safe_mode::TransactionBoundary;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -28,6 +29,16 @@ pub struct AuditRecord {
     pub rows_streamed: Option<u64>,
     pub elapsed_ms: Option<u128>,
     pub error: Option<String>,
+    #[serde(default)]
+    pub affected_rows: Option<u64>,
+    #[serde(default)]
+    pub last_insert_id: Option<u64>,
+    #[serde(default)]
+    pub warning_count: Option<u16>,
+    #[serde(default)]
+    pub info: Option<String>,
+    #[serde(default)]
+    pub transaction: Option<TransactionBoundary>,
 }
 
 #[must_use]
@@ -272,6 +283,7 @@ mod tests {
         rotated_audit_path, unix_timestamp_millis, AuditOutcome, AuditRecord, AuditRetentionPolicy,
         FileAuditTrail,
     };
+    use crate::safe_mode::TransactionBoundary;
 
     #[test]
     fn appends_json_lines_to_file() {
@@ -288,6 +300,11 @@ mod tests {
             rows_streamed: None,
             elapsed_ms: None,
             error: None,
+            affected_rows: None,
+            last_insert_id: None,
+            warning_count: None,
+            info: None,
+            transaction: None,
         };
         trail.append(&first).expect("failed to append first record");
 
@@ -296,10 +313,15 @@ mod tests {
             profile_name: Some("local".to_string()),
             database: Some("app".to_string()),
             outcome: AuditOutcome::Succeeded,
-            sql: "SELECT 1".to_string(),
-            rows_streamed: Some(1),
+            sql: "COMMIT".to_string(),
+            rows_streamed: Some(0),
             elapsed_ms: Some(5),
             error: None,
+            affected_rows: Some(0),
+            last_insert_id: None,
+            warning_count: Some(0),
+            info: None,
+            transaction: Some(TransactionBoundary::Commit),
         };
         trail
             .append(&second)
@@ -324,6 +346,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn legacy_records_without_statement_status_still_parse() {
+        let raw = r#"{"timestamp_unix_ms":1,"profile_name":null,"database":null,"outcome":"succeeded","sql":"UPDATE t SET a = 1","rows_streamed":0,"elapsed_ms":3,"error":null}"#;
+        let record: AuditRecord = serde_json::from_str(raw).expect("legacy record should parse");
+        assert_eq!(record.affected_rows, None);
+        assert_eq!(record.transaction, None);
+        assert_eq!(record.warning_count, None);
+    }
+
     #[test]
     fn timestamp_uses_unix_epoch_millis() {
         assert!(unix_timestamp_millis() > 0);
@@ -351,6 +382,11 @@ mod tests {
                 rows_streamed: None,
                 elapsed_ms: None,
                 error: None,
+                affected_rows: None,
+                last_insert_id: None,
+                warning_count: None,
+                info: None,
+                transaction: None,
             };
             trail.append(&record).expect("append should succeed");
         }
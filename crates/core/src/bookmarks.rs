@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -18,6 +19,8 @@ pub struct SavedBookmark {
     pub column: Option<String>,
     #[serde(default)]
     pub query: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, String>,
 }
 
 impl SavedBookmark {
@@ -30,6 +33,7 @@ impl SavedBookmark {
             table: None,
             column: None,
             query: None,
+            parameters: BTreeMap::new(),
         }
     }
 }
@@ -235,7 +239,10 @@ mod tests {
         bookmark.database = Some("app".to_string());
         bookmark.table = Some("users".to_string());
         bookmark.column = Some("id".to_string());
-        bookmark.query = Some("SELECT * FROM `app`.`users` LIMIT 200".to_string());
+        bookmark.query = Some("SELECT * FROM `app`.`users` WHERE id = :id LIMIT 200".to_string());
+        bookmark
+            .parameters
+            .insert(":id".to_string(), "42".to_string());
 
         store.upsert_bookmark(bookmark.clone());
         store.persist().expect("failed to persist store");
@@ -1,14 +1,17 @@
+use std::collections::VecDeque;
 use std::time::{Duration, Instant, SystemTime};
 
 use async_trait::async_trait;
 use thiserror::Error;
 
 use crate::profiles::ConnectionProfile;
+use crate::server_capabilities::ServerCapabilities;
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 #[error("{message}")]
 pub struct BackendError {
     message: String,
+    code: Option<u16>,
 }
 
 impl BackendError {
@@ -16,8 +19,21 @@ impl BackendError {
     pub fn new(message: impl Into<String>) -> Self {
         Self {
             message: message.into(),
+            code: None,
         }
     }
+
+    #[must_use]
+    pub fn with_code(mut self, code: Option<u16>) -> Self {
+        self.code = code;
+        self
+    }
+
+    // MySQL server error number, or the client `CR_*` number for transport failures.
+    #[must_use]
+    pub fn code(&self) -> Option<u16> {
+        self.code
+    }
 }
 
 #[async_trait]
@@ -26,6 +42,10 @@ pub trait ConnectionBackend {
 
     async fn connect(&self, profile: &ConnectionProfile) -> Result<Self::Connection, BackendError>;
     async fn ping(&self, connection: &mut Self::Connection) -> Result<(), BackendError>;
+    async fn server_capabilities(
+        &self,
+        connection: &mut Self::Connection,
+    ) -> Result<ServerCapabilities, BackendError>;
     async fn disconnect(&self, connection: Self::Connection) -> Result<(), BackendError>;
 }
 
@@ -49,6 +69,62 @@ impl ConnectionStatus {
     }
 }
 
+pub const LATENCY_HISTORY_CAPACITY: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatencyHistory {
+    samples: VecDeque<Duration>,
+    capacity: usize,
+}
+
+impl Default for LatencyHistory {
+    fn default() -> Self {
+        Self::new(LATENCY_HISTORY_CAPACITY)
+    }
+}
+
+impl LatencyHistory {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, latency: Duration) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    #[must_use]
+    pub fn latest(&self) -> Option<Duration> {
+        self.samples.back().copied()
+    }
+
+    #[must_use]
+    pub fn max(&self) -> Option<Duration> {
+        self.samples.iter().max().copied()
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = Duration> + '_ {
+        self.samples.iter().copied()
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ConnectionManagerError {
     #[error("active connection already exists for profile `{active_profile}`")]
@@ -59,10 +135,21 @@ pub enum ConnectionManagerError {
     Backend(#[source] BackendError),
 }
 
+impl ConnectionManagerError {
+    #[must_use]
+    pub fn code(&self) -> Option<u16> {
+        match self {
+            Self::Backend(error) => error.code(),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct ActiveConnection<C> {
     profile: ConnectionProfile,
     handle: C,
+    server: Option<ServerCapabilities>,
 }
 
 #[derive(Debug)]
@@ -102,6 +189,13 @@ impl<B: ConnectionBackend> ConnectionManager<B> {
         self.active.as_ref().map(|active| &active.profile)
     }
 
+    #[must_use]
+    pub fn server_capabilities(&self) -> Option<&ServerCapabilities> {
+        self.active
+            .as_ref()
+            .and_then(|active| active.server.as_ref())
+    }
+
     pub async fn connect(
         &mut self,
         profile: ConnectionProfile,
@@ -122,11 +216,17 @@ impl<B: ConnectionBackend> ConnectionManager<B> {
             .ping(&mut handle)
             .await
             .map_err(ConnectionManagerError::Backend)?;
+        // An unrecognised server still gets a working session, just no capability flags.
+        let server = self.backend.server_capabilities(&mut handle).await.ok();
 
         let latency = started_at.elapsed();
         self.last_latency = Some(latency);
         self.last_health_check_at = Some(SystemTime::now());
-        self.active = Some(ActiveConnection { profile, handle });
+        self.active = Some(ActiveConnection {
+            profile,
+            handle,
+            server,
+        });
 
         Ok(latency)
     }
@@ -174,9 +274,10 @@ mod tests {
 
     use super::{
         BackendError, ConnectionBackend, ConnectionManager, ConnectionManagerError,
-        ConnectionStatus,
+        ConnectionStatus, LatencyHistory,
     };
     use crate::profiles::ConnectionProfile;
+    use crate::server_capabilities::{ServerCapabilities, ServerFlavor, ServerVersion};
 
     #[derive(Debug, Default)]
     struct FakeBackend {
@@ -218,6 +319,16 @@ mod tests {
             Ok(())
         }
 
+        async fn server_capabilities(
+            &self,
+            _connection: &mut Self::Connection,
+        ) -> Result<ServerCapabilities, BackendError> {
+            Ok(ServerCapabilities::new(
+                ServerFlavor::MySql,
+                ServerVersion::new(8, 0, 36),
+            ))
+        }
+
         async fn disconnect(&self, _connection: Self::Connection) -> Result<(), BackendError> {
             self.disconnect_calls.fetch_add(1, Ordering::Relaxed);
             Ok(())
@@ -244,6 +355,10 @@ mod tests {
         assert_eq!(status.profile_name.as_deref(), Some("local"));
         assert!(status.last_latency.is_some());
         assert!(status.last_health_check_at.is_some());
+        assert_eq!(
+            manager.server_capabilities().map(ServerCapabilities::label),
+            Some("MySQL 8.0.36".to_string())
+        );
     }
 
     #[tokio::test]
@@ -277,6 +392,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn latency_history_keeps_the_most_recent_samples() {
+        let mut history = LatencyHistory::new(3);
+        for millis in [5, 9, 2, 7] {
+            history.push(std::time::Duration::from_millis(millis));
+        }
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(
+            history
+                .samples()
+                .map(|sample| sample.as_millis())
+                .collect::<Vec<_>>(),
+            vec![9, 2, 7]
+        );
+        assert_eq!(history.latest(), Some(std::time::Duration::from_millis(7)));
+        assert_eq!(history.max(), Some(std::time::Duration::from_millis(9)));
+    }
+
     #[tokio::test]
     async fn disconnect_is_idempotent_and_clears_status() {
         let backend = FakeBackend::default();
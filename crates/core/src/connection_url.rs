@@ -0,0 +1,325 @@
+use thiserror::Error;
+
+use crate::profiles::{ConnectionProfile, TlsMode};
+
+const DEFAULT_URL_PORT: u16 = 3306;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConnectionUrlError {
+    #[error("connection URL must start with `mysql://` or `mariadb://`")]
+    UnsupportedScheme,
+    #[error("connection URL has no host")]
+    MissingHost,
+    #[error("invalid port `{0}` in connection URL")]
+    InvalidPort(String),
+    #[error("connection URLs must not carry a password; use MYR_DB_PASSWORD or the keyring")]
+    PasswordNotAllowed,
+    #[error("invalid percent-encoding in connection URL")]
+    InvalidEncoding,
+    #[error("unsupported connection URL parameter `{0}`")]
+    UnknownParameter(String),
+    #[error("invalid value `{value}` for connection URL parameter `{name}`")]
+    InvalidParameter { name: String, value: String },
+}
+
+impl ConnectionProfile {
+    pub fn from_url(name: impl Into<String>, url: &str) -> Result<Self, ConnectionUrlError> {
+        let mut profile = Self::new(name, String::new(), String::new());
+        profile.apply_url(url)?;
+        Ok(profile)
+    }
+
+    // The URL is authoritative for the address and database; the user and the TLS and
+    // timeout settings only change when the URL names them.
+    pub fn apply_url(&mut self, url: &str) -> Result<(), ConnectionUrlError> {
+        let (scheme, rest) = url
+            .trim()
+            .split_once("://")
+            .ok_or(ConnectionUrlError::UnsupportedScheme)?;
+        if !matches!(scheme.to_ascii_lowercase().as_str(), "mysql" | "mariadb") {
+            return Err(ConnectionUrlError::UnsupportedScheme);
+        }
+
+        let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let (user, address) = match authority.rsplit_once('@') {
+            Some((user, address)) => (Some(user), address),
+            None => (None, authority),
+        };
+        if let Some(user) = user {
+            if user.contains(':') {
+                return Err(ConnectionUrlError::PasswordNotAllowed);
+            }
+            if !user.is_empty() {
+                self.user = percent_decode(user)?;
+            }
+        }
+
+        let (host, port) = split_host_port(address)?;
+        self.host = percent_decode(host)?;
+        self.port = port.unwrap_or(DEFAULT_URL_PORT);
+        self.socket_path = None;
+        let database = percent_decode(path.trim_end_matches('/'))?;
+        self.database = (!database.is_empty()).then_some(database);
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let name = name.to_ascii_lowercase().replace('_', "-");
+            let value = percent_decode(value)?;
+            self.apply_url_parameter(&name, value)?;
+        }
+
+        if self.host.is_empty() {
+            if self.socket_path.is_none() {
+                return Err(ConnectionUrlError::MissingHost);
+            }
+            self.host = "localhost".to_string();
+        }
+        Ok(())
+    }
+
+    pub(crate) fn apply_url_parameter(
+        &mut self,
+        name: &str,
+        value: String,
+    ) -> Result<(), ConnectionUrlError> {
+        let invalid = |value: String| ConnectionUrlError::InvalidParameter {
+            name: name.to_string(),
+            value,
+        };
+        let non_empty = |value: String| (!value.is_empty()).then_some(value);
+        match name {
+            "ssl-mode" | "sslmode" => {
+                let (tls_mode, skip_domain_validation) =
+                    match value.to_ascii_uppercase().replace('-', "_").as_str() {
+                        "DISABLED" => (TlsMode::Disabled, false),
+                        "PREFERRED" => (TlsMode::Prefer, false),
+                        "REQUIRED" => (TlsMode::Require, false),
+                        "VERIFY_CA" => (TlsMode::VerifyIdentity, true),
+                        "VERIFY_IDENTITY" => (TlsMode::VerifyIdentity, false),
+                        _ => return Err(invalid(value)),
+                    };
+                self.tls_mode = tls_mode;
+                self.tls_skip_domain_validation = skip_domain_validation;
+            }
+            "ssl-ca" => self.tls_ca_cert_path = non_empty(value),
+            "ssl-cert" => self.tls_client_cert_path = non_empty(value),
+            "ssl-key" => self.tls_client_key_path = non_empty(value),
+            "socket" => self.socket_path = non_empty(value),
+            "connect-timeout" => {
+                self.connect_timeout_secs = Some(value.parse().map_err(|_| invalid(value))?);
+            }
+            _ => return Err(ConnectionUrlError::UnknownParameter(name.to_string())),
+        }
+        Ok(())
+    }
+
+    // Profiles never hold a password, so a shared URL cannot leak one. SSH jump and
+    // session settings have no URL form and are left out.
+    #[must_use]
+    pub fn to_url(&self) -> String {
+        let mut url = format!("mysql://{}@", percent_encode(&self.user, false));
+        let socket_path = self.socket_path();
+        if socket_path.is_some() {
+            url.push_str("localhost");
+        } else if self.host.contains(':') {
+            url.push_str(&format!("[{}]:{}", self.host, self.port));
+        } else {
+            url.push_str(&format!(
+                "{}:{}",
+                percent_encode(&self.host, false),
+                self.port
+            ));
+        }
+        if let Some(database) = self.database.as_deref().filter(|db| !db.is_empty()) {
+            url.push('/');
+            url.push_str(&percent_encode(database, false));
+        }
+
+        let ssl_mode = match self.tls_mode {
+            TlsMode::Prefer => None,
+            TlsMode::Disabled => Some("DISABLED"),
+            TlsMode::Require => Some("REQUIRED"),
+            TlsMode::VerifyIdentity if self.tls_skip_domain_validation => Some("VERIFY_CA"),
+            TlsMode::VerifyIdentity => Some("VERIFY_IDENTITY"),
+        };
+        let parameters = [
+            ("socket", socket_path),
+            ("ssl-mode", ssl_mode),
+            ("ssl-ca", self.tls_ca_cert_path.as_deref()),
+            ("ssl-cert", self.tls_client_cert_path.as_deref()),
+            ("ssl-key", self.tls_client_key_path.as_deref()),
+        ];
+        let mut separator = '?';
+        for (name, value) in parameters {
+            if let Some(value) = value.filter(|value| !value.is_empty()) {
+                url.push(separator);
+                url.push_str(&format!("{name}={}", percent_encode(value, true)));
+                separator = '&';
+            }
+        }
+        url
+    }
+}
+
+fn split_host_port(address: &str) -> Result<(&str, Option<u16>), ConnectionUrlError> {
+    let (host, port) = if let Some(bracketed) = address.strip_prefix('[') {
+        let (host, rest) = bracketed
+            .split_once(']')
+            .ok_or(ConnectionUrlError::MissingHost)?;
+        (host, rest.strip_prefix(':'))
+    } else {
+        match address.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (address, None),
+        }
+    };
+    let port = match port.filter(|port| !port.is_empty()) {
+        Some(port) => Some(
+            port.parse::<u16>()
+                .ok()
+                .filter(|port| *port > 0)
+                .ok_or_else(|| ConnectionUrlError::InvalidPort(port.to_string()))?,
+        ),
+        None => None,
+    };
+    Ok((host, port))
+}
+
+fn percent_decode(raw: &str) -> Result<String, ConnectionUrlError> {
+    let bytes = raw.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = bytes
+                .get(index + 1..index + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or(ConnectionUrlError::InvalidEncoding)?;
+            decoded.push(hex);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| ConnectionUrlError::InvalidEncoding)
+}
+
+// Query values keep `/` readable so certificate and socket paths survive a copy/paste.
+fn percent_encode(raw: &str, keep_slash: bool) -> String {
+    let mut encoded = String::with_capacity(raw.len());
+    for byte in raw.bytes() {
+        if byte.is_ascii_alphanumeric()
+            || matches!(byte, b'-' | b'.' | b'_' | b'~')
+            || (keep_slash && byte == b'/')
+        {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConnectionUrlError;
+    use crate::profiles::{ConnectionProfile, TlsMode};
+
+    #[test]
+    fn url_with_tls_parameters_parses_into_a_profile() {
+        let profile = ConnectionProfile::from_url(
+            "shared",
+            "mysql://app%40corp@db.internal:3307/billing?ssl-mode=VERIFY_CA&ssl_ca=/etc/ssl/ca.pem",
+        )
+        .expect("url should parse");
+
+        assert_eq!(profile.name, "shared");
+        assert_eq!(profile.user, "app@corp");
+        assert_eq!(profile.host, "db.internal");
+        assert_eq!(profile.port, 3307);
+        assert_eq!(profile.database.as_deref(), Some("billing"));
+        assert_eq!(profile.tls_mode, TlsMode::VerifyIdentity);
+        assert!(profile.tls_skip_domain_validation);
+        assert_eq!(profile.tls_ca_cert_path.as_deref(), Some("/etc/ssl/ca.pem"));
+    }
+
+    #[test]
+    fn ipv6_and_socket_addresses_are_supported() {
+        let ipv6 = ConnectionProfile::from_url("v6", "mysql://root@[::1]/").expect("ipv6 url");
+        assert_eq!(ipv6.host, "::1");
+        assert_eq!(ipv6.port, 3306);
+        assert_eq!(ipv6.database, None);
+
+        let socket = ConnectionProfile::from_url(
+            "local",
+            "mysql://root@/app?socket=%2Fvar%2Frun%2Fmysqld%2Fmysqld.sock",
+        )
+        .expect("socket url");
+        assert_eq!(socket.host, "localhost");
+        assert_eq!(socket.socket_path(), Some("/var/run/mysqld/mysqld.sock"));
+    }
+
+    #[test]
+    fn invalid_urls_are_rejected_with_a_reason() {
+        let parse = |url| ConnectionProfile::from_url("bad", url).map(|_| ());
+        assert_eq!(
+            parse("postgres://db/app"),
+            Err(ConnectionUrlError::UnsupportedScheme)
+        );
+        assert_eq!(
+            parse("mysql://app:secret@db/app"),
+            Err(ConnectionUrlError::PasswordNotAllowed)
+        );
+        assert_eq!(
+            parse("mysql://app@db:99999"),
+            Err(ConnectionUrlError::InvalidPort("99999".to_string()))
+        );
+        assert_eq!(
+            parse("mysql://app@db?charset=utf8mb4"),
+            Err(ConnectionUrlError::UnknownParameter("charset".to_string()))
+        );
+        assert_eq!(parse("mysql:///app"), Err(ConnectionUrlError::MissingHost));
+    }
+
+    #[test]
+    fn profiles_round_trip_through_urls_without_a_password() {
+        let mut profile = ConnectionProfile::new("prod", "db.example.com", "report user");
+        profile.database = Some("sales".to_string());
+        profile.tls_mode = TlsMode::Require;
+        profile.tls_client_cert_path = Some("/home/me/client.pem".to_string());
+        profile.tls_client_key_path = Some("/home/me/client.key".to_string());
+
+        let url = profile.to_url();
+        assert_eq!(
+            url,
+            "mysql://report%20user@db.example.com:3306/sales?ssl-mode=REQUIRED\
+             &ssl-cert=/home/me/client.pem&ssl-key=/home/me/client.key"
+        );
+        assert_eq!(
+            ConnectionProfile::from_url("prod", &url).expect("round trip"),
+            profile
+        );
+    }
+
+    #[test]
+    fn applying_a_url_keeps_settings_it_does_not_name() {
+        let mut profile = ConnectionProfile::new("ops", "old-host", "ops");
+        profile.socket_path = Some("/tmp/mysql.sock".to_string());
+        profile.read_only = true;
+        profile.tls_mode = TlsMode::Require;
+
+        profile
+            .apply_url("mysql://new-host:3310")
+            .expect("url should apply");
+
+        assert_eq!(profile.host, "new-host");
+        assert_eq!(profile.port, 3310);
+        assert_eq!(profile.user, "ops");
+        assert_eq!(profile.socket_path, None);
+        assert!(profile.read_only);
+        assert_eq!(profile.tls_mode, TlsMode::Require);
+    }
+}
@@ -2,11 +2,16 @@ pub mod actions_engine;
 pub mod audit_trail;
 pub mod bookmarks;
 pub mod connection_manager;
+pub mod connection_url;
 pub mod profiles;
+pub mod query_params;
 pub mod query_runner;
 pub mod results_buffer;
+pub mod results_store;
+pub mod retry_policy;
 pub mod safe_mode;
 pub mod schema_cache;
+pub mod server_capabilities;
 pub mod sql_generator;
 
 #[must_use]
@@ -1,11 +1,30 @@
 use std::env;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::retry_policy::{
+    RetryPolicy, DEFAULT_QUERY_RETRIES, DEFAULT_RECONNECT_ATTEMPTS, DEFAULT_RETRY_BACKOFF_MS,
+    MAX_RETRY_BACKOFF,
+};
+
+mod option_files;
+
+pub use option_files::{
+    default_option_file_path, import_option_file, OptionFileError, OptionFileImport,
+};
+
 const PROFILES_FORMAT_VERSION: u32 = 1;
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 8;
+pub const DEFAULT_QUERY_TIMEOUT_SECS: u64 = 20;
+pub const DEFAULT_KEEPALIVE_INTERVAL_SECS: u64 = 60;
+pub const DEFAULT_SSH_PORT: u16 = 22;
+pub const DEFAULT_PASSWORD_ENV_VAR: &str = "MYR_DB_PASSWORD";
+pub const DEFAULT_PASSWORD_COMMAND_TIMEOUT_SECS: u64 = 10;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
@@ -23,6 +42,95 @@ pub enum PasswordSource {
     #[default]
     EnvVar,
     Keyring,
+    Command,
+    File,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SshKnownHosts {
+    #[default]
+    Strict,
+    AcceptNew,
+    Off,
+}
+
+// Any tag other than the three well-known ones is kept verbatim as a custom environment.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub enum ProfileEnvironment {
+    Dev,
+    Staging,
+    Prod,
+    Custom(String),
+}
+
+impl ProfileEnvironment {
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        Some(match trimmed.to_ascii_lowercase().as_str() {
+            "dev" | "development" | "local" => Self::Dev,
+            "staging" | "stage" => Self::Staging,
+            "prod" | "production" => Self::Prod,
+            _ => Self::Custom(trimmed.to_string()),
+        })
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Dev => "dev",
+            Self::Staging => "staging",
+            Self::Prod => "prod",
+            Self::Custom(name) => name,
+        }
+    }
+
+    #[must_use]
+    pub fn label(&self) -> String {
+        self.name().to_uppercase()
+    }
+}
+
+impl From<String> for ProfileEnvironment {
+    fn from(value: String) -> Self {
+        Self::parse(&value).unwrap_or(Self::Custom(value))
+    }
+}
+
+impl From<ProfileEnvironment> for String {
+    fn from(environment: ProfileEnvironment) -> Self {
+        environment.name().to_string()
+    }
+}
+
+impl fmt::Display for ProfileEnvironment {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
+
+// A read replica of the profile's server. It shares the primary's user, password and TLS
+// settings; only where to connect differs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReplicaEndpoint {
+    pub host: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+}
+
+impl ReplicaEndpoint {
+    #[must_use]
+    pub fn new(host: impl Into<String>, port: Option<u16>) -> Self {
+        Self {
+            host: host.into(),
+            port,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -33,10 +141,22 @@ pub struct ConnectionProfile {
     pub user: String,
     pub database: Option<String>,
     #[serde(default)]
+    pub socket_path: Option<String>,
+    #[serde(default)]
     pub tls_mode: TlsMode,
     #[serde(default)]
     pub password_source: PasswordSource,
     #[serde(default)]
+    pub password_env_var: Option<String>,
+    #[serde(default)]
+    pub password_command: Option<String>,
+    #[serde(default)]
+    pub password_command_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub password_cache_secs: Option<u64>,
+    #[serde(default)]
+    pub password_file: Option<String>,
+    #[serde(default)]
     pub keyring_service: Option<String>,
     #[serde(default)]
     pub keyring_account: Option<String>,
@@ -57,9 +177,54 @@ pub struct ConnectionProfile {
     #[serde(default)]
     pub read_only: bool,
     #[serde(default)]
+    pub environment: Option<ProfileEnvironment>,
+    // A color name or `#rrggbb`; each environment has its own default.
+    #[serde(default)]
+    pub environment_color: Option<String>,
+    // Opts a prod profile out of its read-only default.
+    #[serde(default)]
+    pub allow_writes: bool,
+    #[serde(default)]
     pub is_default: bool,
     #[serde(default)]
     pub quick_reconnect: bool,
+    #[serde(default)]
+    pub fetch_warnings: bool,
+    #[serde(default)]
+    pub pinned_session: bool,
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub query_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub keepalive_interval_secs: Option<u64>,
+    #[serde(default)]
+    pub query_retries: Option<u32>,
+    #[serde(default)]
+    pub reconnect_attempts: Option<u32>,
+    #[serde(default)]
+    pub retry_backoff_ms: Option<u64>,
+    #[serde(default)]
+    pub max_execution_time_hint: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub init_sql: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replicas: Vec<ReplicaEndpoint>,
+    // Replicas further behind than this are skipped; unset trusts them without checking.
+    #[serde(default)]
+    pub max_replica_lag_secs: Option<u64>,
+    #[serde(default)]
+    pub ssh_host: Option<String>,
+    #[serde(default)]
+    pub ssh_port: Option<u16>,
+    #[serde(default)]
+    pub ssh_user: Option<String>,
+    #[serde(default)]
+    pub ssh_key_path: Option<String>,
+    #[serde(default)]
+    pub ssh_known_hosts: SshKnownHosts,
+    #[serde(default)]
+    pub ssh_disable_agent: bool,
 }
 
 impl ConnectionProfile {
@@ -71,8 +236,14 @@ impl ConnectionProfile {
             port: 3306,
             user: user.into(),
             database: None,
+            socket_path: None,
             tls_mode: TlsMode::Prefer,
             password_source: PasswordSource::EnvVar,
+            password_env_var: None,
+            password_command: None,
+            password_command_timeout_secs: None,
+            password_cache_secs: None,
+            password_file: None,
             keyring_service: None,
             keyring_account: None,
             tls_ca_cert_path: None,
@@ -83,8 +254,241 @@ impl ConnectionProfile {
             tls_accept_invalid_certs: false,
             tls_hostname_override: None,
             read_only: false,
+            environment: None,
+            environment_color: None,
+            allow_writes: false,
             is_default: false,
             quick_reconnect: false,
+            fetch_warnings: false,
+            pinned_session: false,
+            connect_timeout_secs: None,
+            query_timeout_secs: None,
+            keepalive_interval_secs: None,
+            query_retries: None,
+            reconnect_attempts: None,
+            retry_backoff_ms: None,
+            max_execution_time_hint: false,
+            init_sql: Vec::new(),
+            replicas: Vec::new(),
+            max_replica_lag_secs: None,
+            ssh_host: None,
+            ssh_port: None,
+            ssh_user: None,
+            ssh_key_path: None,
+            ssh_known_hosts: SshKnownHosts::Strict,
+            ssh_disable_agent: false,
+        }
+    }
+
+    #[must_use]
+    pub fn is_prod(&self) -> bool {
+        self.environment == Some(ProfileEnvironment::Prod)
+    }
+
+    // Prod connections start read-only; `read_only = true` always wins over `allow_writes`.
+    #[must_use]
+    pub fn is_read_only(&self) -> bool {
+        self.read_only || (self.is_prod() && !self.allow_writes)
+    }
+
+    #[must_use]
+    pub fn requires_safe_mode(&self) -> bool {
+        self.is_prod()
+    }
+
+    // Risky statements on prod are confirmed by typing the profile name, not a second Enter.
+    #[must_use]
+    pub fn requires_typed_confirmation(&self) -> bool {
+        self.is_prod()
+    }
+
+    #[must_use]
+    pub fn max_replica_lag(&self) -> Option<Duration> {
+        self.max_replica_lag_secs.map(Duration::from_secs)
+    }
+
+    // The same profile pointed at a replica. A local socket only ever reaches the primary,
+    // while an SSH jump host is kept so replicas behind the bastion stay reachable.
+    #[must_use]
+    pub fn replica_profile(&self, replica: &ReplicaEndpoint) -> Self {
+        let mut profile = self.clone();
+        profile.host.clone_from(&replica.host);
+        profile.port = replica.port.unwrap_or(self.port);
+        profile.socket_path = None;
+        profile.replicas = Vec::new();
+        profile
+    }
+
+    #[must_use]
+    pub fn socket_path(&self) -> Option<&str> {
+        self.socket_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+    }
+
+    // A local socket wins over an SSH jump: the socket only exists on this machine.
+    #[must_use]
+    pub fn transport(&self) -> ConnectionTransport<'_> {
+        if let Some(path) = self.socket_path() {
+            return ConnectionTransport::Socket { path };
+        }
+        match self.ssh_jump_host() {
+            Some(jump_host) => ConnectionTransport::SshJump {
+                jump_host,
+                host: &self.host,
+                port: self.port,
+            },
+            None => ConnectionTransport::Tcp {
+                host: &self.host,
+                port: self.port,
+            },
+        }
+    }
+
+    // A blank `ssh_host` means a direct connection, so the wizard can clear the jump host
+    // without deleting the rest of the SSH settings.
+    #[must_use]
+    pub fn ssh_jump_host(&self) -> Option<&str> {
+        self.ssh_host
+            .as_deref()
+            .map(str::trim)
+            .filter(|host| !host.is_empty())
+    }
+
+    #[must_use]
+    pub fn ssh_port(&self) -> u16 {
+        self.ssh_port.unwrap_or(DEFAULT_SSH_PORT)
+    }
+
+    #[must_use]
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.connect_timeout_secs
+                .unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS)
+                .max(1),
+        )
+    }
+
+    // `query_timeout_secs = 0` lifts the client-side limit for long-running analytics queries.
+    #[must_use]
+    pub fn query_timeout(&self) -> Option<Duration> {
+        match self
+            .query_timeout_secs
+            .unwrap_or(DEFAULT_QUERY_TIMEOUT_SECS)
+        {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        }
+    }
+
+    // Only a limit the user asked for, without the TUI's default.
+    #[must_use]
+    pub fn configured_query_timeout(&self) -> Option<Duration> {
+        self.query_timeout_secs
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+    }
+
+    // Pings well inside MySQL's default 8h `wait_timeout`, and far inside the short
+    // idle limits proxies and managed servers tend to use; `0` turns keepalive off.
+    #[must_use]
+    pub fn keepalive_interval(&self) -> Option<Duration> {
+        match self
+            .keepalive_interval_secs
+            .unwrap_or(DEFAULT_KEEPALIVE_INTERVAL_SECS)
+        {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        }
+    }
+
+    pub fn init_statements(&self) -> impl Iterator<Item = &str> {
+        self.init_sql
+            .iter()
+            .map(|statement| statement.trim())
+            .filter(|statement| !statement.is_empty())
+    }
+
+    #[must_use]
+    pub fn password_env_var(&self) -> &str {
+        self.password_env_var
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(DEFAULT_PASSWORD_ENV_VAR)
+    }
+
+    #[must_use]
+    pub fn password_command_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.password_command_timeout_secs
+                .unwrap_or(DEFAULT_PASSWORD_COMMAND_TIMEOUT_SECS)
+                .max(1),
+        )
+    }
+
+    // Command output is only reused when the profile opts in, since short-lived tokens
+    // must not outlive their own expiry.
+    #[must_use]
+    pub fn password_cache_ttl(&self) -> Option<Duration> {
+        self.password_cache_secs
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+    }
+
+    #[must_use]
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_query_retries: self.query_retries.unwrap_or(DEFAULT_QUERY_RETRIES),
+            max_reconnect_attempts: self
+                .reconnect_attempts
+                .unwrap_or(DEFAULT_RECONNECT_ATTEMPTS),
+            base_backoff: Duration::from_millis(
+                self.retry_backoff_ms.unwrap_or(DEFAULT_RETRY_BACKOFF_MS),
+            ),
+            max_backoff: MAX_RETRY_BACKOFF,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionTransport<'a> {
+    Tcp {
+        host: &'a str,
+        port: u16,
+    },
+    Socket {
+        path: &'a str,
+    },
+    SshJump {
+        jump_host: &'a str,
+        host: &'a str,
+        port: u16,
+    },
+}
+
+impl ConnectionTransport<'_> {
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Tcp { .. } => "tcp",
+            Self::Socket { .. } => "socket",
+            Self::SshJump { .. } => "ssh_jump",
+        }
+    }
+}
+
+impl fmt::Display for ConnectionTransport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp { host, port } => write!(f, "TCP {host}:{port}"),
+            Self::Socket { path } => write!(f, "unix socket {path}"),
+            Self::SshJump {
+                jump_host,
+                host,
+                port,
+            } => write!(f, "SSH jump via {jump_host} to {host}:{port}"),
         }
     }
 }
@@ -217,6 +621,43 @@ fn rename_table_key(table: &mut toml::map::Map<String, toml::Value>, from: &str,
     true
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportConflict {
+    #[default]
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub renamed: Vec<(String, String)>,
+    pub skipped: Vec<String>,
+}
+
+impl fmt::Display for ImportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} added", self.added.len())?;
+        if !self.overwritten.is_empty() {
+            write!(f, ", {} overwritten", self.overwritten.len())?;
+        }
+        if !self.renamed.is_empty() {
+            let renamed = self
+                .renamed
+                .iter()
+                .map(|(from, to)| format!("{from} -> {to}"))
+                .collect::<Vec<_>>();
+            write!(f, ", renamed {}", renamed.join(", "))?;
+        }
+        if !self.skipped.is_empty() {
+            write!(f, ", skipped existing {}", self.skipped.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FileProfilesStore {
     path: PathBuf,
@@ -331,6 +772,44 @@ impl FileProfilesStore {
         true
     }
 
+    // Markers stay with the stored profile on overwrite, since imported profiles never
+    // carry them. Nothing is written until `persist`.
+    pub fn import_profiles(
+        &mut self,
+        profiles: Vec<ConnectionProfile>,
+        on_conflict: ImportConflict,
+    ) -> ImportSummary {
+        let mut summary = ImportSummary::default();
+        for mut profile in profiles {
+            let Some(existing) = self.profile(&profile.name) else {
+                summary.added.push(profile.name.clone());
+                self.upsert_profile(profile);
+                continue;
+            };
+            match on_conflict {
+                ImportConflict::Skip => summary.skipped.push(profile.name),
+                ImportConflict::Overwrite => {
+                    profile.is_default = existing.is_default;
+                    profile.quick_reconnect = existing.quick_reconnect;
+                    summary.overwritten.push(profile.name.clone());
+                    self.upsert_profile(profile);
+                }
+                ImportConflict::Rename => {
+                    let renamed = (2..)
+                        .map(|suffix| format!("{}-{suffix}", profile.name))
+                        .find(|candidate| self.profile(candidate).is_none())
+                        .unwrap_or_default();
+                    summary.renamed.push((
+                        std::mem::replace(&mut profile.name, renamed.clone()),
+                        renamed,
+                    ));
+                    self.upsert_profile(profile);
+                }
+            }
+        }
+        summary
+    }
+
     #[must_use]
     pub fn delete_profile(&mut self, name: &str) -> bool {
         let original_len = self.profiles.len();
@@ -378,10 +857,15 @@ pub fn default_profiles_path() -> Result<PathBuf, ProfilesError> {
 mod tests {
     use std::fs;
     use std::path::PathBuf;
+    use std::time::Duration;
 
     use tempfile::TempDir;
 
-    use super::{ConnectionProfile, FileProfilesStore, PasswordSource, TlsMode};
+    use super::{
+        ConnectionProfile, ConnectionTransport, FileProfilesStore, ImportConflict, PasswordSource,
+        ProfileEnvironment, ReplicaEndpoint, SshKnownHosts, TlsMode,
+    };
+    use crate::retry_policy::RetryPolicy;
 
     fn temp_profiles_path(temp_dir: &TempDir) -> PathBuf {
         temp_dir.path().join("profiles.toml")
@@ -396,6 +880,219 @@ mod tests {
         assert!(store.profiles().is_empty());
     }
 
+    #[test]
+    fn timeouts_fall_back_to_defaults_and_zero_disables_query_timeout() {
+        let mut profile = ConnectionProfile::new("local", "127.0.0.1", "root");
+        assert_eq!(profile.connect_timeout(), Duration::from_secs(8));
+        assert_eq!(profile.query_timeout(), Some(Duration::from_secs(20)));
+        assert_eq!(profile.configured_query_timeout(), None);
+
+        profile.connect_timeout_secs = Some(0);
+        profile.query_timeout_secs = Some(0);
+        assert_eq!(profile.connect_timeout(), Duration::from_secs(1));
+        assert_eq!(profile.query_timeout(), None);
+
+        let parsed: ConnectionProfile = toml::from_str(
+            "name = \"analytics\"\nhost = \"db\"\nport = 3306\nuser = \"ro\"\nquery_timeout_secs = 600\n",
+        )
+        .expect("profile should parse");
+        assert_eq!(parsed.query_timeout(), Some(Duration::from_secs(600)));
+        assert_eq!(
+            parsed.configured_query_timeout(),
+            Some(Duration::from_secs(600))
+        );
+        assert!(!parsed.max_execution_time_hint);
+    }
+
+    #[test]
+    fn keepalive_interval_defaults_and_zero_disables_it() {
+        let mut profile = ConnectionProfile::new("local", "127.0.0.1", "root");
+        assert_eq!(profile.keepalive_interval(), Some(Duration::from_secs(60)));
+
+        profile.keepalive_interval_secs = Some(15);
+        assert_eq!(profile.keepalive_interval(), Some(Duration::from_secs(15)));
+
+        profile.keepalive_interval_secs = Some(0);
+        assert_eq!(profile.keepalive_interval(), None);
+    }
+
+    #[test]
+    fn retry_policy_reads_profile_overrides() {
+        let parsed: ConnectionProfile = toml::from_str(
+            "name = \"flaky\"\nhost = \"db\"\nport = 3306\nuser = \"app\"\n\
+             query_retries = 3\nreconnect_attempts = 0\nretry_backoff_ms = 50\n",
+        )
+        .expect("profile should parse");
+        let policy = parsed.retry_policy();
+        assert_eq!(policy.max_query_retries, 3);
+        assert_eq!(policy.max_reconnect_attempts, 0);
+        assert_eq!(policy.base_backoff, Duration::from_millis(50));
+
+        let defaults = ConnectionProfile::new("local", "127.0.0.1", "root").retry_policy();
+        assert_eq!(defaults, RetryPolicy::default());
+    }
+
+    #[test]
+    fn password_command_and_file_sources_parse_with_defaults() {
+        let parsed: ConnectionProfile = toml::from_str(
+            "name = \"vault\"\nhost = \"db\"\nport = 3306\nuser = \"app\"\n\
+             password_source = \"command\"\npassword_command = \"vault read -field=password db/app\"\n\
+             password_cache_secs = 300\n",
+        )
+        .expect("profile should parse");
+        assert_eq!(parsed.password_source, PasswordSource::Command);
+        assert_eq!(parsed.password_command_timeout(), Duration::from_secs(10));
+        assert_eq!(parsed.password_cache_ttl(), Some(Duration::from_secs(300)));
+        assert_eq!(parsed.password_env_var(), "MYR_DB_PASSWORD");
+
+        let mut profile = ConnectionProfile::new("file", "db", "app");
+        profile.password_source = PasswordSource::File;
+        profile.password_env_var = Some("APP_DB_PASSWORD".to_string());
+        profile.password_cache_secs = Some(0);
+        assert_eq!(profile.password_env_var(), "APP_DB_PASSWORD");
+        assert_eq!(profile.password_cache_ttl(), None);
+        let rendered = toml::to_string(&profile).expect("profile should serialize");
+        assert!(rendered.contains("password_source = \"file\""));
+    }
+
+    #[test]
+    fn init_sql_parses_as_a_list_and_is_omitted_when_empty() {
+        let parsed: ConnectionProfile = toml::from_str(
+            "name = \"prod\"\nhost = \"db\"\nport = 3306\nuser = \"app\"\n\
+             init_sql = [\"SET time_zone = '+00:00'\", \"  \", \"SET SESSION TRANSACTION READ ONLY\"]\n",
+        )
+        .expect("profile should parse");
+        assert_eq!(
+            parsed.init_statements().collect::<Vec<_>>(),
+            [
+                "SET time_zone = '+00:00'",
+                "SET SESSION TRANSACTION READ ONLY"
+            ]
+        );
+
+        let plain = ConnectionProfile::new("local", "127.0.0.1", "root");
+        let rendered = toml::to_string(&plain).expect("profile should serialize");
+        assert!(!rendered.contains("init_sql"));
+    }
+
+    #[test]
+    fn prod_environment_defaults_to_read_only_unless_writes_are_allowed() {
+        let parsed: ConnectionProfile = toml::from_str(
+            "name = \"prod\"\nhost = \"db\"\nport = 3306\nuser = \"app\"\n\
+             environment = \"Production\"\nenvironment_color = \"#ff5f00\"\n",
+        )
+        .expect("profile should parse");
+        assert_eq!(parsed.environment, Some(ProfileEnvironment::Prod));
+        assert_eq!(parsed.environment_color.as_deref(), Some("#ff5f00"));
+        assert!(parsed.is_read_only());
+        assert!(parsed.requires_safe_mode());
+        assert!(parsed.requires_typed_confirmation());
+
+        let mut writable = parsed.clone();
+        writable.allow_writes = true;
+        assert!(!writable.is_read_only());
+        writable.read_only = true;
+        assert!(writable.is_read_only());
+
+        let mut qa = ConnectionProfile::new("qa", "127.0.0.1", "root");
+        qa.environment = ProfileEnvironment::parse("qa-eu");
+        assert!(!qa.is_read_only() && !qa.requires_safe_mode());
+        let rendered = toml::to_string(&qa).expect("profile should serialize");
+        assert!(rendered.contains("environment = \"qa-eu\""));
+        assert_eq!(
+            qa.environment.as_ref().map(ProfileEnvironment::label),
+            Some("QA-EU".to_string())
+        );
+
+        let mut local = ConnectionProfile::new("laptop", "127.0.0.1", "root");
+        local.environment = ProfileEnvironment::parse("local");
+        let rendered = toml::to_string(&local).expect("profile should serialize");
+        assert!(rendered.contains("environment = \"dev\""), "{rendered}");
+    }
+
+    #[test]
+    fn replicas_parse_and_inherit_the_primary_settings() {
+        let parsed: ConnectionProfile = toml::from_str(
+            "name = \"shop\"\nhost = \"db-primary\"\nport = 3307\nuser = \"app\"\n\
+             socket_path = \"/run/mysqld/mysqld.sock\"\nmax_replica_lag_secs = 5\n\
+             [[replicas]]\nhost = \"db-r1\"\n\
+             [[replicas]]\nhost = \"db-r2\"\nport = 3306\n",
+        )
+        .expect("profile should parse");
+        assert_eq!(
+            parsed.replicas,
+            [
+                ReplicaEndpoint::new("db-r1", None),
+                ReplicaEndpoint::new("db-r2", Some(3306))
+            ]
+        );
+        assert_eq!(parsed.max_replica_lag(), Some(Duration::from_secs(5)));
+
+        let replica = parsed.replica_profile(&parsed.replicas[0]);
+        assert_eq!((replica.host.as_str(), replica.port), ("db-r1", 3307));
+        assert_eq!(replica.user, "app");
+        assert_eq!(replica.socket_path(), None);
+        assert!(replica.replicas.is_empty());
+
+        let plain = ConnectionProfile::new("local", "127.0.0.1", "root");
+        let rendered = toml::to_string(&plain).expect("profile should serialize");
+        assert!(!rendered.contains("replicas"));
+    }
+
+    #[test]
+    fn ssh_jump_settings_parse_with_defaults() {
+        let parsed: ConnectionProfile = toml::from_str(
+            "name = \"prod\"\nhost = \"db.internal\"\nport = 3306\nuser = \"app\"\n\
+             ssh_host = \"bastion.example.com\"\nssh_user = \"ops\"\n\
+             ssh_known_hosts = \"accept_new\"\n",
+        )
+        .expect("profile should parse");
+        assert_eq!(parsed.ssh_jump_host(), Some("bastion.example.com"));
+        assert_eq!(parsed.ssh_port(), 22);
+        assert_eq!(parsed.ssh_known_hosts, SshKnownHosts::AcceptNew);
+        assert!(!parsed.ssh_disable_agent);
+
+        assert_eq!(
+            parsed.transport().to_string(),
+            "SSH jump via bastion.example.com to db.internal:3306"
+        );
+
+        let mut direct = parsed.clone();
+        direct.ssh_host = Some("  ".to_string());
+        assert_eq!(direct.ssh_jump_host(), None);
+        assert_eq!(direct.transport().kind(), "tcp");
+        assert_eq!(
+            ConnectionProfile::new("local", "127.0.0.1", "root").ssh_jump_host(),
+            None
+        );
+    }
+
+    #[test]
+    fn socket_path_takes_precedence_over_tcp_and_ssh() {
+        let mut profile = ConnectionProfile::new("local", "localhost", "root");
+        profile.socket_path = Some(" ".to_string());
+        assert_eq!(
+            profile.transport(),
+            ConnectionTransport::Tcp {
+                host: "localhost",
+                port: 3306,
+            }
+        );
+
+        profile.socket_path = Some("/var/run/mysqld/mysqld.sock".to_string());
+        profile.ssh_host = Some("bastion".to_string());
+        assert_eq!(
+            profile.transport(),
+            ConnectionTransport::Socket {
+                path: "/var/run/mysqld/mysqld.sock",
+            }
+        );
+        assert_eq!(
+            profile.transport().to_string(),
+            "unix socket /var/run/mysqld/mysqld.sock"
+        );
+    }
+
     #[test]
     fn upsert_persist_reload_and_delete_profile() {
         let temp_dir = TempDir::new().expect("failed to create temp directory");
@@ -482,6 +1179,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn import_profiles_resolves_name_conflicts_per_policy() {
+        let temp_dir = TempDir::new().expect("failed to create temp directory");
+        let path = temp_profiles_path(&temp_dir);
+        let mut store = FileProfilesStore::load_from_path(path).expect("failed to load store");
+        store.upsert_profile(ConnectionProfile::new("prod", "old.prod", "root"));
+        store.upsert_profile(ConnectionProfile::new("prod-2", "older.prod", "root"));
+        assert!(store.set_default_profile("prod"));
+        let imported = || {
+            vec![
+                ConnectionProfile::new("prod", "db.prod", "app"),
+                ConnectionProfile::new("replica", "db.replica", "app"),
+            ]
+        };
+
+        let summary = store.import_profiles(imported(), ImportConflict::Skip);
+        assert_eq!(summary.added, ["replica"]);
+        assert_eq!(summary.skipped, ["prod"]);
+        assert_eq!(summary.to_string(), "1 added, skipped existing prod");
+        assert_eq!(
+            store.profile("prod").map(|p| p.host.as_str()),
+            Some("old.prod")
+        );
+
+        let summary = store.import_profiles(imported(), ImportConflict::Rename);
+        assert_eq!(
+            summary.renamed,
+            [
+                ("prod".to_string(), "prod-3".to_string()),
+                ("replica".to_string(), "replica-2".to_string())
+            ]
+        );
+        assert_eq!(
+            store.profile("prod-3").map(|p| p.host.as_str()),
+            Some("db.prod")
+        );
+
+        let summary = store.import_profiles(imported(), ImportConflict::Overwrite);
+        assert_eq!(summary.overwritten, ["prod", "replica"]);
+        let prod = store.profile("prod").expect("prod profile");
+        assert_eq!(prod.host, "db.prod");
+        assert!(prod.is_default);
+    }
+
     #[test]
     fn persist_writes_profiles_format_version() {
         let temp_dir = TempDir::new().expect("failed to create temp directory");
@@ -0,0 +1,525 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use super::{ConnectionProfile, TlsMode};
+
+// MySQL stops include loops with a depth limit rather than tracking visited files.
+const MAX_INCLUDE_DEPTH: usize = 10;
+
+// Every MySQL/MariaDB client reads these groups; a login path layers its own group on
+// top of them, which is how `mysql --login-path=name` resolves it.
+const CLIENT_GROUPS: [&str; 5] = [
+    "client",
+    "client-server",
+    "client-mariadb",
+    "mysql",
+    "mariadb-client",
+];
+
+// Server and bundled-tool groups (`mysqld`, `mysqldump`, `mariadb-10.6`, ...) configure
+// other programs, so they never become profiles.
+const NON_CONNECTION_GROUP_PREFIXES: [&str; 6] =
+    ["client", "mysql", "mariadb", "server", "galera", "embedded"];
+
+// Tools that share my.cnf without a mysql/mariadb prefix. Backup tools usually carry a
+// `user`/`host` of their own, so requiring connection options alone would not drop them.
+const TOOL_GROUPS: [&str; 10] = [
+    "xtrabackup",
+    "mariabackup",
+    "mariadb-backup",
+    "innobackupex",
+    "safe_mysqld",
+    "myisamchk",
+    "myisampack",
+    "isamchk",
+    "aria_chk",
+    "sst",
+];
+
+const CONNECTION_OPTIONS: [&str; 5] = ["host", "port", "socket", "user", "database"];
+
+#[derive(Debug, Error)]
+pub enum OptionFileError {
+    #[error("failed to read option file at {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error(
+        "{path} is encrypted by mysql_config_editor; run `mysql_config_editor print --all > <file>` and import that file"
+    )]
+    Encrypted { path: PathBuf },
+    #[error("{path}:{line}: {message}")]
+    Syntax {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    #[error("`!include` nesting deeper than {MAX_INCLUDE_DEPTH} levels at {path}")]
+    IncludeDepth { path: PathBuf },
+    #[error("invalid value `{value}` for `{option}` in option group [{group}]")]
+    InvalidValue {
+        group: String,
+        option: String,
+        value: String,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OptionFileImport {
+    pub profiles: Vec<ConnectionProfile>,
+    pub warnings: Vec<String>,
+}
+
+// Reads `path` and everything it pulls in through `!include`/`!includedir`. `[client]`
+// becomes a profile named `client` when it describes a server; every other client
+// group (a login path) becomes a profile of the same name on top of `[client]`.
+pub fn import_option_file(path: impl AsRef<Path>) -> Result<OptionFileImport, OptionFileError> {
+    let mut groups = OptionGroups::default();
+    read_option_file(path.as_ref(), 0, &mut groups)?;
+    groups.into_import()
+}
+
+#[must_use]
+pub fn default_option_file_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".my.cnf"))
+}
+
+#[derive(Debug, Default)]
+struct OptionGroups {
+    groups: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl OptionGroups {
+    fn push(&mut self, group: &str, option: String, value: String) {
+        let index = match self.groups.iter().position(|(name, _)| name == group) {
+            Some(index) => index,
+            None => {
+                self.groups.push((group.to_string(), Vec::new()));
+                self.groups.len() - 1
+            }
+        };
+        self.groups[index].1.push((option, value));
+    }
+
+    fn client_options(&self) -> Vec<&(String, String)> {
+        CLIENT_GROUPS
+            .iter()
+            .flat_map(|client_group| {
+                self.groups
+                    .iter()
+                    .filter(|(name, _)| name.eq_ignore_ascii_case(client_group))
+                    .flat_map(|(_, options)| options)
+            })
+            .collect()
+    }
+
+    fn into_import(self) -> Result<OptionFileImport, OptionFileError> {
+        let client = self.client_options();
+        let mut import = OptionFileImport::default();
+        if client
+            .iter()
+            .any(|(option, _)| CONNECTION_OPTIONS.contains(&option.as_str()))
+        {
+            import.add_profile("client", client.iter().copied())?;
+        }
+        for (group, options) in &self.groups {
+            if is_login_path(group, options) {
+                import.add_profile(group, client.iter().copied().chain(options))?;
+            }
+        }
+        Ok(import)
+    }
+}
+
+impl OptionFileImport {
+    fn add_profile<'a>(
+        &mut self,
+        name: &str,
+        options: impl Iterator<Item = &'a (String, String)>,
+    ) -> Result<(), OptionFileError> {
+        let mut profile = ConnectionProfile::new(name, "localhost", default_os_user());
+        let mut has_password = false;
+        for (option, value) in options {
+            has_password |= apply_option(&mut profile, option, value).map_err(|()| {
+                OptionFileError::InvalidValue {
+                    group: name.to_string(),
+                    option: option.clone(),
+                    value: value.clone(),
+                }
+            })?;
+        }
+
+        if has_password {
+            self.warnings.push(format!(
+                "`{name}`: password not imported; give the profile a password_source (env var, keyring, command or file)"
+            ));
+        }
+        self.profiles.push(profile);
+        Ok(())
+    }
+}
+
+// Returns whether the option was a password, which profiles deliberately never store.
+fn apply_option(profile: &mut ConnectionProfile, option: &str, value: &str) -> Result<bool, ()> {
+    match option {
+        "host" => profile.host = value.to_string(),
+        "port" => profile.port = value.parse().ok().filter(|port| *port > 0).ok_or(())?,
+        "user" => profile.user = value.to_string(),
+        "database" => profile.database = (!value.is_empty()).then(|| value.to_string()),
+        "password" => return Ok(true),
+        "ssl" => {
+            if !is_truthy(value) {
+                profile.tls_mode = TlsMode::Disabled;
+            } else if matches!(profile.tls_mode, TlsMode::Disabled | TlsMode::Prefer) {
+                profile.tls_mode = TlsMode::Require;
+            }
+        }
+        "ssl-verify-server-cert" if is_truthy(value) => {
+            profile.tls_mode = TlsMode::VerifyIdentity;
+            profile.tls_skip_domain_validation = false;
+        }
+        "ssl-mode" | "ssl-ca" | "ssl-cert" | "ssl-key" | "socket" | "connect-timeout" => {
+            profile
+                .apply_url_parameter(option, value.to_string())
+                .map_err(|_| ())?;
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+fn read_option_file(
+    path: &Path,
+    depth: usize,
+    groups: &mut OptionGroups,
+) -> Result<(), OptionFileError> {
+    if depth > MAX_INCLUDE_DEPTH {
+        return Err(OptionFileError::IncludeDepth {
+            path: path.to_path_buf(),
+        });
+    }
+    let bytes = fs::read(path).map_err(|source| OptionFileError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    // `.mylogin.cnf` opens with four unused zero bytes ahead of its AES key.
+    if bytes.starts_with(&[0, 0, 0, 0]) {
+        return Err(OptionFileError::Encrypted {
+            path: path.to_path_buf(),
+        });
+    }
+
+    let raw = String::from_utf8_lossy(&bytes);
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut current_group: Option<String> = None;
+    for (index, line) in raw.lines().enumerate() {
+        let syntax = |message: String| OptionFileError::Syntax {
+            path: path.to_path_buf(),
+            line: index + 1,
+            message,
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(directive) = line.strip_prefix('!') {
+            let (name, target) = directive
+                .split_once(char::is_whitespace)
+                .map_or((directive, ""), |(name, target)| (name, target.trim()));
+            if target.is_empty() {
+                return Err(syntax(format!("`!{name}` needs a path")));
+            }
+            let target = base_dir.join(target);
+            match name {
+                "include" => read_option_file(&target, depth + 1, groups)?,
+                "includedir" => {
+                    for included in included_option_files(&target)? {
+                        read_option_file(&included, depth + 1, groups)?;
+                    }
+                }
+                _ => return Err(syntax(format!("unknown directive `!{name}`"))),
+            }
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let group = header
+                .strip_suffix(']')
+                .map(str::trim)
+                .filter(|group| !group.is_empty())
+                .ok_or_else(|| syntax("malformed [group] header".to_string()))?;
+            current_group = Some(group.to_string());
+            continue;
+        }
+
+        let Some(group) = current_group.as_deref() else {
+            return Err(syntax(
+                "option appears before any [group] header".to_string(),
+            ));
+        };
+        let (name, value) = match line.split_once('=') {
+            Some((name, value)) => (
+                name,
+                parse_option_value(value)
+                    .ok_or_else(|| syntax("unterminated quoted value".to_string()))?,
+            ),
+            None => (strip_inline_comment(line), String::new()),
+        };
+        let (option, value) = normalize_option(name, value);
+        groups.push(group, option, value);
+    }
+    Ok(())
+}
+
+// MySQL reads `*.cnf` (and `*.ini` on Windows) from an `!includedir`, in name order.
+fn included_option_files(dir: &Path) -> Result<Vec<PathBuf>, OptionFileError> {
+    let entries = fs::read_dir(dir).map_err(|source| OptionFileError::Read {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+    let mut files = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension == "cnf" || extension == "ini")
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+// Dashes and underscores are interchangeable in option names, and the `loose-`,
+// `skip-`, `disable-` and `enable-` prefixes only change how a value is given.
+fn normalize_option(name: &str, value: String) -> (String, String) {
+    let name = name.trim().to_ascii_lowercase().replace('_', "-");
+    let name = name.strip_prefix("loose-").unwrap_or(&name);
+    if let Some(flag) = name
+        .strip_prefix("skip-")
+        .or_else(|| name.strip_prefix("disable-"))
+    {
+        return (flag.to_string(), "0".to_string());
+    }
+    if let Some(flag) = name.strip_prefix("enable-") {
+        return (flag.to_string(), "1".to_string());
+    }
+    (name.to_string(), value)
+}
+
+fn parse_option_value(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if let Some(quote) = raw.chars().next().filter(|ch| *ch == '"' || *ch == '\'') {
+        let inner = &raw[1..];
+        let mut escaped = false;
+        for (index, ch) in inner.char_indices() {
+            match ch {
+                '\\' if !escaped => escaped = true,
+                ch if ch == quote && !escaped => return Some(unescape(&inner[..index])),
+                _ => escaped = false,
+            }
+        }
+        return None;
+    }
+    Some(unescape(strip_inline_comment(raw).trim()))
+}
+
+fn strip_inline_comment(raw: &str) -> &str {
+    raw.split_once('#').map_or(raw, |(value, _)| value).trim()
+}
+
+// Unknown escapes keep their backslash so unquoted Windows paths survive.
+fn unescape(raw: &str) -> String {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            value.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('b') => value.push('\u{8}'),
+            Some('t') => value.push('\t'),
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('s') => value.push(' '),
+            Some('\\') => value.push('\\'),
+            Some('"') => value.push('"'),
+            Some('\'') => value.push('\''),
+            Some(other) => {
+                value.push('\\');
+                value.push(other);
+            }
+            None => value.push('\\'),
+        }
+    }
+    value
+}
+
+// A login path has to say where to connect; groups that only tune a program (key
+// buffers, `quick`, ...) are left alone.
+fn is_login_path(group: &str, options: &[(String, String)]) -> bool {
+    let group = group.to_ascii_lowercase();
+    !NON_CONNECTION_GROUP_PREFIXES
+        .iter()
+        .any(|prefix| group.starts_with(prefix))
+        && !TOOL_GROUPS.contains(&group.as_str())
+        && options
+            .iter()
+            .any(|(option, _)| CONNECTION_OPTIONS.contains(&option.as_str()))
+}
+
+fn is_truthy(value: &str) -> bool {
+    !matches!(
+        value.to_ascii_lowercase().as_str(),
+        "0" | "off" | "false" | "no"
+    )
+}
+
+// Like the mysql client, a missing `user` falls back to the login name.
+fn default_os_user() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_else(|_| "root".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::{import_option_file, OptionFileError};
+    use crate::profiles::TlsMode;
+
+    #[test]
+    fn client_group_and_login_paths_become_profiles() {
+        let temp_dir = TempDir::new().expect("failed to create temp directory");
+        let conf_dir = temp_dir.path().join("conf.d");
+        fs::create_dir(&conf_dir).expect("create include dir");
+        fs::write(
+            conf_dir.join("replica.cnf"),
+            "[replica]\nhost = replica.internal\nport=3307\n",
+        )
+        .expect("write included file");
+        fs::write(conf_dir.join("notes.txt"), "[ignored]\nhost=nope\n").expect("write notes");
+        fs::write(
+            temp_dir.path().join("prod.cnf"),
+            "[prod]\nhost=db.prod # primary\nssl-mode = VERIFY_IDENTITY\nssl_ca=\"/etc/ssl/My CA.pem\"\n",
+        )
+        .expect("write include file");
+        let path = temp_dir.path().join("my.cnf");
+        fs::write(
+            &path,
+            "# shared settings\n[client]\nuser=app\npassword='s3cr#t'\ndefault-character-set=utf8mb4\n\
+             \n[mysqld]\ndatadir=/var/lib/mysql\n\n[mysqldump]\nquick\n\n\
+             !include prod.cnf\n!includedir conf.d\n\n[analytics]\nsocket=/run/mysqld/mysqld.sock\ndatabase=warehouse\nskip-ssl\n",
+        )
+        .expect("write option file");
+
+        let import = import_option_file(&path).expect("option file should import");
+        let names = import
+            .profiles
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["client", "prod", "replica", "analytics"]);
+        assert!(import.profiles.iter().all(|profile| profile.user == "app"));
+
+        let client = &import.profiles[0];
+        assert_eq!(client.host, "localhost");
+        assert_eq!(client.port, 3306);
+
+        let prod = &import.profiles[1];
+        assert_eq!(prod.host, "db.prod");
+        assert_eq!(prod.tls_mode, TlsMode::VerifyIdentity);
+        assert_eq!(prod.tls_ca_cert_path.as_deref(), Some("/etc/ssl/My CA.pem"));
+
+        let replica = &import.profiles[2];
+        assert_eq!(replica.host, "replica.internal");
+        assert_eq!(replica.port, 3307);
+
+        let analytics = &import.profiles[3];
+        assert_eq!(analytics.socket_path(), Some("/run/mysqld/mysqld.sock"));
+        assert_eq!(analytics.database.as_deref(), Some("warehouse"));
+        assert_eq!(analytics.tls_mode, TlsMode::Disabled);
+
+        assert_eq!(import.warnings.len(), 4);
+        assert!(import.warnings[0].starts_with("`client`: password not imported"));
+    }
+
+    #[test]
+    fn server_and_tool_groups_in_a_typical_my_cnf_are_not_profiles() {
+        let temp_dir = TempDir::new().expect("failed to create temp directory");
+        let path = temp_dir.path().join("my.cnf");
+        fs::write(
+            &path,
+            "[client]\nport=3306\nsocket=/var/run/mysqld/mysqld.sock\n\n\
+             [mysqld_safe]\nsocket=/var/run/mysqld/mysqld.sock\nnice=0\n\n\
+             [mysqld]\nuser=mysql\nbind-address=127.0.0.1\nkey_buffer_size=16M\n\n\
+             [mysqldump]\nquick\nquote-names\nmax_allowed_packet=16M\n\n\
+             [mysqladmin]\nuser=root\n\n\
+             [mysqld_multi]\nmysqld=/usr/bin/mysqld_safe\nuser=multi_admin\n\n\
+             [xtrabackup]\nuser=backup\nhost=127.0.0.1\ntarget_dir=/backups\n\n\
+             [mariabackup]\nuser=backup\n\n\
+             [isamchk]\nkey_buffer=16M\n\n\
+             [tuning]\nmax_connections=500\n\n\
+             [reporting]\nhost=reports.internal\nuser=analyst\n",
+        )
+        .expect("write option file");
+
+        let import = import_option_file(&path).expect("option file should import");
+        let names = import
+            .profiles
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["client", "reporting"]);
+        assert_eq!(import.profiles[1].host, "reports.internal");
+        assert_eq!(import.profiles[1].user, "analyst");
+        assert!(import.warnings.is_empty());
+    }
+
+    #[test]
+    fn malformed_and_encrypted_option_files_are_rejected() {
+        let temp_dir = TempDir::new().expect("failed to create temp directory");
+
+        let orphan = temp_dir.path().join("orphan.cnf");
+        fs::write(&orphan, "host=db\n").expect("write option file");
+        assert!(matches!(
+            import_option_file(&orphan),
+            Err(OptionFileError::Syntax { line: 1, .. })
+        ));
+
+        let bad_port = temp_dir.path().join("port.cnf");
+        fs::write(&bad_port, "[prod]\nport=abc\n").expect("write option file");
+        let error = import_option_file(&bad_port).expect_err("port must be numeric");
+        assert_eq!(
+            error.to_string(),
+            "invalid value `abc` for `port` in option group [prod]"
+        );
+
+        let looping = temp_dir.path().join("loop.cnf");
+        fs::write(&looping, "!include loop.cnf\n").expect("write option file");
+        assert!(matches!(
+            import_option_file(&looping),
+            Err(OptionFileError::IncludeDepth { .. })
+        ));
+
+        let login = temp_dir.path().join(".mylogin.cnf");
+        fs::write(&login, [0, 0, 0, 0, 17, 42]).expect("write option file");
+        assert!(matches!(
+            import_option_file(&login),
+            Err(OptionFileError::Encrypted { .. })
+        ));
+    }
+}
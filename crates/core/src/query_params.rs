@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+
+use crate::query_runner::QueryValue;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterizedQuery {
+    sql: String,
+    bindings: Vec<String>,
+    parameter_names: Vec<String>,
+}
+
+impl ParameterizedQuery {
+    #[must_use]
+    pub fn parse(sql: &str) -> Option<Self> {
+        let mut rewritten = String::with_capacity(sql.len());
+        let mut bindings = Vec::new();
+        let mut chars = sql.chars().peekable();
+        let mut previous = None;
+        let mut positional_count = 0_usize;
+
+        let mut in_single_quote = false;
+        let mut in_double_quote = false;
+        let mut in_backtick = false;
+        let mut in_line_comment = false;
+        let mut in_block_comment = false;
+
+        while let Some(ch) = chars.next() {
+            rewritten.push(ch);
+
+            if in_line_comment {
+                in_line_comment = ch != '\n';
+            } else if in_block_comment {
+                if ch == '*' && chars.peek() == Some(&'/') {
+                    rewritten.push('/');
+                    chars.next();
+                    in_block_comment = false;
+                }
+            } else if in_single_quote || in_double_quote {
+                let quote = if in_single_quote { '\'' } else { '"' };
+                if ch == '\\' {
+                    if let Some(escaped) = chars.next() {
+                        rewritten.push(escaped);
+                    }
+                } else if ch == quote {
+                    in_single_quote = false;
+                    in_double_quote = false;
+                }
+            } else if in_backtick {
+                in_backtick = ch != '`';
+            } else {
+                match ch {
+                    '\'' => in_single_quote = true,
+                    '"' => in_double_quote = true,
+                    '`' => in_backtick = true,
+                    '#' => in_line_comment = true,
+                    '-' if chars.peek() == Some(&'-') => in_line_comment = true,
+                    '/' if chars.peek() == Some(&'*') => {
+                        rewritten.push('*');
+                        chars.next();
+                        in_block_comment = true;
+                    }
+                    '?' => {
+                        positional_count += 1;
+                        bindings.push(format!("?{positional_count}"));
+                    }
+                    ':' if !previous.is_some_and(is_identifier_char)
+                        && chars
+                            .peek()
+                            .is_some_and(|next| next.is_ascii_alphabetic() || *next == '_') =>
+                    {
+                        rewritten.pop();
+                        rewritten.push('?');
+                        let mut name = String::new();
+                        while let Some(next) =
+                            chars.peek().copied().filter(|c| is_identifier_char(*c))
+                        {
+                            name.push(next);
+                            chars.next();
+                        }
+                        bindings.push(format!(":{name}"));
+                    }
+                    _ => {}
+                }
+            }
+            previous = rewritten.chars().last();
+        }
+
+        if bindings.is_empty() {
+            return None;
+        }
+
+        let mut parameter_names = Vec::new();
+        for binding in &bindings {
+            if !parameter_names.contains(binding) {
+                parameter_names.push(binding.clone());
+            }
+        }
+
+        Some(Self {
+            sql: rewritten,
+            bindings,
+            parameter_names,
+        })
+    }
+
+    #[must_use]
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    #[must_use]
+    pub fn parameter_names(&self) -> &[String] {
+        &self.parameter_names
+    }
+
+    #[must_use]
+    pub fn bind(&self, values: &BTreeMap<String, String>) -> Vec<QueryValue> {
+        self.bindings
+            .iter()
+            .map(|binding| {
+                values
+                    .get(binding)
+                    .map_or(QueryValue::Null, |raw| parse_parameter_value(raw))
+            })
+            .collect()
+    }
+}
+
+#[must_use]
+pub fn parse_parameter_value(raw: &str) -> QueryValue {
+    let trimmed = raw.trim();
+    if trimmed.eq_ignore_ascii_case("null") {
+        return QueryValue::Null;
+    }
+    if trimmed.len() >= 2 && trimmed.starts_with('\'') && trimmed.ends_with('\'') {
+        return QueryValue::Text(trimmed[1..trimmed.len() - 1].to_string());
+    }
+    let digits = trimmed.strip_prefix('-').unwrap_or(trimmed);
+    let canonical_integer = !digits.is_empty()
+        && digits.bytes().all(|byte| byte.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'));
+    if canonical_integer {
+        if let Ok(value) = trimmed.parse::<i64>() {
+            return QueryValue::Int(value);
+        }
+    }
+    QueryValue::Text(raw.to_string())
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{parse_parameter_value, ParameterizedQuery};
+    use crate::query_runner::QueryValue;
+
+    #[test]
+    fn sql_without_placeholders_is_not_parameterized() {
+        assert!(ParameterizedQuery::parse("SELECT * FROM users LIMIT 10").is_none());
+        assert!(ParameterizedQuery::parse("SELECT '?', \"a:b\", `c?` -- why?\n").is_none());
+        assert!(ParameterizedQuery::parse("SET @total := 1 /* :skip ? */").is_none());
+    }
+
+    #[test]
+    fn positional_and_named_placeholders_are_rewritten_in_order() {
+        let query = ParameterizedQuery::parse(
+            "SELECT * FROM users WHERE id = ? AND (email = :email OR backup = :email) LIMIT ?",
+        )
+        .expect("query should be parameterized");
+
+        assert_eq!(
+            query.sql(),
+            "SELECT * FROM users WHERE id = ? AND (email = ? OR backup = ?) LIMIT ?"
+        );
+        assert_eq!(query.parameter_names(), ["?1", ":email", "?2"]);
+
+        let values = BTreeMap::from([
+            ("?1".to_string(), "42".to_string()),
+            (":email".to_string(), "a@example.com".to_string()),
+        ]);
+        assert_eq!(
+            query.bind(&values),
+            vec![
+                QueryValue::Int(42),
+                QueryValue::from("a@example.com"),
+                QueryValue::from("a@example.com"),
+                QueryValue::Null,
+            ]
+        );
+    }
+
+    #[test]
+    fn placeholders_inside_literals_and_comments_are_ignored() {
+        let query = ParameterizedQuery::parse(
+            "SELECT 'it''s ?', 'a\\':b' # :comment\nFROM t WHERE x = :x /* ? */",
+        )
+        .expect("query should be parameterized");
+
+        assert_eq!(
+            query.sql(),
+            "SELECT 'it''s ?', 'a\\':b' # :comment\nFROM t WHERE x = ? /* ? */"
+        );
+        assert_eq!(query.parameter_names(), [":x"]);
+    }
+
+    #[test]
+    fn parameter_values_are_typed_conservatively() {
+        assert_eq!(parse_parameter_value("NULL"), QueryValue::Null);
+        assert_eq!(parse_parameter_value(" -17 "), QueryValue::Int(-17));
+        assert_eq!(parse_parameter_value("007"), QueryValue::from("007"));
+        assert_eq!(parse_parameter_value("1.50"), QueryValue::from("1.50"));
+        assert_eq!(parse_parameter_value("'NULL'"), QueryValue::from("NULL"));
+        assert_eq!(parse_parameter_value(""), QueryValue::from(""));
+    }
+}
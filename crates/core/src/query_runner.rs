@@ -1,23 +1,406 @@
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::pin::pin;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
-    Arc,
+    Arc, Mutex,
 };
+use std::task::{Poll, Waker};
 use std::time::Duration;
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::results_buffer::ResultsRingBuffer;
+use crate::results_buffer::{ResultsRingBuffer, RowFootprint};
+use crate::results_store::ResultsStore;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum QueryValue {
+    Null,
+    Int(i64),
+    UInt(u64),
+    Decimal(String),
+    Float(f64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Date {
+        year: u16,
+        month: u8,
+        day: u8,
+    },
+    DateTime {
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        micros: u32,
+    },
+    Time {
+        negative: bool,
+        days: u32,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        micros: u32,
+    },
+    Json(String),
+    Truncated {
+        preview: String,
+        full_bytes: usize,
+    },
+}
+
+impl QueryValue {
+    #[must_use]
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    #[must_use]
+    pub fn is_truncated(&self) -> bool {
+        matches!(self, Self::Truncated { .. })
+    }
+
+    #[must_use]
+    pub fn heap_bytes(&self) -> usize {
+        match self {
+            Self::Decimal(text) | Self::Text(text) | Self::Json(text) => text.len(),
+            Self::Bytes(bytes) => bytes.len(),
+            Self::Truncated { preview, .. } => preview.len(),
+            _ => 0,
+        }
+    }
+
+    fn truncated(&self, max_bytes: usize) -> Option<Self> {
+        let full_bytes = self.heap_bytes();
+        if full_bytes <= max_bytes {
+            return None;
+        }
+        let preview = match self {
+            Self::Decimal(text) | Self::Text(text) | Self::Json(text) => {
+                let mut end = max_bytes;
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                text[..end].to_string()
+            }
+            Self::Bytes(bytes) => String::from_utf8_lossy(&bytes[..max_bytes]).into_owned(),
+            _ => return None,
+        };
+        Some(Self::Truncated {
+            preview,
+            full_bytes,
+        })
+    }
+
+    #[must_use]
+    pub fn to_compat_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for QueryValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("NULL"),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::UInt(value) => write!(f, "{value}"),
+            Self::Decimal(value) | Self::Text(value) | Self::Json(value) => f.write_str(value),
+            Self::Truncated {
+                preview,
+                full_bytes,
+            } => write!(f, "{preview}… [truncated, {full_bytes} bytes]"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::Bytes(bytes) => f.write_str(&String::from_utf8_lossy(bytes)),
+            Self::Date { year, month, day } => write!(f, "{year:04}-{month:02}-{day:02}"),
+            Self::DateTime {
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                micros,
+            } => {
+                write!(
+                    f,
+                    "{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02}"
+                )?;
+                write_fraction(f, *micros)
+            }
+            Self::Time {
+                negative,
+                days,
+                hours,
+                minutes,
+                seconds,
+                micros,
+            } => {
+                let sign = if *negative { "-" } else { "" };
+                let total_hours = u64::from(*days) * 24 + u64::from(*hours);
+                write!(f, "{sign}{total_hours:02}:{minutes:02}:{seconds:02}")?;
+                write_fraction(f, *micros)
+            }
+        }
+    }
+}
+
+fn write_fraction(f: &mut fmt::Formatter<'_>, micros: u32) -> fmt::Result {
+    if micros == 0 {
+        Ok(())
+    } else {
+        write!(f, ".{micros:06}")
+    }
+}
+
+impl From<String> for QueryValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for QueryValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<i64> for QueryValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u64> for QueryValue {
+    fn from(value: u64) -> Self {
+        Self::UInt(value)
+    }
+}
+
+impl<T: Into<QueryValue>> From<Option<T>> for QueryValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct QueryRow {
-    pub values: Vec<String>,
+    pub values: Vec<QueryValue>,
 }
 
 impl QueryRow {
     #[must_use]
-    pub fn new(values: Vec<String>) -> Self {
-        Self { values }
+    pub fn new<V: Into<QueryValue>>(values: impl IntoIterator<Item = V>) -> Self {
+        Self {
+            values: values.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn to_compat_strings(&self) -> Vec<String> {
+        self.values
+            .iter()
+            .map(QueryValue::to_compat_string)
+            .collect()
+    }
+}
+
+impl RowFootprint for QueryRow {
+    fn approximate_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self
+                .values
+                .iter()
+                .map(|value| std::mem::size_of::<QueryValue>() + value.heap_bytes())
+                .sum::<usize>()
+    }
+
+    fn largest_cell_bytes(&self) -> usize {
+        self.values
+            .iter()
+            .map(QueryValue::heap_bytes)
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn truncate_cells(&mut self, max_cell_bytes: usize) -> usize {
+        let mut truncated = 0;
+        for value in &mut self.values {
+            if let Some(preview) = value.truncated(max_cell_bytes) {
+                *value = preview;
+                truncated += 1;
+            }
+        }
+        truncated
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlType {
+    TinyInt,
+    SmallInt,
+    MediumInt,
+    Int,
+    BigInt,
+    Decimal,
+    Float,
+    Double,
+    Bit,
+    Year,
+    Date,
+    Time,
+    DateTime,
+    Timestamp,
+    Char,
+    VarChar,
+    Binary,
+    VarBinary,
+    Text,
+    Blob,
+    Enum,
+    Set,
+    Json,
+    Geometry,
+    Null,
+    Unknown,
+}
+
+impl SqlType {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::TinyInt => "TINYINT",
+            Self::SmallInt => "SMALLINT",
+            Self::MediumInt => "MEDIUMINT",
+            Self::Int => "INT",
+            Self::BigInt => "BIGINT",
+            Self::Decimal => "DECIMAL",
+            Self::Float => "FLOAT",
+            Self::Double => "DOUBLE",
+            Self::Bit => "BIT",
+            Self::Year => "YEAR",
+            Self::Date => "DATE",
+            Self::Time => "TIME",
+            Self::DateTime => "DATETIME",
+            Self::Timestamp => "TIMESTAMP",
+            Self::Char => "CHAR",
+            Self::VarChar => "VARCHAR",
+            Self::Binary => "BINARY",
+            Self::VarBinary => "VARBINARY",
+            Self::Text => "TEXT",
+            Self::Blob => "BLOB",
+            Self::Enum => "ENUM",
+            Self::Set => "SET",
+            Self::Json => "JSON",
+            Self::Geometry => "GEOMETRY",
+            Self::Null => "NULL",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+
+    #[must_use]
+    pub fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            Self::TinyInt
+                | Self::SmallInt
+                | Self::MediumInt
+                | Self::Int
+                | Self::BigInt
+                | Self::Decimal
+                | Self::Float
+                | Self::Double
+                | Self::Year
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultColumn {
+    pub name: String,
+    pub sql_type: SqlType,
+    pub length: u32,
+    pub decimals: u8,
+    pub not_null: bool,
+    pub primary_key: bool,
+    pub unsigned: bool,
+    pub binary: bool,
+    pub charset: u16,
+    pub origin_table: Option<String>,
+    pub origin_column: Option<String>,
+}
+
+impl ResultColumn {
+    #[must_use]
+    pub fn new(name: impl Into<String>, sql_type: SqlType) -> Self {
+        Self {
+            name: name.into(),
+            sql_type,
+            length: 0,
+            decimals: 0,
+            not_null: false,
+            primary_key: false,
+            unsigned: false,
+            binary: false,
+            charset: 0,
+            origin_table: None,
+            origin_column: None,
+        }
+    }
+
+    #[must_use]
+    pub fn type_label(&self) -> String {
+        let mut label = self.sql_type.name().to_string();
+        if self.unsigned {
+            label.push_str(" UNSIGNED");
+        }
+        label
+    }
+
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let mut parts = vec![self.type_label()];
+        if self.not_null {
+            parts.push("NOT NULL".to_string());
+        }
+        if self.primary_key {
+            parts.push("PK".to_string());
+        }
+        if let (Some(table), Some(column)) = (&self.origin_table, &self.origin_column) {
+            parts.push(format!("from {table}.{column}"));
+        }
+        parts.join(" ")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryWarning {
+    pub level: String,
+    pub code: u32,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StatementStatus {
+    pub affected_rows: u64,
+    pub last_insert_id: Option<u64>,
+    pub info: Option<String>,
+    pub warning_count: u16,
+    pub warnings: Vec<QueryWarning>,
+}
+
+impl StatementStatus {
+    #[must_use]
+    pub fn outcome(&self) -> String {
+        format_statement_outcome(
+            self.affected_rows,
+            self.last_insert_id,
+            self.warning_count,
+            self.info.as_deref(),
+        )
     }
 }
 
@@ -25,6 +408,7 @@ impl QueryRow {
 #[error("{message}")]
 pub struct QueryBackendError {
     message: String,
+    code: Option<u16>,
 }
 
 impl QueryBackendError {
@@ -32,8 +416,21 @@ impl QueryBackendError {
     pub fn new(message: impl Into<String>) -> Self {
         Self {
             message: message.into(),
+            code: None,
         }
     }
+
+    #[must_use]
+    pub fn with_code(mut self, code: Option<u16>) -> Self {
+        self.code = code;
+        self
+    }
+
+    // MySQL server error number, or the client `CR_*` number for transport failures.
+    #[must_use]
+    pub fn code(&self) -> Option<u16> {
+        self.code
+    }
 }
 
 #[derive(Debug, Error)]
@@ -42,9 +439,19 @@ pub enum QueryRunnerError {
     Backend(#[source] QueryBackendError),
 }
 
+impl QueryRunnerError {
+    #[must_use]
+    pub fn code(&self) -> Option<u16> {
+        match self {
+            Self::Backend(error) => error.code(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct CancellationToken {
     cancelled: Arc<AtomicBool>,
+    wakers: Arc<Mutex<Vec<Waker>>>,
 }
 
 impl CancellationToken {
@@ -55,19 +462,160 @@ impl CancellationToken {
 
     pub fn cancel(&self) {
         self.cancelled.store(true, Ordering::SeqCst);
+        let wakers = self
+            .wakers
+            .lock()
+            .map(|mut wakers| std::mem::take(&mut *wakers))
+            .unwrap_or_default();
+        for waker in wakers {
+            waker.wake();
+        }
     }
 
     #[must_use]
     pub fn is_cancelled(&self) -> bool {
         self.cancelled.load(Ordering::SeqCst)
     }
+
+    fn register(&self, waker: &Waker) {
+        if let Ok(mut wakers) = self.wakers.lock() {
+            if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+                wakers.push(waker.clone());
+            }
+        }
+    }
+
+    pub async fn run_until_cancelled<F: Future>(&self, future: F) -> Option<F::Output> {
+        let mut future = pin!(future);
+        poll_fn(|cx| {
+            if self.is_cancelled() {
+                return Poll::Ready(None);
+            }
+            if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                return Poll::Ready(Some(output));
+            }
+            self.register(cx.waker());
+            if self.is_cancelled() {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+// Trips `cancellation` when `deadline` completes instead of dropping `execution`, so a
+// statement that runs out of time is killed on the server exactly like a user cancel.
+// The flag reports whether the deadline fired.
+pub async fn run_with_deadline<F: Future>(
+    cancellation: &CancellationToken,
+    deadline: impl Future<Output = ()>,
+    execution: F,
+) -> (F::Output, bool) {
+    let mut deadline = pin!(deadline);
+    let mut execution = pin!(execution);
+    let mut timed_out = false;
+    poll_fn(|cx| {
+        if !timed_out && deadline.as_mut().poll(cx).is_ready() {
+            timed_out = true;
+            cancellation.cancel();
+        }
+        execution
+            .as_mut()
+            .poll(cx)
+            .map(|output| (output, timed_out))
+    })
+    .await
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerCancellation {
+    ClientOnly,
+    Killed { connection_id: u32 },
+    KillFailed { connection_id: u32, message: String },
+}
+
+impl ServerCancellation {
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            Self::ClientOnly => "client stream closed".to_string(),
+            Self::Killed { connection_id } => {
+                format!("server query killed on connection {connection_id}")
+            }
+            Self::KillFailed {
+                connection_id,
+                message,
+            } => format!("KILL QUERY {connection_id} failed: {message}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResultSetCapture {
+    pub columns: Vec<ResultColumn>,
+    pub rows: ResultsRingBuffer<QueryRow>,
+    pub rows_streamed: u64,
+    pub status: StatementStatus,
+}
+
+#[derive(Debug, Clone)]
 pub struct QueryExecutionSummary {
     pub rows_streamed: u64,
     pub was_cancelled: bool,
     pub elapsed: Duration,
+    pub columns: Vec<ResultColumn>,
+    pub affected_rows: u64,
+    pub last_insert_id: Option<u64>,
+    pub info: Option<String>,
+    pub warning_count: u16,
+    pub warnings: Vec<QueryWarning>,
+    pub additional_result_sets: Vec<ResultSetCapture>,
+    pub server_cancellation: Option<ServerCancellation>,
+    pub session_state_lost: bool,
+}
+
+impl QueryExecutionSummary {
+    #[must_use]
+    pub fn result_set_count(&self) -> usize {
+        1 + self.additional_result_sets.len()
+    }
+
+    #[must_use]
+    pub fn statement_outcome(&self) -> String {
+        format_statement_outcome(
+            self.affected_rows,
+            self.last_insert_id,
+            self.warning_count,
+            self.info.as_deref(),
+        )
+    }
+}
+
+fn format_statement_outcome(
+    affected_rows: u64,
+    last_insert_id: Option<u64>,
+    warning_count: u16,
+    info: Option<&str>,
+) -> String {
+    let mut parts = vec![format!(
+        "{affected_rows} row{} affected",
+        if affected_rows == 1 { "" } else { "s" }
+    )];
+    if let Some(last_insert_id) = last_insert_id {
+        parts.push(format!("last insert id {last_insert_id}"));
+    }
+    if warning_count > 0 {
+        parts.push(format!(
+            "{warning_count} warning{}",
+            if warning_count == 1 { "" } else { "s" }
+        ));
+    }
+    if let Some(info) = info {
+        parts.push(info.to_string());
+    }
+    parts.join(", ")
 }
 
 #[async_trait]
@@ -76,10 +624,26 @@ pub trait QueryRowStream: Send {
         None
     }
 
+    fn column_metadata(&self) -> Option<&[ResultColumn]> {
+        None
+    }
+
+    fn statement_status(&self) -> Option<&StatementStatus> {
+        None
+    }
+
+    fn session_state_lost(&self) -> bool {
+        false
+    }
+
     async fn next_row(&mut self) -> Result<Option<QueryRow>, QueryBackendError>;
 
-    async fn cancel(&mut self) -> Result<(), QueryBackendError> {
-        Ok(())
+    async fn next_result_set(&mut self) -> Result<bool, QueryBackendError> {
+        Ok(false)
+    }
+
+    async fn cancel(&mut self) -> Result<ServerCancellation, QueryBackendError> {
+        Ok(ServerCancellation::ClientOnly)
     }
 }
 
@@ -88,6 +652,12 @@ pub trait QueryBackend {
     type Stream: QueryRowStream + Send;
 
     async fn start_query(&self, sql: &str) -> Result<Self::Stream, QueryBackendError>;
+
+    async fn start_prepared(
+        &self,
+        sql: &str,
+        params: &[QueryValue],
+    ) -> Result<Self::Stream, QueryBackendError>;
 }
 
 #[derive(Debug)]
@@ -104,44 +674,163 @@ impl<B: QueryBackend> QueryRunner<B> {
     pub async fn execute_streaming(
         &self,
         sql: &str,
-        buffer: &mut ResultsRingBuffer<QueryRow>,
+        buffer: &mut impl ResultsStore<QueryRow>,
         cancellation: &CancellationToken,
     ) -> Result<QueryExecutionSummary, QueryRunnerError> {
         let started_at = std::time::Instant::now();
-        let mut stream = self
+        let stream = self
             .backend
             .start_query(sql)
             .await
             .map_err(QueryRunnerError::Backend)?;
+        stream_execution(stream, started_at, buffer, cancellation, &mut |_, _| {}).await
+    }
 
-        let mut rows_streamed = 0_u64;
-        let mut was_cancelled = false;
-
-        while !cancellation.is_cancelled() {
-            let maybe_row = stream.next_row().await.map_err(QueryRunnerError::Backend)?;
-            let Some(row) = maybe_row else {
-                return Ok(QueryExecutionSummary {
-                    rows_streamed,
-                    was_cancelled,
-                    elapsed: started_at.elapsed(),
-                });
-            };
+    pub async fn execute_prepared_streaming(
+        &self,
+        sql: &str,
+        params: &[QueryValue],
+        buffer: &mut impl ResultsStore<QueryRow>,
+        cancellation: &CancellationToken,
+    ) -> Result<QueryExecutionSummary, QueryRunnerError> {
+        let started_at = std::time::Instant::now();
+        let stream = self
+            .backend
+            .start_prepared(sql, params)
+            .await
+            .map_err(QueryRunnerError::Backend)?;
+        stream_execution(stream, started_at, buffer, cancellation, &mut |_, _| {}).await
+    }
 
-            buffer.push(row);
-            rows_streamed += 1;
+    // Same as the streaming variants, but hands every row of the first result set to
+    // `on_row` as it arrives so callers can show progress before the query finishes.
+    pub async fn execute_observed(
+        &self,
+        sql: &str,
+        params: &[QueryValue],
+        buffer: &mut impl ResultsStore<QueryRow>,
+        cancellation: &CancellationToken,
+        on_row: &mut (dyn FnMut(&[ResultColumn], &QueryRow) + Send),
+    ) -> Result<QueryExecutionSummary, QueryRunnerError> {
+        let started_at = std::time::Instant::now();
+        let stream = if params.is_empty() {
+            self.backend.start_query(sql).await
+        } else {
+            self.backend.start_prepared(sql, params).await
         }
+        .map_err(QueryRunnerError::Backend)?;
+        stream_execution(stream, started_at, buffer, cancellation, on_row).await
+    }
+}
 
-        stream.cancel().await.map_err(QueryRunnerError::Backend)?;
-        was_cancelled = true;
+async fn stream_execution(
+    mut stream: impl QueryRowStream,
+    started_at: std::time::Instant,
+    buffer: &mut impl ResultsStore<QueryRow>,
+    cancellation: &CancellationToken,
+    on_row: &mut (dyn FnMut(&[ResultColumn], &QueryRow) + Send),
+) -> Result<QueryExecutionSummary, QueryRunnerError> {
+    let mut columns = stream_column_metadata(&stream);
+    let (rows_streamed, mut was_cancelled) =
+        stream_result_set(&mut stream, buffer, &mut columns, cancellation, on_row).await?;
+    let mut summary = build_summary(
+        &stream,
+        rows_streamed,
+        was_cancelled,
+        started_at.elapsed(),
+        columns,
+    );
 
-        Ok(QueryExecutionSummary {
-            rows_streamed,
-            was_cancelled,
-            elapsed: started_at.elapsed(),
-        })
+    while !was_cancelled
+        && stream
+            .next_result_set()
+            .await
+            .map_err(QueryRunnerError::Backend)?
+    {
+        let mut capture = ResultSetCapture {
+            columns: stream_column_metadata(&stream),
+            rows: ResultsRingBuffer::new(buffer.capacity()),
+            rows_streamed: 0,
+            status: StatementStatus::default(),
+        };
+        (capture.rows_streamed, was_cancelled) = stream_result_set(
+            &mut stream,
+            &mut capture.rows,
+            &mut capture.columns,
+            cancellation,
+            &mut |_, _| {},
+        )
+        .await?;
+        capture.status = stream.statement_status().cloned().unwrap_or_default();
+        summary.additional_result_sets.push(capture);
+    }
+
+    if was_cancelled {
+        summary.server_cancellation =
+            Some(stream.cancel().await.map_err(QueryRunnerError::Backend)?);
+        summary.was_cancelled = true;
+    }
+    summary.elapsed = started_at.elapsed();
+    Ok(summary)
+}
+
+async fn stream_result_set(
+    stream: &mut impl QueryRowStream,
+    buffer: &mut impl ResultsStore<QueryRow>,
+    columns: &mut Vec<ResultColumn>,
+    cancellation: &CancellationToken,
+    on_row: &mut (dyn FnMut(&[ResultColumn], &QueryRow) + Send),
+) -> Result<(u64, bool), QueryRunnerError> {
+    let mut rows_streamed = 0_u64;
+    loop {
+        let Some(maybe_row) = cancellation.run_until_cancelled(stream.next_row()).await else {
+            return Ok((rows_streamed, true));
+        };
+        let maybe_row = maybe_row.map_err(QueryRunnerError::Backend)?;
+        if columns.is_empty() {
+            *columns = stream_column_metadata(stream);
+        }
+        let Some(row) = maybe_row else {
+            return Ok((rows_streamed, false));
+        };
+
+        on_row(columns, &row);
+        buffer.push(row);
+        rows_streamed += 1;
+    }
+}
+
+fn build_summary(
+    stream: &impl QueryRowStream,
+    rows_streamed: u64,
+    was_cancelled: bool,
+    elapsed: Duration,
+    columns: Vec<ResultColumn>,
+) -> QueryExecutionSummary {
+    let status = stream.statement_status().cloned().unwrap_or_default();
+    QueryExecutionSummary {
+        rows_streamed,
+        was_cancelled,
+        elapsed,
+        columns,
+        affected_rows: status.affected_rows,
+        last_insert_id: status.last_insert_id,
+        info: status.info,
+        warning_count: status.warning_count,
+        warnings: status.warnings,
+        additional_result_sets: Vec::new(),
+        server_cancellation: None,
+        session_state_lost: stream.session_state_lost(),
     }
 }
 
+fn stream_column_metadata(stream: &impl QueryRowStream) -> Vec<ResultColumn> {
+    stream
+        .column_metadata()
+        .map(<[ResultColumn]>::to_vec)
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::VecDeque;
@@ -151,32 +840,65 @@ mod tests {
     };
 
     use super::{
-        CancellationToken, QueryBackend, QueryBackendError, QueryRow, QueryRowStream, QueryRunner,
+        run_with_deadline, CancellationToken, QueryBackend, QueryBackendError, QueryRow,
+        QueryRowStream, QueryRunner, QueryValue, QueryWarning, ResultColumn, ServerCancellation,
+        SqlType, StatementStatus,
     };
-    use crate::results_buffer::ResultsRingBuffer;
+    use crate::results_buffer::{ResultsRingBuffer, RowFootprint};
 
     #[derive(Debug, Clone)]
     struct FakeQueryBackend {
         rows: Vec<QueryRow>,
+        columns: Vec<ResultColumn>,
+        status: Option<StatementStatus>,
+        extra_sets: Vec<FakeResultSet>,
         cancel_called: Arc<AtomicBool>,
     }
 
+    #[derive(Debug, Clone)]
+    struct FakeResultSet {
+        rows: Vec<QueryRow>,
+        columns: Vec<ResultColumn>,
+        status: Option<StatementStatus>,
+    }
+
     #[derive(Debug)]
     struct FakeStream {
         rows: VecDeque<QueryRow>,
+        columns: Vec<ResultColumn>,
+        status: Option<StatementStatus>,
+        extra_sets: VecDeque<FakeResultSet>,
         cancel_called: Arc<AtomicBool>,
         _state: Mutex<usize>,
     }
 
     #[async_trait::async_trait]
     impl QueryRowStream for FakeStream {
+        fn column_metadata(&self) -> Option<&[ResultColumn]> {
+            Some(&self.columns)
+        }
+
+        fn statement_status(&self) -> Option<&StatementStatus> {
+            self.status.as_ref()
+        }
+
         async fn next_row(&mut self) -> Result<Option<QueryRow>, QueryBackendError> {
             Ok(self.rows.pop_front())
         }
 
-        async fn cancel(&mut self) -> Result<(), QueryBackendError> {
+        async fn next_result_set(&mut self) -> Result<bool, QueryBackendError> {
+            let Some(next) = self.extra_sets.pop_front() else {
+                return Ok(false);
+            };
+            self.rows = next.rows.into();
+            self.columns = next.columns;
+            self.status = next.status;
+            Ok(true)
+        }
+
+        async fn cancel(&mut self) -> Result<ServerCancellation, QueryBackendError> {
             self.cancel_called.store(true, Ordering::SeqCst);
-            Ok(())
+            Ok(ServerCancellation::Killed { connection_id: 7 })
         }
     }
 
@@ -187,6 +909,24 @@ mod tests {
         async fn start_query(&self, _sql: &str) -> Result<Self::Stream, QueryBackendError> {
             Ok(FakeStream {
                 rows: self.rows.iter().cloned().collect(),
+                columns: self.columns.clone(),
+                status: self.status.clone(),
+                extra_sets: self.extra_sets.iter().cloned().collect(),
+                cancel_called: Arc::clone(&self.cancel_called),
+                _state: Mutex::new(0),
+            })
+        }
+
+        async fn start_prepared(
+            &self,
+            _sql: &str,
+            params: &[QueryValue],
+        ) -> Result<Self::Stream, QueryBackendError> {
+            Ok(FakeStream {
+                rows: VecDeque::from([QueryRow::new(params.to_vec())]),
+                columns: self.columns.clone(),
+                status: self.status.clone(),
+                extra_sets: VecDeque::new(),
                 cancel_called: Arc::clone(&self.cancel_called),
                 _state: Mutex::new(0),
             })
@@ -202,6 +942,9 @@ mod tests {
                 QueryRow::new(vec!["2".to_string()]),
                 QueryRow::new(vec!["3".to_string()]),
             ],
+            columns: Vec::new(),
+            status: None,
+            extra_sets: Vec::new(),
             cancel_called: Arc::clone(&cancel_called),
         };
         let runner = QueryRunner::new(backend);
@@ -218,11 +961,11 @@ mod tests {
         assert_eq!(buffer.len(), 2);
         assert_eq!(
             buffer.get(0).map(|row| &row.values[0]),
-            Some(&"2".to_string())
+            Some(&QueryValue::Text("2".to_string()))
         );
         assert_eq!(
             buffer.get(1).map(|row| &row.values[0]),
-            Some(&"3".to_string())
+            Some(&QueryValue::Text("3".to_string()))
         );
         assert!(!cancel_called.load(Ordering::SeqCst));
     }
@@ -235,6 +978,9 @@ mod tests {
                 QueryRow::new(vec!["1".to_string()]),
                 QueryRow::new(vec!["2".to_string()]),
             ],
+            columns: Vec::new(),
+            status: None,
+            extra_sets: Vec::new(),
             cancel_called: Arc::clone(&cancel_called),
         };
         let runner = QueryRunner::new(backend);
@@ -251,5 +997,415 @@ mod tests {
         assert!(summary.was_cancelled);
         assert!(cancel_called.load(Ordering::SeqCst));
         assert!(buffer.is_empty());
+        assert_eq!(
+            summary.server_cancellation,
+            Some(ServerCancellation::Killed { connection_id: 7 })
+        );
+    }
+
+    #[derive(Debug)]
+    struct StallingBackend;
+
+    #[derive(Debug)]
+    struct StallingStream;
+
+    #[async_trait::async_trait]
+    impl QueryRowStream for StallingStream {
+        async fn next_row(&mut self) -> Result<Option<QueryRow>, QueryBackendError> {
+            std::future::pending().await
+        }
+
+        async fn cancel(&mut self) -> Result<ServerCancellation, QueryBackendError> {
+            Ok(ServerCancellation::KillFailed {
+                connection_id: 9,
+                message: "Access denied".to_string(),
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl QueryBackend for StallingBackend {
+        type Stream = StallingStream;
+
+        async fn start_query(&self, _sql: &str) -> Result<Self::Stream, QueryBackendError> {
+            Ok(StallingStream)
+        }
+
+        async fn start_prepared(
+            &self,
+            _sql: &str,
+            _params: &[QueryValue],
+        ) -> Result<Self::Stream, QueryBackendError> {
+            Ok(StallingStream)
+        }
+    }
+
+    #[tokio::test]
+    async fn cancellation_interrupts_a_query_waiting_for_its_first_row() {
+        let runner = QueryRunner::new(StallingBackend);
+        let cancellation = CancellationToken::new();
+        let mut buffer = ResultsRingBuffer::new(2);
+
+        let canceller = async {
+            tokio::task::yield_now().await;
+            cancellation.cancel();
+        };
+        let (summary, ()) = tokio::join!(
+            runner.execute_streaming("select sleep(600)", &mut buffer, &cancellation),
+            canceller
+        );
+        let summary = summary.expect("cancelled query should still summarize");
+
+        assert!(summary.was_cancelled);
+        assert_eq!(
+            summary
+                .server_cancellation
+                .as_ref()
+                .map(ServerCancellation::describe)
+                .as_deref(),
+            Some("KILL QUERY 9 failed: Access denied")
+        );
+    }
+
+    #[tokio::test]
+    async fn deadline_cancels_through_the_stream_instead_of_dropping_it() {
+        let runner = QueryRunner::new(StallingBackend);
+        let cancellation = CancellationToken::new();
+        let mut buffer = ResultsRingBuffer::new(2);
+
+        let (summary, timed_out) = run_with_deadline(
+            &cancellation,
+            tokio::task::yield_now(),
+            runner.execute_streaming("select sleep(600)", &mut buffer, &cancellation),
+        )
+        .await;
+        let summary = summary.expect("timed-out query should still summarize");
+
+        assert!(timed_out);
+        assert!(cancellation.is_cancelled());
+        assert!(summary.was_cancelled);
+        assert_eq!(
+            summary.server_cancellation,
+            Some(ServerCancellation::KillFailed {
+                connection_id: 9,
+                message: "Access denied".to_string(),
+            })
+        );
+    }
+
+    #[derive(Debug)]
+    struct ReconnectedBackend;
+
+    #[derive(Debug)]
+    struct ReconnectedStream;
+
+    #[async_trait::async_trait]
+    impl QueryRowStream for ReconnectedStream {
+        fn session_state_lost(&self) -> bool {
+            true
+        }
+
+        async fn next_row(&mut self) -> Result<Option<QueryRow>, QueryBackendError> {
+            Ok(None)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl QueryBackend for ReconnectedBackend {
+        type Stream = ReconnectedStream;
+
+        async fn start_query(&self, _sql: &str) -> Result<Self::Stream, QueryBackendError> {
+            Ok(ReconnectedStream)
+        }
+
+        async fn start_prepared(
+            &self,
+            _sql: &str,
+            _params: &[QueryValue],
+        ) -> Result<Self::Stream, QueryBackendError> {
+            Ok(ReconnectedStream)
+        }
+    }
+
+    #[tokio::test]
+    async fn summary_reports_lost_session_state() {
+        let runner = QueryRunner::new(ReconnectedBackend);
+        let mut buffer = ResultsRingBuffer::new(2);
+
+        let summary = runner
+            .execute_streaming("select @x", &mut buffer, &CancellationToken::new())
+            .await
+            .expect("query should succeed");
+
+        assert!(summary.session_state_lost);
+        assert_eq!(summary.rows_streamed, 0);
+    }
+
+    #[tokio::test]
+    async fn summary_carries_stream_column_metadata() {
+        let mut id = ResultColumn::new("id", SqlType::BigInt);
+        id.not_null = true;
+        id.primary_key = true;
+        id.unsigned = true;
+        id.origin_table = Some("users".to_string());
+        id.origin_column = Some("id".to_string());
+        let backend = FakeQueryBackend {
+            rows: vec![QueryRow::new(vec![QueryValue::UInt(1)])],
+            columns: vec![id.clone()],
+            status: None,
+            extra_sets: Vec::new(),
+            cancel_called: Arc::new(AtomicBool::new(false)),
+        };
+        let runner = QueryRunner::new(backend);
+        let mut buffer = ResultsRingBuffer::new(2);
+
+        let summary = runner
+            .execute_streaming(
+                "select id from users",
+                &mut buffer,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("query should succeed");
+
+        assert_eq!(summary.columns, vec![id]);
+        assert!(summary.columns[0].sql_type.is_numeric());
+        assert_eq!(
+            summary.columns[0].describe(),
+            "BIGINT UNSIGNED NOT NULL PK from users.id"
+        );
+    }
+
+    #[tokio::test]
+    async fn summary_reports_dml_status_from_stream() {
+        let backend = FakeQueryBackend {
+            rows: Vec::new(),
+            columns: Vec::new(),
+            status: Some(StatementStatus {
+                affected_rows: 3,
+                last_insert_id: Some(42),
+                info: Some("Records: 3  Duplicates: 0  Warnings: 1".to_string()),
+                warning_count: 1,
+                warnings: vec![QueryWarning {
+                    level: "Warning".to_string(),
+                    code: 1265,
+                    message: "Data truncated for column 'name' at row 2".to_string(),
+                }],
+            }),
+            extra_sets: Vec::new(),
+            cancel_called: Arc::new(AtomicBool::new(false)),
+        };
+        let runner = QueryRunner::new(backend);
+        let mut buffer = ResultsRingBuffer::new(2);
+
+        let summary = runner
+            .execute_streaming(
+                "insert into users (name) values ('a'), ('b'), ('c')",
+                &mut buffer,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("query should succeed");
+
+        assert_eq!(summary.rows_streamed, 0);
+        assert_eq!(summary.affected_rows, 3);
+        assert_eq!(summary.last_insert_id, Some(42));
+        assert_eq!(summary.warnings.len(), 1);
+        assert_eq!(
+            summary.statement_outcome(),
+            "3 rows affected, last insert id 42, 1 warning, Records: 3  Duplicates: 0  Warnings: 1"
+        );
+    }
+
+    #[tokio::test]
+    async fn additional_result_sets_are_captured_in_order() {
+        let backend = FakeQueryBackend {
+            rows: vec![QueryRow::new(vec![QueryValue::Int(1)])],
+            columns: vec![ResultColumn::new("id", SqlType::BigInt)],
+            status: None,
+            extra_sets: vec![
+                FakeResultSet {
+                    rows: vec![
+                        QueryRow::new(vec!["a@example.com"]),
+                        QueryRow::new(vec!["b@example.com"]),
+                    ],
+                    columns: vec![ResultColumn::new("email", SqlType::VarChar)],
+                    status: None,
+                },
+                FakeResultSet {
+                    rows: Vec::new(),
+                    columns: Vec::new(),
+                    status: Some(StatementStatus {
+                        affected_rows: 2,
+                        ..StatementStatus::default()
+                    }),
+                },
+            ],
+            cancel_called: Arc::new(AtomicBool::new(false)),
+        };
+        let runner = QueryRunner::new(backend);
+        let mut buffer = ResultsRingBuffer::new(4);
+
+        let summary = runner
+            .execute_streaming("call list_users()", &mut buffer, &CancellationToken::new())
+            .await
+            .expect("query should succeed");
+
+        assert_eq!(summary.result_set_count(), 3);
+        assert_eq!(summary.rows_streamed, 1);
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(summary.columns[0].name, "id");
+
+        let emails = &summary.additional_result_sets[0];
+        assert_eq!(emails.columns[0].name, "email");
+        assert_eq!(emails.rows_streamed, 2);
+        assert_eq!(emails.rows.capacity(), 4);
+        assert_eq!(
+            emails.rows.get(1).map(|row| &row.values[0]),
+            Some(&QueryValue::Text("b@example.com".to_string()))
+        );
+
+        let trailer = &summary.additional_result_sets[1];
+        assert!(trailer.columns.is_empty());
+        assert_eq!(trailer.status.affected_rows, 2);
+    }
+
+    #[tokio::test]
+    async fn prepared_execution_passes_bound_parameters_to_backend() {
+        let backend = FakeQueryBackend {
+            rows: Vec::new(),
+            columns: vec![ResultColumn::new("id", SqlType::BigInt)],
+            status: None,
+            extra_sets: Vec::new(),
+            cancel_called: Arc::new(AtomicBool::new(false)),
+        };
+        let runner = QueryRunner::new(backend);
+        let mut buffer = ResultsRingBuffer::new(4);
+
+        let summary = runner
+            .execute_prepared_streaming(
+                "select * from users where id = ? and email = ?",
+                &[QueryValue::Int(7), QueryValue::Null],
+                &mut buffer,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("prepared query should succeed");
+
+        assert_eq!(summary.rows_streamed, 1);
+        assert_eq!(
+            buffer.get(0).map(|row| row.values.clone()),
+            Some(vec![QueryValue::Int(7), QueryValue::Null])
+        );
+    }
+
+    #[tokio::test]
+    async fn observed_execution_reports_rows_as_they_stream() {
+        let backend = FakeQueryBackend {
+            rows: vec![
+                QueryRow::new(vec!["1".to_string()]),
+                QueryRow::new(vec!["2".to_string()]),
+                QueryRow::new(vec!["3".to_string()]),
+            ],
+            columns: vec![ResultColumn::new("id", SqlType::BigInt)],
+            status: None,
+            extra_sets: Vec::new(),
+            cancel_called: Arc::new(AtomicBool::new(false)),
+        };
+        let runner = QueryRunner::new(backend);
+        let mut buffer = ResultsRingBuffer::new(2);
+        let mut observed = Vec::new();
+
+        let summary = runner
+            .execute_observed(
+                "select id from users",
+                &[],
+                &mut buffer,
+                &CancellationToken::new(),
+                &mut |columns, row| {
+                    observed.push((columns[0].name.clone(), row.values[0].clone()));
+                },
+            )
+            .await
+            .expect("observed query should succeed");
+
+        assert_eq!(summary.rows_streamed, 3);
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(
+            observed,
+            vec![
+                ("id".to_string(), QueryValue::from("1")),
+                ("id".to_string(), QueryValue::from("2")),
+                ("id".to_string(), QueryValue::from("3")),
+            ]
+        );
+    }
+
+    #[test]
+    fn null_stays_distinct_from_null_text() {
+        let row = QueryRow::new(vec![QueryValue::Null, QueryValue::from("NULL")]);
+
+        assert!(row.values[0].is_null());
+        assert!(!row.values[1].is_null());
+        assert_ne!(row.values[0], row.values[1]);
+        assert_eq!(row.to_compat_strings(), vec!["NULL", "NULL"]);
+    }
+
+    #[test]
+    fn oversized_cells_truncate_to_a_marked_preview() {
+        let mut row = QueryRow::new(vec![
+            QueryValue::Int(1),
+            QueryValue::from("héllo world"),
+            QueryValue::Bytes(vec![b'x'; 64]),
+            QueryValue::from("ok"),
+        ]);
+        assert_eq!(row.largest_cell_bytes(), 64);
+        let before = row.approximate_bytes();
+
+        assert_eq!(row.truncate_cells(2), 2);
+        assert!(row.approximate_bytes() < before);
+        assert_eq!(
+            row.values[1],
+            QueryValue::Truncated {
+                preview: "h".to_string(),
+                full_bytes: 12,
+            }
+        );
+        assert_eq!(row.values[1].to_string(), "h… [truncated, 12 bytes]");
+        assert!(row.values[2].is_truncated());
+        assert_eq!(row.values[3], QueryValue::from("ok"));
+    }
+
+    #[test]
+    fn temporal_values_render_like_server_text() {
+        let datetime = QueryValue::DateTime {
+            year: 2026,
+            month: 2,
+            day: 24,
+            hour: 12,
+            minute: 5,
+            second: 9,
+            micros: 0,
+        };
+        let time = QueryValue::Time {
+            negative: true,
+            days: 1,
+            hours: 2,
+            minutes: 3,
+            seconds: 4,
+            micros: 500,
+        };
+
+        assert_eq!(datetime.to_string(), "2026-02-24 12:05:09");
+        assert_eq!(time.to_string(), "-26:03:04.000500");
+        assert_eq!(
+            QueryValue::Date {
+                year: 2026,
+                month: 2,
+                day: 24
+            }
+            .to_string(),
+            "2026-02-24"
+        );
     }
 }
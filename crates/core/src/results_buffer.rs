@@ -1,10 +1,40 @@
 use std::collections::VecDeque;
 
+pub trait RowFootprint {
+    fn approximate_bytes(&self) -> usize;
+
+    fn largest_cell_bytes(&self) -> usize;
+
+    fn truncate_cells(&mut self, max_cell_bytes: usize) -> usize;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetUsage {
+    pub used_bytes: usize,
+    pub max_bytes: usize,
+    pub rows_in_memory: usize,
+    pub truncated_cells: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct ResultsRingBuffer<T> {
     capacity: usize,
     rows: VecDeque<T>,
     total_rows_seen: u64,
+    byte_budget: Option<ByteBudget<T>>,
+}
+
+// Row sizes are remembered so evictions can give their bytes back without
+// re-measuring; the function pointers keep the plain ring buffer usable for any `T`.
+#[derive(Debug, Clone)]
+struct ByteBudget<T> {
+    max_bytes: usize,
+    max_cell_bytes: usize,
+    used_bytes: usize,
+    row_bytes: VecDeque<usize>,
+    truncated_cells: u64,
+    weigh: fn(&T) -> usize,
+    truncate: fn(&mut T, usize) -> usize,
 }
 
 impl<T> ResultsRingBuffer<T> {
@@ -18,9 +48,45 @@ impl<T> ResultsRingBuffer<T> {
             capacity,
             rows: VecDeque::with_capacity(capacity),
             total_rows_seen: 0,
+            byte_budget: None,
         }
     }
 
+    #[must_use]
+    pub fn with_byte_budget(mut self, max_bytes: usize, max_cell_bytes: usize) -> Self
+    where
+        T: RowFootprint,
+    {
+        self.byte_budget = Some(ByteBudget {
+            max_bytes,
+            max_cell_bytes,
+            used_bytes: 0,
+            row_bytes: VecDeque::new(),
+            truncated_cells: 0,
+            weigh: T::approximate_bytes,
+            truncate: T::truncate_cells,
+        });
+        self
+    }
+
+    #[must_use]
+    pub fn budget_usage(&self) -> Option<BudgetUsage> {
+        self.byte_budget.as_ref().map(|budget| BudgetUsage {
+            used_bytes: budget.used_bytes,
+            max_bytes: budget.max_bytes,
+            rows_in_memory: self.rows.len(),
+            truncated_cells: budget.truncated_cells,
+        })
+    }
+
+    #[must_use]
+    pub fn would_evict(&self, row: &T) -> bool {
+        self.rows.len() == self.capacity
+            || self.byte_budget.as_ref().is_some_and(|budget| {
+                !self.rows.is_empty() && budget.used_bytes + (budget.weigh)(row) > budget.max_bytes
+            })
+    }
+
     #[must_use]
     pub fn capacity(&self) -> usize {
         self.capacity
@@ -54,12 +120,35 @@ impl<T> ResultsRingBuffer<T> {
         Some(self.total_rows_seen - 1)
     }
 
-    pub fn push(&mut self, row: T) {
+    pub fn push(&mut self, mut row: T) {
         if self.rows.len() == self.capacity {
-            self.rows.pop_front();
+            self.pop_oldest();
+        }
+        if let Some(budget) = self.byte_budget.as_mut() {
+            budget.truncated_cells += (budget.truncate)(&mut row, budget.max_cell_bytes) as u64;
+            let bytes = (budget.weigh)(&row);
+            budget.used_bytes += bytes;
+            budget.row_bytes.push_back(bytes);
         }
         self.rows.push_back(row);
         self.total_rows_seen += 1;
+
+        while self.rows.len() > 1
+            && self
+                .byte_budget
+                .as_ref()
+                .is_some_and(|budget| budget.used_bytes > budget.max_bytes)
+        {
+            self.pop_oldest();
+        }
+    }
+
+    fn pop_oldest(&mut self) {
+        self.rows.pop_front();
+        if let Some(budget) = self.byte_budget.as_mut() {
+            let bytes = budget.row_bytes.pop_front().unwrap_or_default();
+            budget.used_bytes = budget.used_bytes.saturating_sub(bytes);
+        }
     }
 
     #[must_use]
@@ -84,7 +173,25 @@ impl<T> ResultsRingBuffer<T> {
 
 #[cfg(test)]
 mod tests {
-    use super::ResultsRingBuffer;
+    use super::{BudgetUsage, ResultsRingBuffer, RowFootprint};
+
+    impl RowFootprint for String {
+        fn approximate_bytes(&self) -> usize {
+            self.len()
+        }
+
+        fn largest_cell_bytes(&self) -> usize {
+            self.len()
+        }
+
+        fn truncate_cells(&mut self, max_cell_bytes: usize) -> usize {
+            if self.len() <= max_cell_bytes {
+                return 0;
+            }
+            self.truncate(max_cell_bytes);
+            1
+        }
+    }
 
     #[test]
     fn keeps_memory_bounded_to_capacity() {
@@ -113,6 +220,31 @@ mod tests {
         assert_eq!(rows, vec![&20, &30]);
     }
 
+    #[test]
+    fn byte_budget_evicts_by_size_and_truncates_oversized_rows() {
+        let mut buffer = ResultsRingBuffer::new(10).with_byte_budget(10, 6);
+        buffer.push("aaaa".to_string());
+        buffer.push("bbbb".to_string());
+        assert!(buffer.would_evict(&"cccc".to_string()));
+        buffer.push("cccc".to_string());
+
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.get(0), Some(&"bbbb".to_string()));
+
+        buffer.push("dddddddddd".to_string());
+        assert_eq!(buffer.get(buffer.len() - 1), Some(&"dddddd".to_string()));
+        assert_eq!(
+            buffer.budget_usage(),
+            Some(BudgetUsage {
+                used_bytes: 10,
+                max_bytes: 10,
+                rows_in_memory: 2,
+                truncated_cells: 1,
+            })
+        );
+        assert_eq!(buffer.total_rows_seen(), 4);
+    }
+
     #[test]
     fn index_metadata_tracks_stream_position() {
         let mut buffer = ResultsRingBuffer::new(2);
@@ -0,0 +1,391 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::results_buffer::{BudgetUsage, ResultsRingBuffer, RowFootprint};
+
+pub trait ResultsStore<T>: fmt::Debug {
+    fn capacity(&self) -> usize;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn total_rows_seen(&self) -> u64;
+
+    fn push(&mut self, row: T);
+
+    fn row(&self, index: usize) -> Option<T>;
+
+    fn rows(&self, start: usize, limit: usize) -> Vec<T>;
+
+    fn full_rows(&self, start: usize, limit: usize) -> Vec<T> {
+        self.rows(start, limit)
+    }
+
+    fn budget_usage(&self) -> Option<BudgetUsage> {
+        None
+    }
+
+    fn spill_error(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<T: Clone + fmt::Debug> ResultsStore<T> for ResultsRingBuffer<T> {
+    fn capacity(&self) -> usize {
+        ResultsRingBuffer::capacity(self)
+    }
+
+    fn len(&self) -> usize {
+        ResultsRingBuffer::len(self)
+    }
+
+    fn total_rows_seen(&self) -> u64 {
+        ResultsRingBuffer::total_rows_seen(self)
+    }
+
+    fn push(&mut self, row: T) {
+        ResultsRingBuffer::push(self, row);
+    }
+
+    fn row(&self, index: usize) -> Option<T> {
+        self.get(index).cloned()
+    }
+
+    fn rows(&self, start: usize, limit: usize) -> Vec<T> {
+        self.visible_rows(start, limit)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    fn budget_usage(&self) -> Option<BudgetUsage> {
+        ResultsRingBuffer::budget_usage(self)
+    }
+}
+
+// Keeps the most recent rows in memory like `ResultsRingBuffer`, but once the window
+// overflows every row is also appended to an anonymous temp file so older rows stay
+// reachable through a row-offset index instead of being evicted. With a byte budget,
+// the spill also starts before the first oversized cell is truncated in memory, so the
+// file always holds full values. A failed write drops the spill, and a failed read is
+// recorded as well, so callers can report why rows went missing instead of showing blanks.
+#[derive(Debug)]
+pub struct SpillingResultsStore<T> {
+    window: ResultsRingBuffer<T>,
+    max_cell_bytes: Option<usize>,
+    spill: RefCell<Option<SpillFile>>,
+    spill_error: RefCell<Option<String>>,
+}
+
+#[derive(Debug)]
+struct SpillFile {
+    writer: BufWriter<File>,
+    offsets: Vec<u64>,
+    written: u64,
+}
+
+impl<T> SpillingResultsStore<T>
+where
+    T: Clone + fmt::Debug + Serialize + DeserializeOwned + RowFootprint,
+{
+    #[must_use]
+    pub fn new(window_capacity: usize) -> Self {
+        Self {
+            window: ResultsRingBuffer::new(window_capacity),
+            max_cell_bytes: None,
+            spill: RefCell::new(None),
+            spill_error: RefCell::new(None),
+        }
+    }
+
+    #[must_use]
+    pub fn with_byte_budget(mut self, max_bytes: usize, max_cell_bytes: usize) -> Self {
+        self.window = ResultsRingBuffer::new(self.window.capacity())
+            .with_byte_budget(max_bytes, max_cell_bytes);
+        self.max_cell_bytes = Some(max_cell_bytes);
+        self
+    }
+
+    #[must_use]
+    pub fn is_spilled(&self) -> bool {
+        self.spill.borrow().is_some()
+    }
+
+    fn start_spill(&mut self) {
+        let spill = tempfile::tempfile().and_then(|file| {
+            let mut spill = SpillFile {
+                writer: BufWriter::new(file),
+                offsets: Vec::new(),
+                written: 0,
+            };
+            for row in self.window.visible_rows(0, self.window.len()) {
+                spill.append(row)?;
+            }
+            Ok(spill)
+        });
+        match spill {
+            Ok(spill) => *self.spill.get_mut() = Some(spill),
+            Err(error) => self.spill_failed(&error),
+        }
+    }
+
+    fn spill_failed(&mut self, error: &io::Error) {
+        *self.spill.get_mut() = None;
+        *self.spill_error.get_mut() = Some(format!(
+            "could not spill results to disk ({error}); only the last {} rows are kept",
+            self.window.capacity()
+        ));
+    }
+
+    fn window_start(&self) -> usize {
+        self.len() - self.window.len()
+    }
+
+    fn read_spilled(&self, start: usize, limit: usize) -> Vec<T> {
+        let read = match self.spill.borrow_mut().as_mut() {
+            Some(spill) => spill.read_rows(start, limit),
+            None => return Vec::new(),
+        };
+        read.unwrap_or_else(|error| {
+            *self.spill_error.borrow_mut() = Some(format!(
+                "could not read spilled rows from row {} ({error})",
+                start + 1
+            ));
+            Vec::new()
+        })
+    }
+}
+
+impl<T> ResultsStore<T> for SpillingResultsStore<T>
+where
+    T: Clone + fmt::Debug + Serialize + DeserializeOwned + RowFootprint,
+{
+    fn capacity(&self) -> usize {
+        self.window.capacity()
+    }
+
+    fn len(&self) -> usize {
+        self.spill
+            .borrow()
+            .as_ref()
+            .map_or(self.window.len(), |spill| spill.offsets.len())
+    }
+
+    fn total_rows_seen(&self) -> u64 {
+        self.window.total_rows_seen()
+    }
+
+    fn push(&mut self, row: T) {
+        let loses_data = self.window.would_evict(&row)
+            || self
+                .max_cell_bytes
+                .is_some_and(|max_cell_bytes| row.largest_cell_bytes() > max_cell_bytes);
+        if loses_data && self.spill.get_mut().is_none() && self.spill_error.get_mut().is_none() {
+            self.start_spill();
+        }
+        if let Some(spill) = self.spill.get_mut() {
+            if let Err(error) = spill.append(&row) {
+                self.spill_failed(&error);
+            }
+        }
+        self.window.push(row);
+    }
+
+    fn row(&self, index: usize) -> Option<T> {
+        self.rows(index, 1).pop()
+    }
+
+    fn rows(&self, start: usize, limit: usize) -> Vec<T> {
+        let end = start.saturating_add(limit).min(self.len());
+        if start >= end {
+            return Vec::new();
+        }
+
+        let window_start = self.window_start();
+        let mut rows = if start < window_start {
+            self.read_spilled(start, end.min(window_start) - start)
+        } else {
+            Vec::new()
+        };
+        if end > window_start {
+            let from = start.max(window_start);
+            rows.extend(
+                self.window
+                    .visible_rows(from - window_start, end - from)
+                    .into_iter()
+                    .cloned(),
+            );
+        }
+        rows
+    }
+
+    fn full_rows(&self, start: usize, limit: usize) -> Vec<T> {
+        if self.is_spilled() {
+            self.read_spilled(start, limit)
+        } else {
+            self.rows(start, limit)
+        }
+    }
+
+    fn budget_usage(&self) -> Option<BudgetUsage> {
+        self.window.budget_usage()
+    }
+
+    fn spill_error(&self) -> Option<String> {
+        self.spill_error.borrow().clone()
+    }
+}
+
+impl SpillFile {
+    fn append(&mut self, row: &impl Serialize) -> io::Result<()> {
+        let mut line = serde_json::to_vec(row)?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+        self.offsets.push(self.written);
+        self.written += line.len() as u64;
+        Ok(())
+    }
+
+    fn read_rows<T: DeserializeOwned>(&mut self, start: usize, limit: usize) -> io::Result<Vec<T>> {
+        let Some(&offset) = self.offsets.get(start) else {
+            return Ok(Vec::new());
+        };
+        self.writer.flush()?;
+        let file = self.writer.get_mut();
+        file.seek(SeekFrom::Start(offset))?;
+
+        let count = limit.min(self.offsets.len() - start);
+        let mut reader = BufReader::new(&*file);
+        let mut rows = Vec::with_capacity(count);
+        let mut line = String::new();
+        for _ in 0..count {
+            line.clear();
+            reader.read_line(&mut line)?;
+            rows.push(serde_json::from_str(&line)?);
+        }
+        drop(reader);
+
+        file.seek(SeekFrom::End(0))?;
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResultsStore, SpillingResultsStore};
+    use crate::query_runner::{QueryRow, QueryValue};
+    use crate::results_buffer::ResultsRingBuffer;
+
+    fn int_row(value: i64) -> QueryRow {
+        QueryRow::new(vec![QueryValue::Int(value), QueryValue::Null])
+    }
+
+    #[test]
+    fn ring_buffer_store_only_reaches_the_window() {
+        let mut store = ResultsRingBuffer::new(2);
+        for value in 1..=3 {
+            ResultsStore::push(&mut store, int_row(value));
+        }
+
+        assert_eq!(ResultsStore::len(&store), 2);
+        assert_eq!(store.row(0), Some(int_row(2)));
+        assert_eq!(store.rows(1, 5), vec![int_row(3)]);
+    }
+
+    #[test]
+    fn spilling_store_stays_in_memory_until_the_window_overflows() {
+        let mut store = SpillingResultsStore::new(3);
+        for value in 1..=3 {
+            store.push(int_row(value));
+        }
+
+        assert!(!store.is_spilled());
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.row(0), Some(int_row(1)));
+    }
+
+    #[test]
+    fn spilling_store_reaches_every_row_after_overflow() {
+        let mut store = SpillingResultsStore::new(4);
+        for value in 1..=10 {
+            store.push(int_row(value));
+        }
+
+        assert!(store.is_spilled());
+        assert!(store.spill_error().is_none());
+        assert_eq!(store.len(), 10);
+        assert_eq!(store.total_rows_seen(), 10);
+        assert_eq!(store.capacity(), 4);
+        assert_eq!(store.row(0), Some(int_row(1)));
+        assert_eq!(store.row(9), Some(int_row(10)));
+        assert_eq!(store.row(10), None);
+        assert_eq!(store.rows(4, 4), (5..=8).map(int_row).collect::<Vec<_>>());
+
+        store.push(int_row(11));
+        assert_eq!(store.rows(9, 5), vec![int_row(10), int_row(11)]);
+        assert_eq!(store.row(2), Some(int_row(3)));
+    }
+
+    #[test]
+    fn spilling_store_records_unreadable_rows() {
+        let mut store = SpillingResultsStore::new(4);
+        for value in 1..=10 {
+            store.push(int_row(value));
+        }
+        // Point the first row into the middle of its line so it no longer deserializes.
+        store
+            .spill
+            .get_mut()
+            .as_mut()
+            .expect("store should spill")
+            .offsets[0] = 1;
+
+        assert_eq!(store.row(0), None);
+        let error = store
+            .spill_error()
+            .expect("the failed read should be recorded");
+        assert!(
+            error.starts_with("could not read spilled rows from row 1"),
+            "{error}"
+        );
+        assert_eq!(store.row(1), Some(int_row(2)));
+    }
+
+    #[test]
+    fn byte_budgeted_store_keeps_full_values_on_disk() {
+        let blob = "x".repeat(256);
+        let mut store = SpillingResultsStore::new(100).with_byte_budget(4_096, 16);
+        store.push(int_row(1));
+        assert!(!store.is_spilled());
+
+        store.push(QueryRow::new(vec![
+            QueryValue::Int(2),
+            QueryValue::from(blob.clone()),
+        ]));
+
+        assert!(store.is_spilled());
+        assert_eq!(store.len(), 2);
+        let shown = store.row(1).expect("row should be buffered");
+        assert_eq!(
+            shown.values[1],
+            QueryValue::Truncated {
+                preview: "x".repeat(16),
+                full_bytes: 256,
+            }
+        );
+        let full = store.full_rows(1, 1);
+        assert_eq!(full[0].values[1], QueryValue::from(blob));
+        let usage = store.budget_usage().expect("budget should be tracked");
+        assert_eq!(usage.truncated_cells, 1);
+        assert_eq!(usage.rows_in_memory, 2);
+        assert!(usage.used_bytes <= usage.max_bytes);
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::safe_mode::assess_sql_safety;
+
+pub const DEFAULT_QUERY_RETRIES: u32 = 1;
+pub const DEFAULT_RECONNECT_ATTEMPTS: u32 = 2;
+pub const DEFAULT_RETRY_BACKOFF_MS: u64 = 200;
+pub const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(10);
+
+// Client-side failures carry no server error code, so adapters report them with the
+// libmysqlclient `CR_*` code a C client would raise for the same condition.
+pub const CR_CONNECTION_ERROR: u16 = 2002;
+pub const CR_CONN_HOST_ERROR: u16 = 2003;
+pub const CR_SERVER_GONE_ERROR: u16 = 2006;
+pub const CR_SERVER_LOST: u16 = 2013;
+pub const CR_SERVER_LOST_EXTENDED: u16 = 2055;
+
+const ER_SERVER_SHUTDOWN: u16 = 1053;
+const ER_CONNECTION_KILLED: u16 = 1927;
+const ER_CLIENT_INTERACTION_TIMEOUT: u16 = 4031;
+const ER_CON_COUNT_ERROR: u16 = 1040;
+const ER_TOO_MANY_USER_CONNECTIONS: u16 = 1203;
+const ER_LOCK_WAIT_TIMEOUT: u16 = 1205;
+const ER_LOCK_DEADLOCK: u16 = 1213;
+const ER_XA_RBDEADLOCK: u16 = 1614;
+const ER_TOO_MANY_CONCURRENT_TRXS: u16 = 1637;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    // The session is gone; the statement can only run again on a fresh connection.
+    ConnectionLost,
+    // The server turned the statement away for now; the same session can try again.
+    Transient,
+    Permanent,
+}
+
+impl ErrorClass {
+    #[must_use]
+    pub fn from_code(code: Option<u16>) -> Self {
+        match code {
+            Some(
+                CR_CONNECTION_ERROR
+                | CR_CONN_HOST_ERROR
+                | CR_SERVER_GONE_ERROR
+                | CR_SERVER_LOST
+                | CR_SERVER_LOST_EXTENDED
+                | ER_SERVER_SHUTDOWN
+                | ER_CONNECTION_KILLED
+                | ER_CLIENT_INTERACTION_TIMEOUT,
+            ) => Self::ConnectionLost,
+            Some(
+                ER_CON_COUNT_ERROR
+                | ER_TOO_MANY_USER_CONNECTIONS
+                | ER_LOCK_WAIT_TIMEOUT
+                | ER_LOCK_DEADLOCK
+                | ER_XA_RBDEADLOCK
+                | ER_TOO_MANY_CONCURRENT_TRXS,
+            ) => Self::Transient,
+            _ => Self::Permanent,
+        }
+    }
+
+    // A lost connection can drop the reply to a statement the server already committed,
+    // so only reads run again after one. Deadlocks and lock waits roll the statement back,
+    // so any statement may run again after those.
+    #[must_use]
+    pub fn allows_rerun(self, sql: &str) -> bool {
+        match self {
+            Self::ConnectionLost => assess_sql_safety(sql).is_safe_read_only(),
+            Self::Transient => true,
+            Self::Permanent => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_query_retries: u32,
+    pub max_reconnect_attempts: u32,
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_query_retries: DEFAULT_QUERY_RETRIES,
+            max_reconnect_attempts: DEFAULT_RECONNECT_ATTEMPTS,
+            base_backoff: Duration::from_millis(DEFAULT_RETRY_BACKOFF_MS),
+            max_backoff: MAX_RETRY_BACKOFF,
+        }
+    }
+}
+
+impl RetryPolicy {
+    #[must_use]
+    pub fn max_attempts(&self, class: ErrorClass) -> u32 {
+        match class {
+            ErrorClass::ConnectionLost => self.max_reconnect_attempts,
+            ErrorClass::Transient => self.max_query_retries,
+            ErrorClass::Permanent => 0,
+        }
+    }
+
+    // `attempt` counts from 1; `None` means the budget for this class is spent.
+    #[must_use]
+    pub fn retry_delay(&self, class: ErrorClass, attempt: u32) -> Option<Duration> {
+        (attempt >= 1 && attempt <= self.max_attempts(class)).then(|| self.backoff(attempt))
+    }
+
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.backoff_with_jitter(attempt, jitter_sample())
+    }
+
+    // Equal jitter: half of the exponential delay is kept so retries never fire back to
+    // back, and the other half is spread so clients that failed together drift apart.
+    #[must_use]
+    pub fn backoff_with_jitter(&self, attempt: u32, sample: f64) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let ceiling = self
+            .base_backoff
+            .saturating_mul(1_u32 << exponent)
+            .min(self.max_backoff);
+        let half = ceiling / 2;
+        half + half.mul_f64(sample.clamp(0.0, 1.0))
+    }
+}
+
+// `RandomState` is seeded per process, which is all the spread retries need.
+fn jitter_sample() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.subsec_nanos());
+    hasher.write_u32(nanos);
+    (hasher.finish() >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{ErrorClass, RetryPolicy, CR_SERVER_GONE_ERROR, CR_SERVER_LOST};
+
+    #[test]
+    fn error_codes_are_classified_by_recoverability() {
+        assert_eq!(
+            ErrorClass::from_code(Some(CR_SERVER_GONE_ERROR)),
+            ErrorClass::ConnectionLost
+        );
+        assert_eq!(
+            ErrorClass::from_code(Some(CR_SERVER_LOST)),
+            ErrorClass::ConnectionLost
+        );
+        assert_eq!(ErrorClass::from_code(Some(1213)), ErrorClass::Transient);
+        assert_eq!(ErrorClass::from_code(Some(1205)), ErrorClass::Transient);
+        assert_eq!(ErrorClass::from_code(Some(1064)), ErrorClass::Permanent);
+        assert_eq!(ErrorClass::from_code(Some(1045)), ErrorClass::Permanent);
+        assert_eq!(ErrorClass::from_code(None), ErrorClass::Permanent);
+    }
+
+    #[test]
+    fn only_reads_rerun_after_a_lost_connection() {
+        assert!(ErrorClass::ConnectionLost.allows_rerun("SELECT * FROM orders"));
+        assert!(!ErrorClass::ConnectionLost.allows_rerun("INSERT INTO orders (id) VALUES (1)"));
+        assert!(!ErrorClass::ConnectionLost.allows_rerun("SELECT * FROM orders FOR UPDATE"));
+        assert!(ErrorClass::Transient.allows_rerun("UPDATE orders SET state = 'paid'"));
+        assert!(!ErrorClass::Permanent.allows_rerun("SELECT 1"));
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_within_the_jitter_band() {
+        let policy = RetryPolicy {
+            max_query_retries: 3,
+            max_reconnect_attempts: 5,
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+
+        assert_eq!(
+            policy.backoff_with_jitter(1, 0.0),
+            Duration::from_millis(50)
+        );
+        assert_eq!(
+            policy.backoff_with_jitter(1, 1.0),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            policy.backoff_with_jitter(3, 1.0),
+            Duration::from_millis(400)
+        );
+        assert_eq!(
+            policy.backoff_with_jitter(5, 1.0),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            policy.backoff_with_jitter(5, 0.0),
+            Duration::from_millis(250)
+        );
+
+        let delay = policy.backoff(2);
+        assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn retry_budget_depends_on_the_error_class() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.retry_delay(ErrorClass::Transient, 1).is_some());
+        assert!(policy.retry_delay(ErrorClass::Transient, 2).is_none());
+        assert!(policy.retry_delay(ErrorClass::ConnectionLost, 2).is_some());
+        assert!(policy.retry_delay(ErrorClass::ConnectionLost, 3).is_none());
+        assert!(policy.retry_delay(ErrorClass::Permanent, 1).is_none());
+        assert!(policy.retry_delay(ErrorClass::Transient, 0).is_none());
+    }
+}
@@ -1,8 +1,15 @@
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
+use serde::{Deserialize, Serialize};
+use sqlparser::dialect::MySqlDialect;
+use sqlparser::tokenizer::{Token, Tokenizer};
 use thiserror::Error;
 
+mod parsed;
+
+use parsed::parsed_statement_risk;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SqlRiskReason {
     MultiStatement,
@@ -28,6 +35,25 @@ impl SqlSafetyAssessment {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionBoundary {
+    Begin,
+    Commit,
+    Rollback,
+}
+
+impl TransactionBoundary {
+    #[must_use]
+    pub fn sql(self) -> &'static str {
+        match self {
+            Self::Begin => "START TRANSACTION",
+            Self::Commit => "COMMIT",
+            Self::Rollback => "ROLLBACK",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ConfirmationToken(String);
 
@@ -141,33 +167,9 @@ pub fn assess_sql_safety(sql: &str) -> SqlSafetyAssessment {
         .first()
         .and_then(|statement| first_keyword(statement));
     for statement in &statements {
-        if let Some(keyword) = first_keyword(statement) {
-            if is_safe_read_keyword(&keyword) {
-                continue;
-            }
-
-            if is_write_keyword(&keyword) {
-                reasons.push(SqlRiskReason::WriteOperation(keyword));
-                continue;
-            }
-
-            if is_ddl_keyword(&keyword) {
-                reasons.push(SqlRiskReason::DdlOperation(keyword));
-                continue;
-            }
-
-            if is_transaction_keyword(&keyword) {
-                reasons.push(SqlRiskReason::TransactionControl(keyword));
-                continue;
-            }
-
-            if is_session_mutation_keyword(&keyword) {
-                reasons.push(SqlRiskReason::SessionMutation(keyword));
-                continue;
-            }
-
-            reasons.push(SqlRiskReason::UnknownStatement(keyword));
-        }
+        let risk = parsed_statement_risk(statement)
+            .unwrap_or_else(|_| heuristic_statement_risk(statement));
+        reasons.extend(risk);
     }
 
     let normalized_sql = statements.join("; ");
@@ -180,84 +182,144 @@ pub fn assess_sql_safety(sql: &str) -> SqlSafetyAssessment {
     }
 }
 
+// Returns the last statement in the batch that opens or closes a transaction, which
+// is what decides whether a transaction is still open once the batch has run.
+#[must_use]
+pub fn transaction_boundary(sql: &str) -> Option<TransactionBoundary> {
+    split_statements(sql).iter().rev().find_map(|statement| {
+        let words = statement
+            .split_whitespace()
+            .map(str::to_ascii_uppercase)
+            .collect::<Vec<_>>();
+        let second = words.get(1).map(String::as_str);
+        // `AND CHAIN` ends the transaction and opens the next one straight away, so the
+        // session is left inside a transaction, just as after a BEGIN.
+        let chained = words
+            .windows(2)
+            .any(|pair| pair[0] == "AND" && pair[1] == "CHAIN");
+        match words.first()?.as_str() {
+            "BEGIN" if matches!(second, None | Some("WORK")) => Some(TransactionBoundary::Begin),
+            "START" if second == Some("TRANSACTION") => Some(TransactionBoundary::Begin),
+            "COMMIT" | "ROLLBACK" if chained => Some(TransactionBoundary::Begin),
+            "COMMIT" => Some(TransactionBoundary::Commit),
+            // ROLLBACK TO SAVEPOINT keeps the surrounding transaction open.
+            "ROLLBACK" if !words.iter().any(|word| word == "TO") => {
+                Some(TransactionBoundary::Rollback)
+            }
+            _ => None,
+        }
+    })
+}
+
+// Splits a batch the way the mysql client does: on the current delimiter outside quotes
+// and comments, following `DELIMITER` lines. Comments are dropped except versioned ones
+// (`/*!50700 ... */`, MariaDB's `/*M!100100 ... */`), whose body the server runs.
 fn split_statements(sql: &str) -> Vec<String> {
     let mut statements = Vec::new();
     let mut current = String::new();
-    let mut chars = sql.chars().peekable();
-
-    let mut in_single_quote = false;
-    let mut in_double_quote = false;
-    let mut in_backtick = false;
-    let mut in_line_comment = false;
-    let mut in_block_comment = false;
-
-    while let Some(ch) = chars.next() {
-        if in_line_comment {
-            if ch == '\n' {
-                in_line_comment = false;
+    let mut delimiter = ";".to_string();
+    let mut quote = None;
+    let mut in_versioned_comment = false;
+    let mut rest = sql;
+
+    while let Some(ch) = rest.chars().next() {
+        let after = &rest[ch.len_utf8()..];
+
+        if let Some(open) = quote {
+            current.push(ch);
+            rest = after;
+            if ch == '\\' && open != '`' {
+                if let Some(escaped) = rest.chars().next() {
+                    current.push(escaped);
+                    rest = &rest[escaped.len_utf8()..];
+                }
+            } else if ch == open {
+                quote = None;
             }
             continue;
         }
 
-        if in_block_comment {
-            if ch == '*' && chars.peek() == Some(&'/') {
-                chars.next();
-                in_block_comment = false;
+        if current.trim().is_empty() {
+            if let Some(line) = delimiter_line(rest) {
+                if let Some(new_delimiter) =
+                    line[DELIMITER_KEYWORD.len()..].split_whitespace().next()
+                {
+                    delimiter = new_delimiter.to_string();
+                }
+                rest = &rest[line.len()..];
+                current.clear();
+                continue;
             }
+        }
+
+        if rest.starts_with(delimiter.as_str()) {
+            push_statement(&mut statements, &current);
+            current.clear();
+            rest = &rest[delimiter.len()..];
             continue;
         }
 
-        if !in_single_quote && !in_double_quote && !in_backtick {
-            if ch == '-' && chars.peek() == Some(&'-') {
-                chars.next();
-                in_line_comment = true;
-                continue;
-            }
+        if rest.starts_with("--") || ch == '#' {
+            rest = rest.find('\n').map_or("", |end| &rest[end..]);
+            continue;
+        }
 
-            if ch == '#' {
-                in_line_comment = true;
-                continue;
-            }
+        if let Some(body) = rest
+            .strip_prefix("/*!")
+            .or_else(|| rest.strip_prefix("/*M!"))
+        {
+            rest = body.trim_start_matches(|digit: char| digit.is_ascii_digit());
+            in_versioned_comment = true;
+            current.push(' ');
+            continue;
+        }
 
-            if ch == '/' && chars.peek() == Some(&'*') {
-                chars.next();
-                in_block_comment = true;
-                continue;
-            }
+        if in_versioned_comment && rest.starts_with("*/") {
+            rest = &rest[2..];
+            in_versioned_comment = false;
+            current.push(' ');
+            continue;
         }
 
-        match ch {
-            '\'' if !in_double_quote && !in_backtick => {
-                in_single_quote = !in_single_quote;
-                current.push(ch);
-            }
-            '"' if !in_single_quote && !in_backtick => {
-                in_double_quote = !in_double_quote;
-                current.push(ch);
-            }
-            '`' if !in_single_quote && !in_double_quote => {
-                in_backtick = !in_backtick;
-                current.push(ch);
-            }
-            ';' if !in_single_quote && !in_double_quote && !in_backtick => {
-                let statement = current.trim();
-                if !statement.is_empty() {
-                    statements.push(statement.to_string());
-                }
-                current.clear();
-            }
-            _ => current.push(ch),
+        if let Some(body) = rest.strip_prefix("/*") {
+            rest = body.find("*/").map_or("", |end| &body[end + 2..]);
+            current.push(' ');
+            continue;
         }
-    }
 
-    let trailing = current.trim();
-    if !trailing.is_empty() {
-        statements.push(trailing.to_string());
+        if matches!(ch, '\'' | '"' | '`') {
+            quote = Some(ch);
+        }
+        current.push(ch);
+        rest = after;
     }
 
+    push_statement(&mut statements, &current);
     statements
 }
 
+const DELIMITER_KEYWORD: &str = "DELIMITER";
+
+// `DELIMITER //` is a client command, so it is only recognised at the start of a statement.
+fn delimiter_line(rest: &str) -> Option<&str> {
+    let line = rest.trim_start();
+    let keyword = line.get(..DELIMITER_KEYWORD.len())?;
+    let after = line[DELIMITER_KEYWORD.len()..].chars().next();
+    if !keyword.eq_ignore_ascii_case(DELIMITER_KEYWORD) || !after.is_some_and(char::is_whitespace) {
+        return None;
+    }
+    let leading = rest.len() - line.len();
+    let end = line.find('\n').map_or(line.len(), |end| end + 1);
+    Some(&rest[..leading + end])
+}
+
+fn push_statement(statements: &mut Vec<String>, current: &str) {
+    let statement = current.trim();
+    if !statement.is_empty() {
+        statements.push(statement.to_string());
+    }
+}
+
 fn first_keyword(statement: &str) -> Option<String> {
     statement
         .split_whitespace()
@@ -265,6 +327,90 @@ fn first_keyword(statement: &str) -> Option<String> {
         .map(|keyword| keyword.to_ascii_uppercase())
 }
 
+// Keyword classification for statements the parser cannot read, such as `HANDLER`,
+// `SELECT ... INTO OUTFILE` or stored program bodies.
+fn heuristic_statement_risk(statement: &str) -> Option<SqlRiskReason> {
+    let words = statement_words(statement);
+    let keyword = words.first()?.0.clone();
+    // A CTE list does nothing by itself; the statement after it decides.
+    let effective = if keyword == "WITH" {
+        words
+            .iter()
+            .find(|(word, depth)| {
+                *depth == 0 && matches!(word.as_str(), "SELECT" | "INSERT" | "UPDATE" | "DELETE")
+            })
+            .map_or_else(|| keyword.clone(), |(word, _)| word.clone())
+    } else {
+        keyword.clone()
+    };
+
+    if effective == "SELECT" {
+        return select_clause_risk(&words);
+    }
+    if is_safe_read_keyword(&effective) || is_analyze_select(statement) {
+        return None;
+    }
+    if is_write_keyword(&effective) {
+        return Some(SqlRiskReason::WriteOperation(effective));
+    }
+    if is_ddl_keyword(&effective) {
+        return Some(SqlRiskReason::DdlOperation(effective));
+    }
+    if is_transaction_keyword(&effective) {
+        return Some(SqlRiskReason::TransactionControl(effective));
+    }
+    if is_session_mutation_keyword(&effective) {
+        return Some(SqlRiskReason::SessionMutation(effective));
+    }
+    Some(SqlRiskReason::UnknownStatement(keyword))
+}
+
+// The clauses that turn a SELECT into something other than a read.
+fn select_clause_risk(words: &[(String, usize)]) -> Option<SqlRiskReason> {
+    let words = words
+        .iter()
+        .map(|(word, _)| word.as_str())
+        .collect::<Vec<_>>();
+    words.windows(2).find_map(|pair| match pair {
+        ["INTO", target @ ("OUTFILE" | "DUMPFILE")] => {
+            Some(SqlRiskReason::WriteOperation(format!("INTO {target}")))
+        }
+        ["INTO", _] => Some(SqlRiskReason::SessionMutation("INTO".to_string())),
+        ["FOR", lock @ ("UPDATE" | "SHARE")] => {
+            Some(SqlRiskReason::TransactionControl(format!("FOR {lock}")))
+        }
+        ["LOCK", "IN"] => Some(SqlRiskReason::TransactionControl(
+            "LOCK IN SHARE MODE".to_string(),
+        )),
+        _ => None,
+    })
+}
+
+// Unquoted words in upper case with their parenthesis depth. Quoted strings and
+// identifiers are skipped, so a column named `update` is never mistaken for a clause.
+fn statement_words(statement: &str) -> Vec<(String, usize)> {
+    let Ok(tokens) = Tokenizer::new(&MySqlDialect {}, statement).tokenize() else {
+        return statement
+            .split_whitespace()
+            .map(|word| (word.to_ascii_uppercase(), 0))
+            .collect();
+    };
+
+    let mut depth = 0_usize;
+    let mut words = Vec::new();
+    for token in tokens {
+        match token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth = depth.saturating_sub(1),
+            Token::Word(word) if word.quote_style.is_none() => {
+                words.push((word.value.to_ascii_uppercase(), depth));
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
 fn is_safe_read_keyword(keyword: &str) -> bool {
     matches!(
         keyword,
@@ -272,6 +418,17 @@ fn is_safe_read_keyword(keyword: &str) -> bool {
     )
 }
 
+// MariaDB's `ANALYZE [FORMAT=JSON] SELECT` profiles a query the way MySQL's
+// `EXPLAIN ANALYZE` does; `ANALYZE TABLE` maintenance is still DDL.
+fn is_analyze_select(statement: &str) -> bool {
+    let mut words = statement.split_whitespace().map(str::to_ascii_uppercase);
+    words.next().as_deref() == Some("ANALYZE")
+        && words
+            .find(|word| !(word.starts_with("FORMAT") || word.starts_with('=') || word == "JSON"))
+            .as_deref()
+            == Some("SELECT")
+}
+
 fn is_write_keyword(keyword: &str) -> bool {
     matches!(
         keyword,
@@ -293,8 +450,9 @@ fn is_transaction_keyword(keyword: &str) -> bool {
     )
 }
 
+// `HANDLER ... OPEN` keeps a cursor on the connection until it is closed.
 fn is_session_mutation_keyword(keyword: &str) -> bool {
-    matches!(keyword, "SET" | "GRANT" | "REVOKE")
+    matches!(keyword, "SET" | "GRANT" | "REVOKE" | "HANDLER")
 }
 
 fn fingerprint_sql(normalized_sql: &str) -> u64 {
@@ -305,7 +463,62 @@ fn fingerprint_sql(normalized_sql: &str) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use super::{assess_sql_safety, GuardDecision, SafeModeError, SafeModeGuard, SqlRiskReason};
+    use super::{
+        assess_sql_safety, transaction_boundary, GuardDecision, SafeModeError, SafeModeGuard,
+        SqlRiskReason, TransactionBoundary,
+    };
+
+    const CORPUS: &str = include_str!("safe_mode/corpus.sql");
+
+    fn describe_reasons(reasons: &[SqlRiskReason]) -> String {
+        if reasons.is_empty() {
+            return "read".to_string();
+        }
+        reasons
+            .iter()
+            .map(|reason| match reason {
+                SqlRiskReason::MultiStatement => "multi".to_string(),
+                SqlRiskReason::WriteOperation(keyword) => format!("write {keyword}"),
+                SqlRiskReason::DdlOperation(keyword) => format!("ddl {keyword}"),
+                SqlRiskReason::TransactionControl(keyword) => format!("tx {keyword}"),
+                SqlRiskReason::SessionMutation(keyword) => format!("session {keyword}"),
+                SqlRiskReason::UnknownStatement(keyword) => format!("unknown {keyword}"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    #[test]
+    fn corpus_statements_are_classified_as_expected() {
+        let cases = CORPUS.split("-- expect:").skip(1).collect::<Vec<_>>();
+        assert!(cases.len() >= 40, "corpus lost cases: {}", cases.len());
+        let misclassified = cases
+            .into_iter()
+            .filter_map(|case| {
+                let (expected, sql) = case.split_once('\n').expect("case has SQL");
+                let expected = expected.trim();
+                let actual = describe_reasons(&assess_sql_safety(sql).reasons);
+                (actual != expected)
+                    .then(|| format!("{}\n  expected {expected}, got {actual}", sql.trim()))
+            })
+            .collect::<Vec<_>>();
+        assert!(misclassified.is_empty(), "{}", misclassified.join("\n"));
+    }
+
+    #[test]
+    fn versioned_comments_and_delimiter_lines_shape_the_statements() {
+        let assessment = assess_sql_safety("/*!50000 DROP TABLE users */");
+        assert_eq!(assessment.normalized_sql, "DROP TABLE users");
+        assert_eq!(assessment.primary_keyword.as_deref(), Some("DROP"));
+
+        let assessment =
+            assess_sql_safety("DELIMITER //\nCREATE PROCEDURE p() BEGIN SELECT 1; END//\n");
+        assert_eq!(assessment.statement_count, 1);
+        assert_eq!(
+            assessment.normalized_sql,
+            "CREATE PROCEDURE p() BEGIN SELECT 1; END"
+        );
+    }
 
     #[test]
     fn select_without_side_effects_is_safe() {
@@ -315,6 +528,14 @@ mod tests {
         assert_eq!(assessment.primary_keyword.as_deref(), Some("SELECT"));
     }
 
+    #[test]
+    fn analyze_select_is_a_read_but_analyze_table_is_not() {
+        assert!(assess_sql_safety("ANALYZE SELECT * FROM users").is_safe_read_only());
+        assert!(assess_sql_safety("analyze format=json select 1").is_safe_read_only());
+        assert!(assess_sql_safety("EXPLAIN ANALYZE SELECT 1").is_safe_read_only());
+        assert!(!assess_sql_safety("ANALYZE TABLE users").is_safe_read_only());
+    }
+
     #[test]
     fn destructive_statement_requires_confirmation_when_safe_mode_enabled() {
         let mut guard = SafeModeGuard::new(true);
@@ -410,4 +631,42 @@ mod tests {
             .expect_err("different statement should fail");
         assert_eq!(err, SafeModeError::SqlMismatch);
     }
+
+    #[test]
+    fn transaction_boundaries_are_detected_from_the_last_boundary_statement() {
+        assert_eq!(
+            transaction_boundary("start transaction read only"),
+            Some(TransactionBoundary::Begin)
+        );
+        assert_eq!(
+            transaction_boundary("BEGIN WORK"),
+            Some(TransactionBoundary::Begin)
+        );
+        assert_eq!(
+            transaction_boundary("BEGIN; UPDATE users SET admin = 0; COMMIT"),
+            Some(TransactionBoundary::Commit)
+        );
+        assert_eq!(
+            transaction_boundary("rollback work"),
+            Some(TransactionBoundary::Rollback)
+        );
+        assert_eq!(
+            transaction_boundary("ROLLBACK TO SAVEPOINT before_update"),
+            None
+        );
+        assert_eq!(
+            transaction_boundary("BEGIN; UPDATE users SET admin = 0; COMMIT AND CHAIN"),
+            Some(TransactionBoundary::Begin)
+        );
+        assert_eq!(
+            transaction_boundary("rollback work and chain"),
+            Some(TransactionBoundary::Begin)
+        );
+        assert_eq!(
+            transaction_boundary("COMMIT AND NO CHAIN"),
+            Some(TransactionBoundary::Commit)
+        );
+        assert_eq!(transaction_boundary("SELECT 'COMMIT' FROM dual"), None);
+        assert_eq!(TransactionBoundary::Begin.sql(), "START TRANSACTION");
+    }
 }
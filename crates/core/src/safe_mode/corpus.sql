@@ -0,0 +1,167 @@
+-- Each case starts with an expectation line naming the assessment of the SQL below it:
+-- `read`, or the risk reasons in order (`multi`, `write KW`, `ddl KW`, `tx KW`,
+-- `session KW`, `unknown KW`).
+
+-- expect: read
+SELECT * FROM users WHERE id = 1
+
+-- expect: read
+WITH recent AS (SELECT id FROM orders WHERE created_at > NOW() - INTERVAL 1 DAY)
+SELECT * FROM recent
+
+-- expect: read
+(SELECT id FROM users) UNION (SELECT id FROM archived_users) ORDER BY id
+
+-- expect: read
+SELECT 'DELETE FROM users; DROP TABLE users' AS note
+
+-- expect: read
+SELECT `update`, `delete` FROM `for` WHERE `into` = 'outfile'
+
+-- expect: read
+SELECT 'it\'s; fine' AS quoted, "double ""quoted""" AS doubled
+
+-- expect: read
+SELECT /*+ MAX_EXECUTION_TIME(1000) */ id FROM users
+
+-- expect: read
+SHOW CREATE TABLE users
+
+-- expect: read
+EXPLAIN DELETE FROM users WHERE id = 1
+
+-- expect: read
+EXPLAIN ANALYZE SELECT * FROM users
+
+-- expect: read
+ANALYZE FORMAT=JSON SELECT * FROM users
+
+-- expect: read
+DESCRIBE users
+
+-- expect: read
+SELECT * FROM users WHERE id IN (SELECT user_id FROM accounts WHERE balance < 0)
+
+-- expect: write DELETE
+WITH doomed AS (SELECT id FROM users WHERE last_login < '2020-01-01')
+DELETE FROM users WHERE id IN (SELECT id FROM doomed)
+
+-- expect: write UPDATE
+WITH RECURSIVE chain (id) AS (SELECT 1 UNION ALL SELECT id + 1 FROM chain WHERE id < 5)
+UPDATE counters SET hits = 0 WHERE id IN (SELECT id FROM chain)
+
+-- expect: write DELETE
+EXPLAIN ANALYZE DELETE FROM users WHERE id = 1
+
+-- expect: write INTO OUTFILE
+SELECT * FROM users INTO OUTFILE '/tmp/users.csv'
+
+-- expect: write INTO DUMPFILE
+SELECT avatar FROM users WHERE id = 1 INTO DUMPFILE '/tmp/avatar.png'
+
+-- expect: session INTO
+SELECT COUNT(*) INTO @user_count FROM users
+
+-- expect: tx FOR UPDATE
+SELECT * FROM accounts WHERE id = 7 FOR UPDATE
+
+-- expect: tx FOR SHARE
+SELECT * FROM accounts WHERE id = 7 FOR SHARE
+
+-- expect: tx LOCK IN SHARE MODE
+SELECT * FROM accounts WHERE id = 7 LOCK IN SHARE MODE
+
+-- expect: tx FOR UPDATE
+WITH locked AS (SELECT id FROM accounts WHERE id = 7 FOR UPDATE)
+SELECT * FROM locked
+
+-- expect: tx FOR SHARE
+SELECT * FROM (SELECT id, balance FROM accounts FOR SHARE) AS shared
+
+-- expect: tx FOR UPDATE
+SELECT * FROM users JOIN (SELECT user_id FROM accounts FOR UPDATE) AS held ON held.user_id = users.id
+
+-- expect: tx FOR UPDATE
+SELECT * FROM users WHERE id IN (SELECT user_id FROM accounts WHERE balance < 0 FOR UPDATE)
+
+-- expect: tx FOR UPDATE
+SELECT EXISTS (SELECT 1 FROM accounts WHERE id = 7 FOR UPDATE) AS held
+
+-- expect: tx FOR UPDATE
+SELECT COALESCE((SELECT balance FROM accounts WHERE id = 7 FOR UPDATE), 0) AS balance
+
+-- expect: tx FOR SHARE
+(SELECT id FROM users) UNION (SELECT user_id FROM accounts FOR SHARE)
+
+-- expect: tx LOCK IN SHARE MODE
+SELECT (SELECT balance FROM accounts WHERE id = 7 LOCK IN SHARE MODE) AS balance
+
+-- expect: session INTO
+SELECT * FROM users WHERE id = (SELECT MAX(id) INTO @last_id FROM users)
+
+-- expect: write INTO OUTFILE
+WITH dumped AS (SELECT * FROM users INTO OUTFILE '/tmp/users.csv') SELECT 1
+
+-- expect: write CALL
+CALL purge_inactive_users(30)
+
+-- expect: write REPLACE
+REPLACE INTO settings (name, value) VALUES ('theme', 'dark')
+
+-- expect: write LOAD
+LOAD DATA INFILE '/tmp/users.csv' INTO TABLE users
+
+-- expect: ddl DROP
+/*!50000 DROP TABLE users */
+
+-- expect: ddl ALTER
+/*M!100100 ALTER TABLE users ADD COLUMN note TEXT */
+
+-- expect: multi, ddl DROP
+SELECT 1; /*!40101 DROP DATABASE app */;
+
+-- expect: ddl TRUNCATE
+TRUNCATE TABLE sessions
+
+-- expect: ddl RENAME
+RENAME TABLE users TO old_users
+
+-- expect: ddl ANALYZE
+ANALYZE TABLE users
+
+-- expect: ddl CREATE
+DELIMITER //
+CREATE PROCEDURE purge() BEGIN DELETE FROM sessions; DELETE FROM tokens; END//
+DELIMITER ;
+
+-- expect: multi, ddl CREATE, write CALL
+DELIMITER $$
+CREATE TRIGGER audit_users AFTER UPDATE ON users FOR EACH ROW BEGIN
+  INSERT INTO audit (user_id) VALUES (NEW.id);
+END$$
+DELIMITER ;
+CALL refresh_audit();
+
+-- expect: session HANDLER
+HANDLER users OPEN
+
+-- expect: session HANDLER
+HANDLER users READ `PRIMARY` FIRST
+
+-- expect: tx START
+START TRANSACTION
+
+-- expect: tx LOCK
+LOCK TABLES users WRITE
+
+-- expect: session SET
+SET SESSION sql_mode = 'STRICT_ALL_TABLES'
+
+-- expect: session GRANT
+GRANT SELECT ON app.* TO 'reader'@'%'
+
+-- expect: unknown KILL
+KILL QUERY 42
+
+-- expect: unknown EXECUTE
+EXECUTE purge_statement
@@ -0,0 +1,261 @@
+use sqlparser::ast::{
+    Expr, FunctionArg, FunctionArgExpr, FunctionArguments, JoinConstraint, JoinOperator, LockType,
+    Query, Select, SelectItem, SetExpr, Statement, TableFactor, TableWithJoins,
+};
+use sqlparser::dialect::MySqlDialect;
+use sqlparser::parser::{Parser, ParserError};
+
+use super::{first_keyword, SqlRiskReason};
+
+// Classifies one statement from its MySQL-dialect syntax tree. An error means the parser
+// could not read it, and the caller falls back to the keyword heuristic.
+pub(super) fn parsed_statement_risk(statement: &str) -> Result<Option<SqlRiskReason>, ParserError> {
+    let parsed = Parser::parse_sql(&MySqlDialect {}, statement)?;
+    let [parsed] = parsed.as_slice() else {
+        return Err(ParserError::ParserError(format!(
+            "expected one statement, found {}",
+            parsed.len()
+        )));
+    };
+    let keyword = first_keyword(statement).unwrap_or_default();
+    Ok(statement_risk(parsed, keyword))
+}
+
+fn statement_risk(statement: &Statement, keyword: String) -> Option<SqlRiskReason> {
+    match statement {
+        Statement::Query(query) => query_risk(query),
+        // Plain EXPLAIN only plans the statement; EXPLAIN ANALYZE runs it.
+        Statement::Explain {
+            analyze, statement, ..
+        } => analyze
+            .then(|| statement_risk(statement, rendered_keyword(statement)))
+            .flatten(),
+        Statement::ExplainTable { .. }
+        | Statement::ShowFunctions { .. }
+        | Statement::ShowVariable { .. }
+        | Statement::ShowStatus { .. }
+        | Statement::ShowVariables { .. }
+        | Statement::ShowCreate { .. }
+        | Statement::ShowColumns { .. }
+        | Statement::ShowDatabases { .. }
+        | Statement::ShowSchemas { .. }
+        | Statement::ShowTables { .. }
+        | Statement::ShowViews { .. }
+        | Statement::ShowCollation { .. }
+        | Statement::Use(_) => None,
+        Statement::Insert(_)
+        | Statement::Update { .. }
+        | Statement::Delete(_)
+        | Statement::Merge { .. }
+        | Statement::Call(_)
+        | Statement::Load { .. }
+        | Statement::LoadData { .. }
+        | Statement::Copy { .. } => Some(SqlRiskReason::WriteOperation(keyword)),
+        Statement::CreateView { .. }
+        | Statement::CreateTable(_)
+        | Statement::CreateIndex(_)
+        | Statement::CreateRole { .. }
+        | Statement::CreateSchema { .. }
+        | Statement::CreateDatabase { .. }
+        | Statement::CreateFunction(_)
+        | Statement::CreateTrigger { .. }
+        | Statement::CreateProcedure { .. }
+        | Statement::CreateSequence { .. }
+        | Statement::CreateType { .. }
+        | Statement::AlterTable { .. }
+        | Statement::AlterIndex { .. }
+        | Statement::AlterView { .. }
+        | Statement::AlterRole { .. }
+        | Statement::Drop { .. }
+        | Statement::DropFunction { .. }
+        | Statement::DropProcedure { .. }
+        | Statement::DropTrigger { .. }
+        | Statement::Truncate { .. }
+        | Statement::Analyze { .. }
+        | Statement::OptimizeTable { .. }
+        | Statement::Comment { .. } => Some(SqlRiskReason::DdlOperation(keyword)),
+        Statement::StartTransaction { .. }
+        | Statement::SetTransaction { .. }
+        | Statement::Commit { .. }
+        | Statement::Rollback { .. }
+        | Statement::Savepoint { .. }
+        | Statement::ReleaseSavepoint { .. }
+        | Statement::LockTables { .. }
+        | Statement::UnlockTables => Some(SqlRiskReason::TransactionControl(keyword)),
+        Statement::SetVariable { .. }
+        | Statement::SetNames { .. }
+        | Statement::SetNamesDefault { .. }
+        | Statement::SetTimeZone { .. }
+        | Statement::SetRole { .. }
+        | Statement::Grant { .. }
+        | Statement::Revoke { .. } => Some(SqlRiskReason::SessionMutation(keyword)),
+        // EXECUTE, KILL, FLUSH and the like can do anything, so they are never assumed safe.
+        _ => Some(SqlRiskReason::UnknownStatement(keyword)),
+    }
+}
+
+// A query is a read unless a CTE feeds an INSERT or UPDATE, it takes row locks, or it
+// writes its result somewhere with INTO. Locks and INTO count wherever they sit, so CTE
+// bodies, derived tables, subqueries and every UNION arm are walked too.
+fn query_risk(query: &Query) -> Option<SqlRiskReason> {
+    if let Some(lock) = query.locks.first() {
+        return Some(SqlRiskReason::TransactionControl(
+            match lock.lock_type {
+                LockType::Update => "FOR UPDATE",
+                LockType::Share => "FOR SHARE",
+            }
+            .to_string(),
+        ));
+    }
+    query
+        .with
+        .iter()
+        .flat_map(|with| &with.cte_tables)
+        .find_map(|cte| query_risk(&cte.query))
+        .or_else(|| set_expr_risk(&query.body))
+}
+
+fn set_expr_risk(body: &SetExpr) -> Option<SqlRiskReason> {
+    match body {
+        SetExpr::Select(select) => select
+            .into
+            .as_ref()
+            .map(|_| SqlRiskReason::SessionMutation("INTO".to_string()))
+            .or_else(|| select_risk(select)),
+        SetExpr::Query(query) => query_risk(query),
+        SetExpr::SetOperation { left, right, .. } => {
+            set_expr_risk(left).or_else(|| set_expr_risk(right))
+        }
+        SetExpr::Insert(statement) | SetExpr::Update(statement) => {
+            Some(SqlRiskReason::WriteOperation(rendered_keyword(statement)))
+        }
+        SetExpr::Values(_) | SetExpr::Table(_) => None,
+    }
+}
+
+fn select_risk(select: &Select) -> Option<SqlRiskReason> {
+    select
+        .from
+        .iter()
+        .find_map(table_risk)
+        .or_else(|| {
+            select.projection.iter().find_map(|item| match item {
+                SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                    expr_risk(expr)
+                }
+                SelectItem::QualifiedWildcard(..) | SelectItem::Wildcard(_) => None,
+            })
+        })
+        .or_else(|| {
+            [&select.selection, &select.having]
+                .into_iter()
+                .flatten()
+                .find_map(expr_risk)
+        })
+}
+
+fn table_risk(table: &TableWithJoins) -> Option<SqlRiskReason> {
+    table_factor_risk(&table.relation).or_else(|| {
+        table.joins.iter().find_map(|join| {
+            table_factor_risk(&join.relation).or_else(|| match &join.join_operator {
+                JoinOperator::Inner(JoinConstraint::On(on))
+                | JoinOperator::LeftOuter(JoinConstraint::On(on))
+                | JoinOperator::RightOuter(JoinConstraint::On(on))
+                | JoinOperator::FullOuter(JoinConstraint::On(on)) => expr_risk(on),
+                _ => None,
+            })
+        })
+    })
+}
+
+fn table_factor_risk(factor: &TableFactor) -> Option<SqlRiskReason> {
+    match factor {
+        TableFactor::Derived { subquery, .. } => query_risk(subquery),
+        TableFactor::NestedJoin {
+            table_with_joins, ..
+        } => table_risk(table_with_joins),
+        _ => None,
+    }
+}
+
+// Follows the expression forms a subquery can sit in; the rest hold no query.
+fn expr_risk(expr: &Expr) -> Option<SqlRiskReason> {
+    match expr {
+        Expr::Subquery(subquery) | Expr::Exists { subquery, .. } => query_risk(subquery),
+        Expr::InSubquery { expr, subquery, .. } => expr_risk(expr).or_else(|| query_risk(subquery)),
+        Expr::BinaryOp { left, right, .. }
+        | Expr::AnyOp { left, right, .. }
+        | Expr::AllOp { left, right, .. }
+        | Expr::IsDistinctFrom(left, right)
+        | Expr::IsNotDistinctFrom(left, right)
+        | Expr::Like {
+            expr: left,
+            pattern: right,
+            ..
+        }
+        | Expr::ILike {
+            expr: left,
+            pattern: right,
+            ..
+        }
+        | Expr::RLike {
+            expr: left,
+            pattern: right,
+            ..
+        }
+        | Expr::SimilarTo {
+            expr: left,
+            pattern: right,
+            ..
+        } => expr_risk(left).or_else(|| expr_risk(right)),
+        Expr::UnaryOp { expr, .. }
+        | Expr::Nested(expr)
+        | Expr::Cast { expr, .. }
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::IsTrue(expr)
+        | Expr::IsNotTrue(expr)
+        | Expr::IsFalse(expr)
+        | Expr::IsNotFalse(expr) => expr_risk(expr),
+        Expr::Between {
+            expr, low, high, ..
+        } => [expr, low, high]
+            .into_iter()
+            .find_map(|expr| expr_risk(expr)),
+        Expr::InList { expr, list, .. } => {
+            expr_risk(expr).or_else(|| list.iter().find_map(expr_risk))
+        }
+        Expr::Tuple(exprs) => exprs.iter().find_map(expr_risk),
+        Expr::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => operand
+            .iter()
+            .chain(else_result)
+            .find_map(|expr| expr_risk(expr))
+            .or_else(|| conditions.iter().chain(results).find_map(expr_risk)),
+        Expr::Function(function) => match &function.args {
+            FunctionArguments::Subquery(subquery) => query_risk(subquery),
+            FunctionArguments::List(list) => list.args.iter().find_map(|arg| match arg {
+                FunctionArg::Named {
+                    arg: FunctionArgExpr::Expr(expr),
+                    ..
+                }
+                | FunctionArg::ExprNamed {
+                    arg: FunctionArgExpr::Expr(expr),
+                    ..
+                }
+                | FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => expr_risk(expr),
+                _ => None,
+            }),
+            FunctionArguments::None => None,
+        },
+        _ => None,
+    }
+}
+
+fn rendered_keyword(statement: &Statement) -> String {
+    first_keyword(&statement.to_string()).unwrap_or_default()
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServerFlavor {
+    MySql,
+    MariaDb,
+}
+
+impl fmt::Display for ServerFlavor {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::MySql => "MySQL",
+            Self::MariaDb => "MariaDB",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ServerVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+}
+
+impl ServerVersion {
+    #[must_use]
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    // Reads the leading `major.minor.patch` and ignores any distribution suffix.
+    fn parse_prefix(raw: &str) -> Option<Self> {
+        let numeric_end = raw
+            .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+            .unwrap_or(raw.len());
+        let mut parts = raw[..numeric_end].split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for ServerVersion {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServerCapability {
+    // `EXPLAIN ANALYZE` on MySQL, the `ANALYZE` statement on MariaDB.
+    ExplainAnalyze,
+    // The `MAX_EXECUTION_TIME` optimizer hint on SELECTs.
+    MaxExecutionTime,
+    // MariaDB's `SET STATEMENT max_statement_time = N FOR ...`.
+    MaxStatementTime,
+    // `performance_schema.data_locks` and `data_lock_waits`.
+    DataLocks,
+    // `CREATE SEQUENCE` and `NEXTVAL()`.
+    Sequences,
+}
+
+impl ServerCapability {
+    pub const ALL: [Self; 5] = [
+        Self::ExplainAnalyze,
+        Self::MaxExecutionTime,
+        Self::MaxStatementTime,
+        Self::DataLocks,
+        Self::Sequences,
+    ];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ExplainAnalyze => "explain_analyze",
+            Self::MaxExecutionTime => "max_execution_time",
+            Self::MaxStatementTime => "max_statement_time",
+            Self::DataLocks => "data_locks",
+            Self::Sequences => "sequences",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerCapabilities {
+    pub flavor: ServerFlavor,
+    pub version: ServerVersion,
+    pub version_string: String,
+}
+
+impl ServerCapabilities {
+    #[must_use]
+    pub fn new(flavor: ServerFlavor, version: ServerVersion) -> Self {
+        Self {
+            flavor,
+            version,
+            version_string: version.to_string(),
+        }
+    }
+
+    // Parses `SELECT VERSION()`. MariaDB names itself in the suffix, and servers that
+    // speak to old replicas prepend a fake `5.5.5-` before the real version.
+    #[must_use]
+    pub fn from_version_string(raw: &str) -> Option<Self> {
+        let trimmed = raw.trim();
+        let flavor = if trimmed.to_ascii_lowercase().contains("mariadb") {
+            ServerFlavor::MariaDb
+        } else {
+            ServerFlavor::MySql
+        };
+        let numeric = match flavor {
+            ServerFlavor::MariaDb => trimmed.strip_prefix("5.5.5-").unwrap_or(trimmed),
+            ServerFlavor::MySql => trimmed,
+        };
+
+        Some(Self {
+            flavor,
+            version: ServerVersion::parse_prefix(numeric)?,
+            version_string: trimmed.to_string(),
+        })
+    }
+
+    #[must_use]
+    pub fn at_least(&self, major: u16, minor: u16, patch: u16) -> bool {
+        self.version >= ServerVersion::new(major, minor, patch)
+    }
+
+    #[must_use]
+    pub fn supports(&self, capability: ServerCapability) -> bool {
+        match (capability, self.flavor) {
+            (ServerCapability::ExplainAnalyze, ServerFlavor::MySql) => self.at_least(8, 0, 18),
+            (ServerCapability::ExplainAnalyze, ServerFlavor::MariaDb) => self.at_least(10, 1, 0),
+            (ServerCapability::MaxExecutionTime, ServerFlavor::MySql) => self.at_least(5, 7, 8),
+            (ServerCapability::MaxStatementTime, ServerFlavor::MariaDb) => self.at_least(10, 1, 2),
+            (ServerCapability::DataLocks, ServerFlavor::MySql) => self.at_least(8, 0, 1),
+            (ServerCapability::Sequences, ServerFlavor::MariaDb) => self.at_least(10, 3, 0),
+            _ => false,
+        }
+    }
+
+    pub fn capabilities(&self) -> impl Iterator<Item = ServerCapability> + '_ {
+        ServerCapability::ALL
+            .into_iter()
+            .filter(|capability| self.supports(*capability))
+    }
+
+    // Only SELECTs are offered: MariaDB's ANALYZE really executes UPDATE and DELETE.
+    #[must_use]
+    pub fn explain_analyze_sql(&self, query: &str) -> Option<String> {
+        if !self.supports(ServerCapability::ExplainAnalyze) {
+            return None;
+        }
+        let trimmed = query.trim();
+        let body = trimmed.trim_end_matches(';').trim();
+        let first_word = body.split_whitespace().next()?;
+        if !first_word.eq_ignore_ascii_case("SELECT") || body.contains(';') {
+            return None;
+        }
+
+        Some(match self.flavor {
+            ServerFlavor::MySql => format!("EXPLAIN ANALYZE {body}"),
+            ServerFlavor::MariaDb => format!("ANALYZE {body}"),
+        })
+    }
+
+    #[must_use]
+    pub fn label(&self) -> String {
+        format!("{} {}", self.flavor, self.version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ServerCapabilities, ServerCapability, ServerFlavor, ServerVersion};
+
+    #[test]
+    fn version_strings_are_parsed_per_flavor() {
+        let mysql = ServerCapabilities::from_version_string("8.0.36-0ubuntu0.22.04.1")
+            .expect("mysql version");
+        assert_eq!(mysql.flavor, ServerFlavor::MySql);
+        assert_eq!(mysql.version, ServerVersion::new(8, 0, 36));
+        assert_eq!(mysql.label(), "MySQL 8.0.36");
+
+        let mariadb = ServerCapabilities::from_version_string("5.5.5-10.11.6-MariaDB-log")
+            .expect("mariadb version");
+        assert_eq!(mariadb.flavor, ServerFlavor::MariaDb);
+        assert_eq!(mariadb.version, ServerVersion::new(10, 11, 6));
+        assert_eq!(mariadb.version_string, "5.5.5-10.11.6-MariaDB-log");
+        assert_eq!(mariadb.label(), "MariaDB 10.11.6");
+
+        assert!(ServerCapabilities::from_version_string("unknown").is_none());
+    }
+
+    #[test]
+    fn capabilities_follow_flavor_and_version() {
+        let mysql_57 = ServerCapabilities::new(ServerFlavor::MySql, ServerVersion::new(5, 7, 44));
+        assert_eq!(
+            mysql_57.capabilities().collect::<Vec<_>>(),
+            [ServerCapability::MaxExecutionTime]
+        );
+
+        let mysql_8 = ServerCapabilities::new(ServerFlavor::MySql, ServerVersion::new(8, 0, 36));
+        assert_eq!(
+            mysql_8.capabilities().collect::<Vec<_>>(),
+            [
+                ServerCapability::ExplainAnalyze,
+                ServerCapability::MaxExecutionTime,
+                ServerCapability::DataLocks
+            ]
+        );
+
+        let mariadb = ServerCapabilities::new(ServerFlavor::MariaDb, ServerVersion::new(10, 6, 0));
+        assert_eq!(
+            mariadb.capabilities().collect::<Vec<_>>(),
+            [
+                ServerCapability::ExplainAnalyze,
+                ServerCapability::MaxStatementTime,
+                ServerCapability::Sequences
+            ]
+        );
+    }
+
+    #[test]
+    fn explain_analyze_uses_the_flavor_syntax_for_selects_only() {
+        let mysql = ServerCapabilities::new(ServerFlavor::MySql, ServerVersion::new(8, 0, 36));
+        assert_eq!(
+            mysql.explain_analyze_sql("SELECT * FROM users;").as_deref(),
+            Some("EXPLAIN ANALYZE SELECT * FROM users")
+        );
+        assert_eq!(mysql.explain_analyze_sql("DELETE FROM users"), None);
+
+        let mariadb = ServerCapabilities::new(ServerFlavor::MariaDb, ServerVersion::new(10, 11, 6));
+        assert_eq!(
+            mariadb.explain_analyze_sql("select 1").as_deref(),
+            Some("ANALYZE select 1")
+        );
+
+        let old_mysql = ServerCapabilities::new(ServerFlavor::MySql, ServerVersion::new(8, 0, 17));
+        assert_eq!(old_mysql.explain_analyze_sql("SELECT 1"), None);
+    }
+}
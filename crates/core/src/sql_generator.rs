@@ -1,5 +1,9 @@
+use std::time::Duration;
+
 use thiserror::Error;
 
+use crate::server_capabilities::{ServerCapabilities, ServerCapability};
+
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum SqlGenerationError {
     #[error("database name cannot be empty")]
@@ -196,14 +200,116 @@ pub fn filtered_sorted_preview_sql(
     ))
 }
 
+// MySQL only honours MAX_EXECUTION_TIME on top-level read-only SELECTs, so anything else
+// (including statements that already carry an optimizer hint) is left untouched.
+#[must_use]
+pub fn with_max_execution_time_hint(sql: &str, timeout: Duration) -> Option<String> {
+    let trimmed = sql.trim();
+    let body = trimmed.strip_suffix(';').unwrap_or(trimmed);
+    let keyword = body.get(..6)?;
+    let rest = &body[6..];
+    if !keyword.eq_ignore_ascii_case("select")
+        || !rest.starts_with(char::is_whitespace)
+        || rest.trim_start().starts_with("/*+")
+        || body.contains(';')
+    {
+        return None;
+    }
+
+    let millis = timeout.as_millis().clamp(1, u128::from(u32::MAX));
+    Some(format!(
+        "{keyword} /*+ MAX_EXECUTION_TIME({millis}) */{rest}"
+    ))
+}
+
+// MariaDB parses the MySQL hint as a plain comment, so the same limit is expressed with
+// its per-statement `max_statement_time` (in seconds) instead.
+#[must_use]
+pub fn with_server_time_limit(
+    sql: &str,
+    timeout: Duration,
+    server: &ServerCapabilities,
+) -> Option<String> {
+    if server.supports(ServerCapability::MaxExecutionTime) {
+        return with_max_execution_time_hint(sql, timeout);
+    }
+    if !server.supports(ServerCapability::MaxStatementTime) {
+        return None;
+    }
+
+    // Same eligibility as the hint: one top-level SELECT without a hint of its own.
+    with_max_execution_time_hint(sql, timeout)?;
+    let trimmed = sql.trim();
+    let body = trimmed.strip_suffix(';').unwrap_or(trimmed);
+    let seconds = timeout.as_secs_f64().max(0.001);
+    Some(format!(
+        "SET STATEMENT max_statement_time={seconds:.3} FOR {body}"
+    ))
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::{
         count_estimate_sql, describe_table_sql, filtered_sorted_preview_sql, keyset_first_page_sql,
         keyset_page_sql, offset_page_sql, preview_select_sql, quote_identifier,
-        select_column_preview_sql, show_create_table_sql, show_index_sql, PaginationDirection,
+        select_column_preview_sql, show_create_table_sql, show_index_sql,
+        with_max_execution_time_hint, with_server_time_limit, PaginationDirection,
         SqlGenerationError, SqlTarget,
     };
+    use crate::server_capabilities::{ServerCapabilities, ServerFlavor, ServerVersion};
+
+    #[test]
+    fn max_execution_time_hint_is_only_added_to_single_selects() {
+        assert_eq!(
+            with_max_execution_time_hint("select id from users;", Duration::from_secs(5))
+                .as_deref(),
+            Some("select /*+ MAX_EXECUTION_TIME(5000) */ id from users")
+        );
+        assert_eq!(
+            with_max_execution_time_hint(
+                "SELECT /*+ MAX_EXECUTION_TIME(10) */ 1",
+                Duration::from_secs(5)
+            ),
+            None
+        );
+        assert_eq!(
+            with_max_execution_time_hint("UPDATE users SET a = 1", Duration::from_secs(5)),
+            None
+        );
+        assert_eq!(
+            with_max_execution_time_hint("SELECT 1; SELECT 2", Duration::from_secs(5)),
+            None
+        );
+        assert_eq!(
+            with_max_execution_time_hint("SELECTED", Duration::from_secs(5)),
+            None
+        );
+    }
+
+    #[test]
+    fn server_time_limit_follows_the_server_flavor() {
+        let timeout = Duration::from_millis(2_500);
+        let mysql = ServerCapabilities::new(ServerFlavor::MySql, ServerVersion::new(8, 0, 36));
+        assert_eq!(
+            with_server_time_limit("SELECT 1", timeout, &mysql).as_deref(),
+            Some("SELECT /*+ MAX_EXECUTION_TIME(2500) */ 1")
+        );
+
+        let mariadb = ServerCapabilities::new(ServerFlavor::MariaDb, ServerVersion::new(10, 11, 6));
+        assert_eq!(
+            with_server_time_limit("SELECT * FROM users;", timeout, &mariadb).as_deref(),
+            Some("SET STATEMENT max_statement_time=2.500 FOR SELECT * FROM users")
+        );
+        assert_eq!(
+            with_server_time_limit("UPDATE users SET a = 1", timeout, &mariadb),
+            None
+        );
+
+        let mysql_56 = ServerCapabilities::new(ServerFlavor::MySql, ServerVersion::new(5, 6, 51));
+        assert_eq!(with_server_time_limit("SELECT 1", timeout, &mysql_56), None);
+    }
 
     #[test]
     fn quotes_identifiers_with_backticks() {
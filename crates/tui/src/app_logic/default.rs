@@ -7,6 +7,13 @@ impl Default for TuiApp {
             wizard_form: startup_wizard_form(),
             connected_profile: None,
             last_connection_latency: None,
+            latency_history: LatencyHistory::default(),
+            last_keepalive_at: None,
+            connection_stale: false,
+            server_capabilities: None,
+            keepalive_rx: None,
+            parked_workspaces: Vec::new(),
+            active_workspace_slot: 0,
             data_backend: None,
             schema_cache: None,
             schema_databases: vec!["app".to_string()],
@@ -45,17 +52,30 @@ impl Default for TuiApp {
             query_ticks_remaining: 0,
             safe_mode_guard: SafeModeGuard::new(true),
             pending_confirmation: None,
+            confirmation_phrase: None,
+            confirmation_input: String::new(),
+            parameter_form: None,
+            bound_query: None,
+            transaction_open: false,
+            transaction_prompt: None,
+            deferred_transaction_exit: None,
             has_results: false,
             result_columns: vec![
                 "id".to_string(),
                 "value".to_string(),
                 "observed_at".to_string(),
             ],
+            result_column_metadata: Vec::new(),
+            result_sets: Vec::new(),
+            active_result_set: 0,
             results_cursor: 0,
             results_column_cursor: 1,
             results_search_mode: false,
             results_search_query: String::new(),
-            results: ResultsRingBuffer::new(RESULT_BUFFER_CAPACITY),
+            results_search_scan: None,
+            results: Box::new(ResultsRingBuffer::new(RESULT_BUFFER_CAPACITY)),
+            parked_primary_results: None,
+            reported_spill_error: None,
             pagination_state: None,
             pending_page_transition: None,
             cancel_requested: false,
@@ -64,6 +84,8 @@ impl Default for TuiApp {
             connect_result_rx: None,
             query_result_rx: None,
             query_cancellation: None,
+            query_stream_rx: None,
+            stream_progress: None,
             active_connection_profile: None,
             last_connect_profile: None,
             pending_retry_query: None,
@@ -79,12 +101,14 @@ impl Default for TuiApp {
             audit_trail: default_audit_trail(),
             bookmark_store: default_bookmark_store(),
             profile_store: default_profile_store(),
+            option_file_path: default_option_file_location(),
             bookmark_cycle_index: 0,
             manager_lane: ManagerLane::Profiles,
             manager_profile_cursor: 0,
             manager_bookmark_cursor: 0,
             manager_rename_mode: false,
             manager_rename_buffer: String::new(),
+            clipboard_request: None,
             query_editor_text: "SELECT * FROM `users`".to_string(),
             query_cursor: "SELECT * FROM `users`".len(),
             query_history: Vec::new(),
@@ -101,14 +125,25 @@ impl Default for TuiApp {
 
 pub(super) fn wizard_form_from_profile(profile: &ConnectionProfile) -> ConnectionWizardForm {
     ConnectionWizardForm {
+        url: String::new(),
         profile_name: profile.name.clone(),
         host: profile.host.clone(),
         port: profile.port.to_string(),
+        socket_path: profile.socket_path.clone().unwrap_or_default(),
         user: profile.user.clone(),
         password_source: match profile.password_source {
             PasswordSource::EnvVar => "env".to_string(),
             PasswordSource::Keyring => "keyring".to_string(),
+            PasswordSource::Command => "command".to_string(),
+            PasswordSource::File => "file".to_string(),
         },
+        password_from: match profile.password_source {
+            PasswordSource::EnvVar => profile.password_env_var.clone(),
+            PasswordSource::Keyring => None,
+            PasswordSource::Command => profile.password_command.clone(),
+            PasswordSource::File => profile.password_file.clone(),
+        }
+        .unwrap_or_default(),
         database: profile.database.clone().unwrap_or_default(),
         tls_mode: match profile.tls_mode {
             TlsMode::Disabled => "disabled".to_string(),
@@ -121,6 +156,23 @@ pub(super) fn wizard_form_from_profile(profile: &ConnectionProfile) -> Connectio
         } else {
             "no".to_string()
         },
+        environment: profile
+            .environment
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        ssh_jump: format_ssh_jump(profile),
+        ssh_key_path: profile.ssh_key_path.clone().unwrap_or_default(),
+        ssh_known_hosts: match profile.ssh_known_hosts {
+            SshKnownHosts::Strict => "strict".to_string(),
+            SshKnownHosts::AcceptNew => "accept_new".to_string(),
+            SshKnownHosts::Off => "off".to_string(),
+        },
+        ssh_agent: if profile.ssh_disable_agent {
+            "no".to_string()
+        } else {
+            "yes".to_string()
+        },
         active_field: WizardField::ProfileName,
         editing: false,
         edit_buffer: String::new(),
@@ -22,6 +22,9 @@ impl TuiApp {
             self.append_schema_filter_char(ch);
         } else if self.pane == Pane::ProfileBookmarks {
             self.handle_manager_input_char(ch);
+        } else if self.typing_confirmation() {
+            self.confirmation_input.push(ch);
+            self.status_line = format!("Confirm by typing: {}", self.confirmation_input);
         } else if self.pane == Pane::QueryEditor {
             self.insert_text_at_query_cursor(&ch.to_string());
             self.status_line = "Query text updated".to_string();
@@ -60,6 +63,9 @@ impl TuiApp {
             self.backspace_schema_filter();
         } else if self.pane == Pane::ProfileBookmarks {
             self.handle_manager_backspace();
+        } else if self.typing_confirmation() {
+            self.confirmation_input.pop();
+            self.status_line = format!("Confirm by typing: {}", self.confirmation_input);
         } else if self.pane == Pane::QueryEditor {
             self.backspace_query_editor_char();
             self.status_line = "Query text updated".to_string();
@@ -237,6 +243,39 @@ impl TuiApp {
         self.wizard_form.editing = false;
         self.wizard_form.edit_buffer.clear();
         self.status_line = format!("Saved {}", self.wizard_form.active_field.label());
+        if self.wizard_form.active_field == WizardField::Url {
+            self.fill_wizard_from_url();
+        }
+    }
+
+    // Spreads a pasted URL over the discrete fields so they show what will be used;
+    // fields the URL leaves out keep whatever was typed before.
+    fn fill_wizard_from_url(&mut self) {
+        let url = self.wizard_form.url.trim().to_string();
+        if url.is_empty() {
+            return;
+        }
+
+        let mut profile = ConnectionProfile::new(
+            self.wizard_form.profile_name.clone(),
+            String::new(),
+            self.wizard_form.user.clone(),
+        );
+        profile.tls_mode = parse_tls_mode(&self.wizard_form.tls_mode).unwrap_or_default();
+        if let Err(error) = profile.apply_url(&url) {
+            self.status_line = format!("Invalid connection URL: {error}");
+            return;
+        }
+
+        let filled = wizard_form_from_profile(&profile);
+        self.wizard_form.host = filled.host;
+        self.wizard_form.port = filled.port;
+        self.wizard_form.socket_path = filled.socket_path;
+        self.wizard_form.user = filled.user;
+        self.wizard_form.database = filled.database;
+        self.wizard_form.tls_mode = filled.tls_mode;
+        self.status_line =
+            "Filled host, port, user, database and TLS mode from the URL".to_string();
     }
 
     fn cancel_wizard_edit(&mut self) {
@@ -250,27 +289,43 @@ impl TuiApp {
 
     fn active_wizard_value(&self) -> &str {
         match self.wizard_form.active_field {
+            WizardField::Url => self.wizard_form.url.as_str(),
             WizardField::ProfileName => self.wizard_form.profile_name.as_str(),
             WizardField::Host => self.wizard_form.host.as_str(),
             WizardField::Port => self.wizard_form.port.as_str(),
+            WizardField::SocketPath => self.wizard_form.socket_path.as_str(),
             WizardField::User => self.wizard_form.user.as_str(),
             WizardField::PasswordSource => self.wizard_form.password_source.as_str(),
+            WizardField::PasswordFrom => self.wizard_form.password_from.as_str(),
             WizardField::Database => self.wizard_form.database.as_str(),
             WizardField::TlsMode => self.wizard_form.tls_mode.as_str(),
             WizardField::ReadOnly => self.wizard_form.read_only.as_str(),
+            WizardField::Environment => self.wizard_form.environment.as_str(),
+            WizardField::SshJump => self.wizard_form.ssh_jump.as_str(),
+            WizardField::SshKeyPath => self.wizard_form.ssh_key_path.as_str(),
+            WizardField::SshKnownHosts => self.wizard_form.ssh_known_hosts.as_str(),
+            WizardField::SshAgent => self.wizard_form.ssh_agent.as_str(),
         }
     }
 
     fn active_wizard_value_mut(&mut self) -> &mut String {
         match self.wizard_form.active_field {
+            WizardField::Url => &mut self.wizard_form.url,
             WizardField::ProfileName => &mut self.wizard_form.profile_name,
             WizardField::Host => &mut self.wizard_form.host,
             WizardField::Port => &mut self.wizard_form.port,
+            WizardField::SocketPath => &mut self.wizard_form.socket_path,
             WizardField::User => &mut self.wizard_form.user,
             WizardField::PasswordSource => &mut self.wizard_form.password_source,
+            WizardField::PasswordFrom => &mut self.wizard_form.password_from,
             WizardField::Database => &mut self.wizard_form.database,
             WizardField::TlsMode => &mut self.wizard_form.tls_mode,
             WizardField::ReadOnly => &mut self.wizard_form.read_only,
+            WizardField::Environment => &mut self.wizard_form.environment,
+            WizardField::SshJump => &mut self.wizard_form.ssh_jump,
+            WizardField::SshKeyPath => &mut self.wizard_form.ssh_key_path,
+            WizardField::SshKnownHosts => &mut self.wizard_form.ssh_known_hosts,
+            WizardField::SshAgent => &mut self.wizard_form.ssh_agent,
         }
     }
 
@@ -4,10 +4,14 @@ include!("default.rs");
 include!("runtime/handle.rs");
 include!("runtime/connect.rs");
 include!("runtime/query.rs");
+include!("runtime/workspaces.rs");
+include!("runtime/keepalive.rs");
 include!("navigation.rs");
 include!("input.rs");
 include!("query_actions/action_dispatch.rs");
 include!("query_actions/query_execution.rs");
+include!("query_actions/parameters.rs");
+include!("query_actions/transactions.rs");
 include!("query_actions/pagination.rs");
 include!("query_actions/error_panel.rs");
 include!("query_actions/pane_state.rs");
@@ -14,6 +14,13 @@ impl TuiApp {
         bookmark.column = self.selection.column.clone();
         if !query_trimmed.is_empty() {
             bookmark.query = Some(self.query_editor_text.clone());
+            if let Some(bound) = self
+                .bound_query
+                .as_ref()
+                .filter(|bound| bound.sql == query_trimmed)
+            {
+                bookmark.parameters = bound.values.clone();
+            }
         }
 
         let base_name = bookmark_base_name(
@@ -5,10 +5,11 @@ impl TuiApp {
             return;
         }
 
-        let rows = (0..self.results.len())
-            .filter_map(|index| self.results.get(index))
-            .map(|row| row.values.clone())
-            .collect::<Vec<_>>();
+        let results = self.results.as_ref();
+        let rows = (0..results.len())
+            .step_by(RESULTS_SCAN_CHUNK)
+            .flat_map(|start| results.full_rows(start, RESULTS_SCAN_CHUNK))
+            .map(|row| row.values);
         let file_path = export_file_path(match format {
             myr_core::actions_engine::ExportFormat::Csv => "csv",
             myr_core::actions_engine::ExportFormat::Json => "json",
@@ -20,38 +21,46 @@ impl TuiApp {
 
         let result = match format {
             myr_core::actions_engine::ExportFormat::Csv => {
-                export_rows_to_csv(&file_path, &self.result_columns, &rows)
+                export_rows_to_csv(&file_path, &self.result_columns, rows)
             }
             myr_core::actions_engine::ExportFormat::Json => {
-                export_rows_to_json(&file_path, &self.result_columns, &rows)
+                export_rows_to_json(&file_path, &self.result_columns, rows)
             }
             myr_core::actions_engine::ExportFormat::CsvGzip => export_rows_to_csv_with_options(
                 &file_path,
                 &self.result_columns,
-                &rows,
+                rows,
                 ExportCompression::Gzip,
+                ValueEncoding::Typed,
+                None,
             ),
             myr_core::actions_engine::ExportFormat::JsonGzip => export_rows_to_json_with_options(
                 &file_path,
                 &self.result_columns,
-                &rows,
+                rows,
                 JsonExportFormat::Array,
                 ExportCompression::Gzip,
+                ValueEncoding::Typed,
+                None,
             ),
             myr_core::actions_engine::ExportFormat::JsonLines => export_rows_to_json_with_options(
                 &file_path,
                 &self.result_columns,
-                &rows,
+                rows,
                 JsonExportFormat::JsonLines,
                 ExportCompression::None,
+                ValueEncoding::Typed,
+                None,
             ),
             myr_core::actions_engine::ExportFormat::JsonLinesGzip => {
                 export_rows_to_json_with_options(
                     &file_path,
                     &self.result_columns,
-                    &rows,
+                    rows,
                     JsonExportFormat::JsonLines,
                     ExportCompression::Gzip,
+                    ValueEncoding::Typed,
+                    None,
                 )
             }
         };
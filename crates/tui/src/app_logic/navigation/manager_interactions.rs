@@ -78,9 +78,12 @@ impl TuiApp {
             'r' => self.start_manager_rename(),
             'd' => self.mark_selected_profile_default(),
             'q' => self.mark_selected_profile_quick_reconnect(),
+            'u' => self.copy_selected_profile_url(),
+            'i' => self.import_option_file_profiles(ImportConflict::Skip),
+            'o' => self.import_option_file_profiles(ImportConflict::Overwrite),
             _ => {
                 self.status_line =
-                    "Manager shortcuts: r rename | d default profile | q quick reconnect target"
+                    "Manager shortcuts: r rename | d default profile | q quick reconnect target | u copy URL | i import ~/.my.cnf (o overwrites)"
                         .to_string();
             }
         }
@@ -308,6 +311,57 @@ impl TuiApp {
         }
     }
 
+    fn copy_selected_profile_url(&mut self) {
+        if self.manager_lane != ManagerLane::Profiles {
+            self.status_line = "Copy as URL applies to Profiles lane".to_string();
+            return;
+        }
+        let Some(profile) = self
+            .manager_profiles()
+            .get(self.manager_profile_cursor)
+            .cloned()
+        else {
+            self.status_line = "No profiles available".to_string();
+            return;
+        };
+
+        let url = profile.to_url();
+        self.status_line = format!("Copied URL for `{}`: {url}", profile.name);
+        self.clipboard_request = Some(url);
+    }
+
+    fn import_option_file_profiles(&mut self, on_conflict: ImportConflict) {
+        let Some(path) = self.option_file_path.clone() else {
+            self.status_line = "MySQL option file location is unknown (HOME is unset)".to_string();
+            return;
+        };
+        let Some(store) = self.profile_store.as_mut() else {
+            self.status_line = "Profile storage unavailable on this platform".to_string();
+            return;
+        };
+        let import = match import_option_file(&path) {
+            Ok(import) => import,
+            Err(error) => {
+                self.status_line = format!("Option file import failed: {error}");
+                return;
+            }
+        };
+
+        let summary = store.import_profiles(import.profiles, on_conflict);
+        if let Err(error) = store.persist() {
+            self.status_line = format!("Option file import failed: {error}");
+            return;
+        }
+        self.clamp_manager_cursors();
+        self.status_line = format!("Imported {}: {summary}", path.display());
+        if let Some(warning) = import.warnings.first() {
+            self.status_line.push_str(&format!(
+                " | {} warning(s), e.g. {warning}",
+                import.warnings.len()
+            ));
+        }
+    }
+
     fn mark_selected_profile_quick_reconnect(&mut self) {
         if self.manager_lane != ManagerLane::Profiles {
             self.status_line = "Quick reconnect marker applies to Profiles lane".to_string();
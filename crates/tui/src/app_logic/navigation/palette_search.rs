@@ -12,6 +12,7 @@ fn palette_aliases(action_id: ActionId) -> &'static [&'static str] {
         ActionId::RunCurrentQuery => &["run", "execute", "query"],
         ActionId::ApplyLimit200 => &["limit", "cap rows", "preview limit"],
         ActionId::ExplainQuery => &["explain", "plan", "query plan"],
+        ActionId::ExplainAnalyzeQuery => &["explain analyze", "analyze", "profile", "timings"],
         ActionId::BuildFilterSortQuery => &["filter", "sort", "where", "order by"],
         ActionId::InsertSelectSnippet => &["snippet", "select template"],
         ActionId::InsertJoinSnippet => &["snippet", "join template"],
@@ -28,6 +29,12 @@ fn palette_aliases(action_id: ActionId) -> &'static [&'static str] {
         ActionId::CopyRow => &["copy row", "clipboard row"],
         ActionId::SearchResults => &["search", "find", "grep"],
         ActionId::FocusQueryEditor => &["editor", "sql", "go query editor"],
+        ActionId::NextResultSet => &["result set", "next set", "procedure results"],
+        ActionId::BeginTransaction => &["begin", "start transaction", "tx"],
+        ActionId::CommitTransaction => &["commit", "tx commit"],
+        ActionId::RollbackTransaction => &["rollback", "undo transaction", "tx rollback"],
+        ActionId::NextConnection => &["switch connection", "next tab", "connection tab"],
+        ActionId::CloseConnection => &["disconnect", "close tab", "close connection"],
     }
 }
 
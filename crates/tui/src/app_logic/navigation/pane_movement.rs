@@ -61,14 +61,22 @@ impl TuiApp {
 
     fn previous_wizard_field(&self) -> WizardField {
         match self.wizard_form.active_field {
-            WizardField::ProfileName => WizardField::ReadOnly,
+            WizardField::Url => WizardField::SshAgent,
+            WizardField::ProfileName => WizardField::Url,
             WizardField::Host => WizardField::ProfileName,
             WizardField::Port => WizardField::Host,
-            WizardField::User => WizardField::Port,
+            WizardField::SocketPath => WizardField::Port,
+            WizardField::User => WizardField::SocketPath,
             WizardField::PasswordSource => WizardField::User,
-            WizardField::Database => WizardField::PasswordSource,
+            WizardField::PasswordFrom => WizardField::PasswordSource,
+            WizardField::Database => WizardField::PasswordFrom,
             WizardField::TlsMode => WizardField::Database,
             WizardField::ReadOnly => WizardField::TlsMode,
+            WizardField::Environment => WizardField::ReadOnly,
+            WizardField::SshJump => WizardField::Environment,
+            WizardField::SshKeyPath => WizardField::SshJump,
+            WizardField::SshKnownHosts => WizardField::SshKeyPath,
+            WizardField::SshAgent => WizardField::SshKnownHosts,
         }
     }
 
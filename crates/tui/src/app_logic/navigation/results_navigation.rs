@@ -52,6 +52,7 @@ impl TuiApp {
     }
 
     fn apply_results_search(&mut self, find_next: bool) {
+        self.results_search_scan = None;
         let query = self.results_search_query.trim();
         if query.is_empty() {
             self.status_line = "Search results: type text, Enter next, Esc cancel".to_string();
@@ -70,48 +71,121 @@ impl TuiApp {
             0
         };
 
-        if let Some(index) = self.find_results_match_index(query, start_index) {
-            self.results_cursor = index;
-            self.status_line = format!(
-                "Search matched row {} / {} for `{query}` (Enter next, Esc cancel)",
-                index + 1,
-                row_count
-            );
-        } else {
-            self.status_line = format!("No match for `{query}` in {row_count} buffered rows");
-        }
+        self.results_search_scan = Some(ResultsSearchScan {
+            needle: query.to_ascii_lowercase(),
+            start: start_index,
+            scanned: 0,
+        });
+        self.continue_results_search();
     }
 
-    fn find_results_match_index(&self, query: &str, start_index: usize) -> Option<usize> {
-        if self.results.is_empty() {
-            return None;
+    // A spilled result can hold millions of rows that are read back from disk, so each
+    // call scans at most `RESULTS_SEARCH_ROWS_PER_TICK` rows and the tick loop resumes the
+    // scan until it finds a match, runs out of rows, or the search is cancelled.
+    pub(super) fn continue_results_search(&mut self) {
+        let Some(mut scan) = self.results_search_scan.take() else {
+            return;
+        };
+        let row_count = self.results.len();
+        if !self.results_search_mode || row_count == 0 {
+            return;
         }
 
-        let needle = query.to_ascii_lowercase();
-        let row_count = self.results.len();
-        for offset in 0..row_count {
-            let index = (start_index + offset) % row_count;
-            let Some(row) = self.results.get(index) else {
-                continue;
-            };
-            if row
-                .values
-                .iter()
-                .any(|value| value.to_ascii_lowercase().contains(&needle))
-            {
-                return Some(index);
+        let query = self.results_search_query.trim();
+        let budget_end = (scan.scanned + RESULTS_SEARCH_ROWS_PER_TICK).min(row_count);
+        while scan.scanned < budget_end {
+            let chunk_start = (scan.start + scan.scanned) % row_count;
+            let chunk_len = RESULTS_SCAN_CHUNK
+                .min(row_count - chunk_start)
+                .min(budget_end - scan.scanned);
+            let rows = self.results.rows(chunk_start, chunk_len);
+            if let Some(offset) = rows.iter().position(|row| {
+                row.values
+                    .iter()
+                    .filter(|value| !value.is_null())
+                    .any(|value| value.to_string().to_ascii_lowercase().contains(&scan.needle))
+            }) {
+                let index = chunk_start + offset;
+                self.results_cursor = index;
+                self.status_line = format!(
+                    "Search matched row {} / {} for `{query}` (Enter next, Esc cancel)",
+                    index + 1,
+                    row_count
+                );
+                return;
             }
+            scan.scanned += chunk_len;
         }
 
-        None
+        if scan.scanned >= row_count {
+            self.status_line = format!("No match for `{query}` in {row_count} buffered rows");
+            return;
+        }
+        self.status_line = format!(
+            "Searching for `{query}`: {} / {row_count} rows scanned (Esc cancel)",
+            scan.scanned
+        );
+        self.results_search_scan = Some(scan);
+    }
+
+    pub(super) fn show_full_cell_value(&mut self) -> bool {
+        let row_index = self.results_cursor;
+        let column_index = self.results_column_cursor;
+        let truncated = self
+            .results
+            .row(row_index)
+            .and_then(|row| row.values.get(column_index).cloned())
+            .is_some_and(|value| value.is_truncated());
+        if !truncated {
+            return false;
+        }
+
+        let column = self.result_column_name(column_index);
+        let full_value = self
+            .results
+            .full_rows(row_index, 1)
+            .into_iter()
+            .next()
+            .and_then(|row| row.values.into_iter().nth(column_index))
+            .filter(|value| !value.is_truncated());
+        let Some(value) = full_value else {
+            self.status_line = format!(
+                "Full value of `{column}` is unavailable: the result was not spilled to disk"
+            );
+            return true;
+        };
+
+        let text = value.to_string();
+        let shown = text.chars().take(FULL_VALUE_PANEL_MAX_CHARS).collect::<String>();
+        let summary = if shown.len() < text.len() {
+            format!(
+                "{} bytes in row {}, showing the first {FULL_VALUE_PANEL_MAX_CHARS} characters (export for the rest)",
+                text.len(),
+                row_index + 1
+            )
+        } else {
+            format!("{} bytes in row {}", text.len(), row_index + 1)
+        };
+        self.open_error_panel(
+            ErrorKind::CellValue,
+            format!("Full value: {column}"),
+            summary,
+            shown,
+        );
+        self.status_line = format!("Loaded full value of `{column}` from the result spill");
+        true
     }
 
     pub(super) fn populate_demo_results(&mut self) {
-        self.results = ResultsRingBuffer::new(RESULT_BUFFER_CAPACITY);
+        self.results = Box::new(ResultsRingBuffer::new(RESULT_BUFFER_CAPACITY));
+        self.parked_primary_results = None;
         self.results_cursor = 0;
         self.results_column_cursor = 0;
         self.results_search_mode = false;
         self.results_search_query.clear();
+        self.result_column_metadata.clear();
+        self.result_sets.clear();
+        self.active_result_set = 0;
         self.result_columns = vec![
             "id".to_string(),
             "value".to_string(),
@@ -141,6 +215,57 @@ impl TuiApp {
         self.sync_results_column_selection();
     }
 
+    fn show_next_result_set(&mut self) {
+        let count = self.result_sets.len();
+        if count < 2 {
+            self.status_line = "Only one result set is available".to_string();
+            return;
+        }
+
+        // The first result set may be a spilled store, so it is parked while another
+        // set is shown; the additional sets are small ring buffers and are copied in.
+        let capacity = self.results.capacity();
+        let current = std::mem::replace(
+            &mut self.results,
+            Box::new(ResultsRingBuffer::new(capacity)),
+        );
+        if self.active_result_set == 0 {
+            self.parked_primary_results = Some(current);
+        }
+        self.active_result_set = (self.active_result_set + 1) % count;
+
+        let next = &self.result_sets[self.active_result_set];
+        self.results = if self.active_result_set == 0 {
+            self.parked_primary_results
+                .take()
+                .unwrap_or_else(|| Box::new(ResultsRingBuffer::new(capacity)))
+        } else {
+            Box::new(next.rows.clone())
+        };
+        self.result_column_metadata = next.columns.clone();
+        self.result_columns = next
+            .columns
+            .iter()
+            .map(|column| column.name.clone())
+            .collect();
+        let summary = if next.columns.is_empty() {
+            next.status.outcome()
+        } else {
+            format!("{} rows", next.rows_streamed)
+        };
+
+        self.has_results = !self.results.is_empty();
+        self.results_cursor = 0;
+        self.results_column_cursor = 0;
+        self.results_search_mode = false;
+        self.results_search_query.clear();
+        self.reset_results_column_focus();
+        self.status_line = format!(
+            "Result set {} of {count}: {summary}",
+            self.active_result_set + 1
+        );
+    }
+
     pub(super) fn reset_results_column_focus(&mut self) {
         let column_count = self.result_column_count();
         if column_count == 0 {
@@ -177,7 +302,7 @@ impl TuiApp {
     fn result_column_count(&self) -> usize {
         self.result_columns
             .len()
-            .max(self.results.get(self.results_cursor).map_or(0, |row| row.values.len()))
+            .max(self.results.row(self.results_cursor).map_or(0, |row| row.values.len()))
     }
 
     fn result_column_name(&self, index: usize) -> String {
@@ -12,7 +12,8 @@ impl TuiApp {
             ActionInvocation::RunSql(sql) => {
                 if action_id == ActionId::PreviewTable {
                     self.start_preview_paged_query(sql);
-                } else {
+                } else if action_id != ActionId::RunCurrentQuery || !self.open_parameter_form(&sql)
+                {
                     self.clear_pagination_state();
                     self.execute_sql_with_guard(sql);
                 }
@@ -23,6 +24,14 @@ impl TuiApp {
             ActionInvocation::PaginateNext => {
                 self.run_pagination_transition(PageTransition::Next);
             }
+            ActionInvocation::ShowNextResultSet => {
+                self.show_next_result_set();
+            }
+            ActionInvocation::TransactionControl(boundary) => {
+                self.run_transaction_statement(boundary);
+            }
+            ActionInvocation::NextConnection => self.next_connection(),
+            ActionInvocation::CloseConnection => self.close_active_connection(),
             ActionInvocation::RunHealthDiagnostics => {
                 self.run_health_diagnostics();
             }
@@ -40,6 +49,9 @@ impl TuiApp {
                 self.status_line = "Inserted query snippet".to_string();
             }
             ActionInvocation::CancelQuery => {
+                if let Some(cancellation) = &self.query_cancellation {
+                    cancellation.cancel();
+                }
                 let audit_sql = self.inflight_query_sql.clone().unwrap_or_default();
                 self.query_running = false;
                 self.query_ticks_remaining = 0;
@@ -112,6 +124,10 @@ impl TuiApp {
             pagination_enabled,
             can_page_next,
             can_page_previous,
+            has_multiple_result_sets: self.result_sets.len() > 1,
+            transaction_open: self.transaction_open,
+            open_connections: self.open_connection_count(),
+            server: self.server_capabilities.clone(),
         }
     }
 }
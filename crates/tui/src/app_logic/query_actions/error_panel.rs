@@ -19,6 +19,18 @@ impl TuiApp {
             return;
         };
 
+        if panel.kind == ErrorKind::Warnings {
+            self.error_panel = None;
+            self.status_line = "Warnings panel dismissed".to_string();
+            return;
+        }
+
+        if panel.kind == ErrorKind::CellValue {
+            self.error_panel = None;
+            self.status_line = "Full value panel dismissed".to_string();
+            return;
+        }
+
         if panel.kind == ErrorKind::Query {
             if let Some(sql) = self.last_failed_query.clone() {
                 self.error_panel = None;
@@ -17,6 +17,7 @@ impl TuiApp {
 
         if !self.schema_columns.is_empty() {
             self.result_columns = self.schema_columns.clone();
+            self.result_column_metadata.clear();
             self.reset_results_column_focus();
         }
         self.pagination_state = Some(state);
@@ -45,6 +46,7 @@ impl TuiApp {
 
         if !self.schema_columns.is_empty() {
             self.result_columns = self.schema_columns.clone();
+            self.result_column_metadata.clear();
             self.reset_results_column_focus();
         }
         self.pending_page_transition = Some(transition);
@@ -138,7 +140,7 @@ impl TuiApp {
             .as_ref()
             .and_then(|state| match &state.plan {
                 PaginationPlan::Keyset { key_column, .. } => Some(extract_key_bounds(
-                    &self.results,
+                    self.results.as_ref(),
                     &self.result_columns,
                     key_column,
                 )),
@@ -0,0 +1,148 @@
+impl TuiApp {
+    fn open_parameter_form(&mut self, sql: &str) -> bool {
+        let Some(query) = ParameterizedQuery::parse(sql) else {
+            return false;
+        };
+
+        let remembered = self.remembered_parameter_values(sql);
+        let form = QueryParameterForm::new(sql.to_string(), query, &remembered);
+        self.status_line = format!(
+            "Query parameters: editing {} (Enter run, Tab/Up/Down switch, Esc cancel)",
+            form.active_name()
+        );
+        self.parameter_form = Some(form);
+        true
+    }
+
+    fn remembered_parameter_values(&self, sql: &str) -> BTreeMap<String, String> {
+        if let Some(bound) = self.bound_query.as_ref().filter(|bound| bound.sql == sql) {
+            return bound.values.clone();
+        }
+
+        self.bookmark_store
+            .as_ref()
+            .and_then(|store| {
+                store.bookmarks().iter().find(|bookmark| {
+                    bookmark.query.as_deref().map(str::trim) == Some(sql)
+                        && !bookmark.parameters.is_empty()
+                })
+            })
+            .map(|bookmark| bookmark.parameters.clone())
+            .unwrap_or_default()
+    }
+
+    fn handle_parameter_form_input(&mut self, msg: Msg) {
+        match msg {
+            Msg::Tick => self.on_tick(),
+            Msg::Quit => self.request_quit(),
+            Msg::TogglePalette => {
+                self.parameter_form = None;
+                self.status_line = "Query parameters canceled".to_string();
+            }
+            Msg::Submit => self.submit_parameter_form(),
+            Msg::InputChar(ch) => self.push_parameter_char(ch),
+            Msg::ToggleHelp => self.push_parameter_char('?'),
+            Msg::InvokeActionSlot(index) => {
+                let digit = char::from_digit((index + 1) as u32, 10).unwrap_or('0');
+                self.push_parameter_char(digit);
+            }
+            Msg::Backspace => {
+                if let Some(value) = self
+                    .parameter_form
+                    .as_mut()
+                    .and_then(QueryParameterForm::active_value_mut)
+                {
+                    value.pop();
+                }
+            }
+            Msg::ClearInput => {
+                if let Some(value) = self
+                    .parameter_form
+                    .as_mut()
+                    .and_then(QueryParameterForm::active_value_mut)
+                {
+                    value.clear();
+                }
+            }
+            Msg::NextPane | Msg::Navigate(DirectionKey::Down) => self.move_parameter_field(true),
+            Msg::Navigate(DirectionKey::Up) => self.move_parameter_field(false),
+            _ => {
+                self.status_line =
+                    "Query parameters: Enter run, Tab/Up/Down switch, Ctrl+U clear, Esc cancel"
+                        .to_string();
+            }
+        }
+    }
+
+    fn push_parameter_char(&mut self, ch: char) {
+        if let Some(value) = self
+            .parameter_form
+            .as_mut()
+            .and_then(QueryParameterForm::active_value_mut)
+        {
+            value.push(ch);
+        }
+    }
+
+    fn move_parameter_field(&mut self, forward: bool) {
+        let Some(form) = self.parameter_form.as_mut() else {
+            return;
+        };
+        let count = form.values.len().max(1);
+        form.active_index = if forward {
+            (form.active_index + 1) % count
+        } else {
+            (form.active_index + count - 1) % count
+        };
+        self.status_line = format!("Query parameters: editing {}", form.active_name());
+    }
+
+    fn submit_parameter_form(&mut self) {
+        let Some(form) = self.parameter_form.take() else {
+            return;
+        };
+
+        let values = form.raw_values();
+        self.remember_bookmark_parameters(&form.sql, &values);
+        let sql = form.sql.clone();
+        self.bound_query = Some(BoundQuery {
+            sql: form.sql,
+            query: form.query,
+            values,
+        });
+        self.clear_pagination_state();
+        self.execute_sql_with_guard(sql);
+    }
+
+    fn remember_bookmark_parameters(&mut self, sql: &str, values: &BTreeMap<String, String>) {
+        let Some(store) = self.bookmark_store.as_mut() else {
+            return;
+        };
+
+        let stale = store
+            .bookmarks()
+            .iter()
+            .filter(|bookmark| {
+                bookmark.query.as_deref().map(str::trim) == Some(sql)
+                    && bookmark.parameters != *values
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        if stale.is_empty() {
+            return;
+        }
+
+        for mut bookmark in stale {
+            bookmark.parameters = values.clone();
+            store.upsert_bookmark(bookmark);
+        }
+        let _ = store.persist();
+    }
+
+    pub(super) fn bound_parameters_for(&self, sql: &str) -> Option<(String, Vec<QueryValue>)> {
+        self.bound_query
+            .as_ref()
+            .filter(|bound| bound.sql == sql)
+            .map(|bound| (bound.query.sql().to_string(), bound.query.bind(&bound.values)))
+    }
+}
@@ -7,11 +7,24 @@ impl TuiApp {
         elapsed: Option<Duration>,
         error: Option<&str>,
     ) {
-        let Some(audit_trail) = self.audit_trail.as_ref() else {
-            return;
-        };
+        self.append_audit_record(&self.audit_record(outcome, sql, rows_streamed, elapsed, error));
+    }
+
+    fn append_audit_record(&self, record: &AuditRecord) {
+        if let Some(audit_trail) = self.audit_trail.as_ref() {
+            let _ = audit_trail.append(record);
+        }
+    }
 
-        let record = AuditRecord {
+    fn audit_record(
+        &self,
+        outcome: AuditOutcome,
+        sql: &str,
+        rows_streamed: Option<u64>,
+        elapsed: Option<Duration>,
+        error: Option<&str>,
+    ) -> AuditRecord {
+        AuditRecord {
             timestamp_unix_ms: unix_timestamp_millis(),
             profile_name: self
                 .active_connection_profile
@@ -28,16 +41,26 @@ impl TuiApp {
             rows_streamed,
             elapsed_ms: elapsed.map(|duration| duration.as_millis()),
             error: error.map(|value| truncate_for_audit(value, AUDIT_ERROR_MAX_CHARS)),
-        };
-        let _ = audit_trail.append(&record);
+            affected_rows: None,
+            last_insert_id: None,
+            warning_count: None,
+            info: None,
+            transaction: None,
+        }
     }
 
     fn start_query(&mut self, sql: String) {
-        self.start_query_internal(sql, false);
+        if self.reconnect_stale_connection() {
+            self.pending_retry_query = Some(sql);
+            return;
+        }
+        self.start_query_internal(sql, None);
     }
 
-    fn start_query_internal(&mut self, sql: String, retrying: bool) {
-        if !retrying {
+    // `retry_delay` marks a retry: the attempt counters carry over and the worker backs
+    // off for that long before re-running the statement.
+    fn start_query_internal(&mut self, sql: String, retry_delay: Option<Duration>) {
+        if retry_delay.is_none() {
             self.query_retry_attempts = 0;
             self.last_failed_query = None;
             self.pending_retry_query = None;
@@ -64,20 +87,47 @@ impl TuiApp {
         self.has_results = false;
         self.query_cancellation = None;
         self.query_result_rx = None;
+        self.query_stream_rx = None;
+        self.stream_progress = None;
+
+        if transaction_boundary(&self.query_editor_text) == Some(TransactionBoundary::Begin) {
+            self.ensure_transaction_session();
+        }
 
         if let Some(data_backend) = &self.data_backend {
             self.query_running = true;
             self.query_ticks_remaining = 0;
-            self.results = ResultsRingBuffer::new(RESULT_BUFFER_CAPACITY);
+            self.results = Box::new(
+                ResultsRingBuffer::new(RESULT_BUFFER_CAPACITY)
+                    .with_byte_budget(RESULT_MEMORY_BUDGET_BYTES, RESULT_CELL_PREVIEW_BYTES),
+            );
             let cancellation = CancellationToken::new();
             self.query_cancellation = Some(cancellation.clone());
             let backend = data_backend.clone();
-            let sql = self.query_editor_text.clone();
+            let (sql, params) = self
+                .bound_parameters_for(&self.query_editor_text)
+                .unwrap_or_else(|| (self.query_editor_text.clone(), Vec::new()));
+            let timeout = self.query_timeout();
+            let retry_delay = retry_delay.unwrap_or_default();
             let (tx, rx) = mpsc::channel();
             self.query_result_rx = Some(rx);
+            let (batch_tx, batch_rx) = mpsc::channel();
+            self.query_stream_rx = Some(batch_rx);
+            self.stream_progress = Some(StreamProgress {
+                started_at: Instant::now(),
+                rows_received: 0,
+            });
 
             let _query_worker = thread::spawn(move || {
-                let _ = tx.send(run_query_worker(backend, sql, cancellation));
+                let _ = tx.send(run_query_worker(
+                    backend,
+                    sql,
+                    params,
+                    timeout,
+                    cancellation,
+                    Some(&batch_tx),
+                    retry_delay,
+                ));
             });
 
             self.status_line = "Running query...".to_string();
@@ -89,11 +139,29 @@ impl TuiApp {
         self.status_line = "Running query...".to_string();
     }
 
+    fn query_timeout(&self) -> Option<Duration> {
+        self.active_connection_profile
+            .as_ref()
+            .or(self.last_connect_profile.as_ref())
+            .map_or(
+                Some(Duration::from_secs(DEFAULT_QUERY_TIMEOUT_SECS)),
+                ConnectionProfile::query_timeout,
+            )
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        self.active_connection_profile
+            .as_ref()
+            .or(self.last_connect_profile.as_ref())
+            .map(ConnectionProfile::retry_policy)
+            .unwrap_or_default()
+    }
+
     fn current_profile_read_only(&self) -> bool {
         self.active_connection_profile
             .as_ref()
             .or(self.last_connect_profile.as_ref())
-            .is_some_and(|profile| profile.read_only)
+            .is_some_and(ConnectionProfile::is_read_only)
     }
 
     fn execute_sql_with_guard(&mut self, sql: String) {
@@ -115,6 +183,7 @@ impl TuiApp {
             }
         }
 
+        self.enforce_environment_safe_mode();
         match self.safe_mode_guard.evaluate(&sql) {
             GuardDecision::Allow { .. } => {
                 self.pending_confirmation = None;
@@ -122,17 +191,53 @@ impl TuiApp {
             }
             GuardDecision::RequireConfirmation { token, assessment } => {
                 self.pending_confirmation = Some((token, sql.clone()));
+                self.confirmation_phrase = self
+                    .active_connection_profile
+                    .as_ref()
+                    .filter(|profile| profile.requires_typed_confirmation())
+                    .map(|profile| profile.name.clone());
+                self.confirmation_input.clear();
                 self.query_editor_text = sql;
                 self.query_cursor = self.query_editor_text.len();
                 self.set_active_pane(Pane::QueryEditor);
-                self.status_line = format!(
-                    "Safe mode confirmation required: {:?}. Press Enter again to confirm.",
-                    assessment.reasons
-                );
+                self.status_line = match self.confirmation_phrase.as_deref() {
+                    Some(phrase) => format!(
+                        "Safe mode confirmation required: {:?}. Type `{phrase}` and press Enter to confirm (Esc cancels).",
+                        assessment.reasons
+                    ),
+                    None => format!(
+                        "Safe mode confirmation required: {:?}. Press Enter again to confirm.",
+                        assessment.reasons
+                    ),
+                };
             }
         }
     }
 
+    // Prod profiles keep safe mode on no matter how it was toggled on another connection.
+    fn enforce_environment_safe_mode(&mut self) {
+        if self
+            .active_connection_profile
+            .as_ref()
+            .is_some_and(ConnectionProfile::requires_safe_mode)
+        {
+            self.safe_mode_guard.set_enabled(true);
+        }
+    }
+
+    fn typing_confirmation(&self) -> bool {
+        self.pane == Pane::QueryEditor
+            && self.pending_confirmation.is_some()
+            && self.confirmation_phrase.is_some()
+    }
+
+    fn cancel_typed_confirmation(&mut self) {
+        self.pending_confirmation = None;
+        self.confirmation_phrase = None;
+        self.confirmation_input.clear();
+        self.status_line = "Confirmation canceled".to_string();
+    }
+
     fn run_health_diagnostics(&mut self) {
         let Some(data_backend) = self.data_backend.clone() else {
             self.status_line = "Health diagnostics failed: not connected".to_string();
@@ -159,7 +264,11 @@ impl TuiApp {
         match run_query_worker(
             data_backend,
             "SELECT 1 AS health_check".to_string(),
+            Vec::new(),
+            self.query_timeout(),
             CancellationToken::new(),
+            None,
+            Duration::ZERO,
         ) {
             QueryWorkerOutcome::Success {
                 rows_streamed,
@@ -0,0 +1,134 @@
+impl TuiApp {
+    fn run_transaction_statement(&mut self, boundary: TransactionBoundary) {
+        if self.query_running {
+            self.status_line =
+                "Wait for the running query to finish before ending the transaction".to_string();
+            return;
+        }
+        if boundary == TransactionBoundary::Begin && self.current_profile_read_only() {
+            let blocked_message =
+                "Blocked by read-only profile mode: transactions are disabled".to_string();
+            self.append_audit_event(
+                AuditOutcome::Blocked,
+                boundary.sql(),
+                None,
+                None,
+                Some(&blocked_message),
+            );
+            self.status_line = blocked_message;
+            return;
+        }
+
+        self.pending_confirmation = None;
+        self.clear_pagination_state();
+        self.start_query(boundary.sql().to_string());
+    }
+
+    // A transaction only spans one connection, so beginning one pins the query
+    // backend to a single session for as long as this connection is used.
+    fn ensure_transaction_session(&mut self) {
+        if let Some(backend) = self
+            .data_backend
+            .as_ref()
+            .filter(|backend| !backend.pinned_session())
+        {
+            self.data_backend = Some(backend.clone().with_pinned_session(true));
+        }
+    }
+
+    fn apply_transaction_boundary(&mut self, sql: &str) -> Option<TransactionBoundary> {
+        let boundary = transaction_boundary(sql)?;
+        self.transaction_open = boundary == TransactionBoundary::Begin;
+        Some(boundary)
+    }
+
+    fn discard_open_transaction(&mut self, reason: &str) -> bool {
+        if !self.transaction_open {
+            return false;
+        }
+
+        self.transaction_open = false;
+        let mut record = self.audit_record(
+            AuditOutcome::Failed,
+            TransactionBoundary::Rollback.sql(),
+            None,
+            None,
+            Some(&format!("transaction lost: {reason}")),
+        );
+        record.transaction = Some(TransactionBoundary::Rollback);
+        self.append_audit_record(&record);
+        true
+    }
+
+    fn request_quit(&mut self) {
+        if self.transaction_open || self.focus_parked_transaction() {
+            self.exit_confirmation = false;
+            self.prompt_transaction_exit(TransactionExitIntent::Quit);
+        } else {
+            self.should_quit = true;
+        }
+    }
+
+    fn prompt_transaction_exit(&mut self, intent: TransactionExitIntent) {
+        self.transaction_prompt = Some(intent);
+        self.status_line =
+            "Transaction open: C commit | R roll back | Esc keep transaction open".to_string();
+    }
+
+    fn handle_transaction_prompt_input(&mut self, msg: Msg) {
+        match msg {
+            Msg::Tick => self.on_tick(),
+            Msg::InputChar('c' | 'C') => {
+                self.resolve_transaction_prompt(TransactionBoundary::Commit);
+            }
+            Msg::InputChar('r' | 'R') => {
+                self.resolve_transaction_prompt(TransactionBoundary::Rollback);
+            }
+            Msg::TogglePalette => {
+                self.transaction_prompt = None;
+                self.status_line = "Transaction still open".to_string();
+            }
+            _ => {
+                self.status_line =
+                    "Transaction open: C commit | R roll back | Esc keep transaction open"
+                        .to_string();
+            }
+        }
+    }
+
+    fn resolve_transaction_prompt(&mut self, boundary: TransactionBoundary) {
+        if self.query_running {
+            self.status_line =
+                "Wait for the running query to finish before ending the transaction".to_string();
+            return;
+        }
+        let Some(intent) = self.transaction_prompt.take() else {
+            return;
+        };
+
+        self.deferred_transaction_exit = Some(intent);
+        self.run_transaction_statement(boundary);
+    }
+
+    fn finish_transaction_exit(&mut self, boundary: Option<TransactionBoundary>) {
+        let Some(intent) = self.deferred_transaction_exit.take() else {
+            return;
+        };
+        if self.transaction_open || !matches!(
+            boundary,
+            Some(TransactionBoundary::Commit | TransactionBoundary::Rollback)
+        ) {
+            self.status_line
+                .push_str(" | Transaction still open; exit canceled");
+            return;
+        }
+
+        match intent {
+            TransactionExitIntent::Quit => self.should_quit = true,
+            TransactionExitIntent::Connect(profile) => {
+                self.start_connect_with_profile(profile, ConnectIntent::Manual);
+            }
+            TransactionExitIntent::CloseConnection => self.close_active_connection(),
+        }
+    }
+}
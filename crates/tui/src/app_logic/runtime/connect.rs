@@ -33,6 +33,31 @@ impl TuiApp {
     }
 
     fn start_connect_with_profile(&mut self, profile: ConnectionProfile, intent: ConnectIntent) {
+        if intent == ConnectIntent::Manual
+            && self
+                .connected_profile
+                .as_deref()
+                .is_some_and(|active| active != profile.name)
+        {
+            if let Some(blocker) = self.workspace_switch_blocker() {
+                self.status_line = blocker.to_string();
+                return;
+            }
+            if let Some(index) = self.connection_tab_index(&profile.name) {
+                self.activate_workspace(index);
+            }
+        }
+        // Only a reconnect of the active profile replaces the session holding the transaction.
+        if intent == ConnectIntent::Manual
+            && self.transaction_open
+            && self
+                .connected_profile
+                .as_deref()
+                .is_none_or(|active| active == profile.name)
+        {
+            self.prompt_transaction_exit(TransactionExitIntent::Connect(profile));
+            return;
+        }
         if intent == ConnectIntent::Manual {
             self.reconnect_attempts = 0;
         }
@@ -42,26 +67,34 @@ impl TuiApp {
         self.connect_requested = true;
         self.connect_intent = intent;
         self.last_connect_profile = Some(profile.clone());
+        let policy = profile.retry_policy();
+        let retry_delay = if intent == ConnectIntent::AutoReconnect {
+            policy.backoff(self.reconnect_attempts.max(1))
+        } else {
+            Duration::ZERO
+        };
         self.status_line = if intent == ConnectIntent::AutoReconnect {
             format!(
-                "Auto-reconnect {}/{} for `{}`...",
+                "Auto-reconnect {}/{} for `{}` in {retry_delay:.1?}...",
                 self.reconnect_attempts.max(1),
-                AUTO_RECONNECT_LIMIT,
+                policy.max_reconnect_attempts,
                 profile.name
             )
         } else {
             format!(
-                "Connecting to {}:{} as {}...",
-                profile.host, profile.port, profile.user
+                "Connecting via {} as {}...",
+                profile.transport(),
+                profile.user
             )
         };
 
         let _connect_worker = thread::spawn(move || {
+            thread::sleep(retry_delay);
             let _ = tx.send(run_connect_worker(profile));
         });
     }
 
-    fn wizard_profile(&self) -> Result<ConnectionProfile, String> {
+    pub(super) fn wizard_profile(&self) -> Result<ConnectionProfile, String> {
         let port = self
             .wizard_form
             .port
@@ -69,7 +102,8 @@ impl TuiApp {
             .map_err(|_| "Invalid port in connection wizard".to_string())?;
         let password_source =
             parse_password_source(&self.wizard_form.password_source).ok_or_else(|| {
-                "Invalid password source in connection wizard (use env/keyring)".to_string()
+                "Invalid password source in connection wizard (use env/keyring/command/file)"
+                    .to_string()
             })?;
         let tls_mode = parse_tls_mode(&self.wizard_form.tls_mode).ok_or_else(|| {
             "Invalid TLS mode in connection wizard (use disabled/prefer/require/verify_identity)"
@@ -78,21 +112,89 @@ impl TuiApp {
         let read_only = parse_read_only_flag(&self.wizard_form.read_only).ok_or_else(|| {
             "Invalid read-only mode in connection wizard (use yes/no)".to_string()
         })?;
+        let ssh_jump = parse_ssh_jump(&self.wizard_form.ssh_jump).ok_or_else(|| {
+            "Invalid SSH jump in connection wizard (use user@host:port or leave blank)".to_string()
+        })?;
+        let ssh_known_hosts =
+            parse_ssh_known_hosts(&self.wizard_form.ssh_known_hosts).ok_or_else(|| {
+                "Invalid SSH known_hosts policy in connection wizard (use strict/accept_new/off)"
+                    .to_string()
+            })?;
+        let ssh_agent = parse_ssh_agent_flag(&self.wizard_form.ssh_agent).ok_or_else(|| {
+            "Invalid SSH agent setting in connection wizard (use yes/no)".to_string()
+        })?;
 
-        let mut profile = ConnectionProfile::new(
-            self.wizard_form.profile_name.clone(),
-            self.wizard_form.host.clone(),
-            self.wizard_form.user.clone(),
-        );
+        // Settings the wizard does not edit (timeouts, TLS files, session options) carry
+        // over from the saved profile of the same name.
+        let profile_name = self.wizard_form.profile_name.as_str();
+        let mut profile = self
+            .profile_store
+            .as_ref()
+            .and_then(|store| store.profile(profile_name).cloned())
+            .or_else(|| {
+                self.active_connection_profile
+                    .clone()
+                    .filter(|profile| profile.name == profile_name)
+            })
+            .unwrap_or_else(|| {
+                ConnectionProfile::new(profile_name, String::new(), String::new())
+            });
+        // The URL contributes what the form has no field for (TLS files, VERIFY_CA);
+        // the form fields it was spread into still win.
+        let url = self.wizard_form.url.trim();
+        if !url.is_empty() {
+            profile
+                .apply_url(url)
+                .map_err(|error| format!("Invalid connection URL in wizard: {error}"))?;
+        }
+        profile.host.clone_from(&self.wizard_form.host);
+        profile.user.clone_from(&self.wizard_form.user);
         profile.port = port;
+        profile.socket_path = Some(self.wizard_form.socket_path.trim().to_string())
+            .filter(|path| !path.is_empty());
         profile.database = if self.wizard_form.database.trim().is_empty() {
             None
         } else {
             Some(self.wizard_form.database.clone())
         };
         profile.password_source = password_source;
+        // One field serves whichever source is picked; the other sources keep their
+        // saved settings so switching back and forth loses nothing.
+        let password_from =
+            Some(self.wizard_form.password_from.trim().to_string()).filter(|from| !from.is_empty());
+        match password_source {
+            PasswordSource::EnvVar => profile.password_env_var = password_from,
+            PasswordSource::Keyring => {}
+            PasswordSource::Command => {
+                profile.password_command = Some(password_from.ok_or_else(|| {
+                    "Password command is required when password source is command".to_string()
+                })?);
+            }
+            PasswordSource::File => {
+                profile.password_file = Some(password_from.ok_or_else(|| {
+                    "Password file path is required when password source is file".to_string()
+                })?);
+            }
+        }
         profile.tls_mode = tls_mode;
         profile.read_only = read_only;
+        profile.environment = ProfileEnvironment::parse(&self.wizard_form.environment);
+        match ssh_jump {
+            Some(jump) => {
+                profile.ssh_host = Some(jump.host);
+                profile.ssh_port = jump.port;
+                profile.ssh_user = jump.user;
+            }
+            None => {
+                profile.ssh_host = None;
+                profile.ssh_port = None;
+                profile.ssh_user = None;
+            }
+        }
+        profile.ssh_key_path = Some(self.wizard_form.ssh_key_path.trim().to_string())
+            .filter(|path| !path.is_empty());
+        profile.ssh_known_hosts = ssh_known_hosts;
+        profile.ssh_disable_agent = !ssh_agent;
         Ok(profile)
     }
 
@@ -102,7 +204,7 @@ impl TuiApp {
                 Ok(outcome) => Some(outcome),
                 Err(TryRecvError::Empty) => None,
                 Err(TryRecvError::Disconnected) => Some(ConnectWorkerOutcome::Failure(
-                    "connect worker disconnected".to_string(),
+                    WorkerError::new("connect worker disconnected", None),
                 )),
             },
             None => None,
@@ -120,13 +222,25 @@ impl TuiApp {
         match outcome {
             ConnectWorkerOutcome::Success {
                 profile,
+                password,
                 connect_latency,
+                server,
                 databases,
                 warning,
             } => {
                 self.reconnect_attempts = 0;
-                self.apply_connected_profile(profile, connect_latency, databases, warning);
                 self.error_panel = None;
+                if !self.apply_connected_profile(
+                    profile,
+                    &password,
+                    connect_latency,
+                    databases,
+                    warning,
+                ) {
+                    self.pending_retry_query = None;
+                    return;
+                }
+                self.apply_server_capabilities(server);
                 if intent == ConnectIntent::AutoReconnect {
                     if let Some(sql) = self.pending_retry_query.take() {
                         self.start_query(sql);
@@ -136,8 +250,11 @@ impl TuiApp {
                 }
             }
             ConnectWorkerOutcome::Failure(error) => {
+                // A refused login or unknown database will not fix itself, so only
+                // connection-level failures use up the remaining reconnect attempts.
                 if intent == ConnectIntent::AutoReconnect
-                    && self.reconnect_attempts < AUTO_RECONNECT_LIMIT
+                    && error.class() != ErrorClass::Permanent
+                    && self.reconnect_attempts < self.retry_policy().max_reconnect_attempts
                 {
                     if let Some(profile) = self
                         .active_connection_profile
@@ -151,31 +268,74 @@ impl TuiApp {
                 }
 
                 self.pending_retry_query = None;
-                self.reconnect_attempts = 0;
+                self.deferred_transaction_exit = None;
+                // A failed auto-reconnect leaves its budget spent, so keepalive does not start
+                // another round until the user connects or runs a query.
+                self.reconnect_attempts = if intent == ConnectIntent::AutoReconnect {
+                    self.retry_policy().max_reconnect_attempts
+                } else {
+                    0
+                };
                 self.status_line = format!("Connect failed: {error}");
                 let summary = if intent == ConnectIntent::AutoReconnect {
                     "Auto-reconnect attempts were exhausted".to_string()
                 } else {
                     "Connection attempt failed".to_string()
                 };
-                self.open_error_panel(ErrorKind::Connection, "Connection Error", summary, error);
+                self.open_error_panel(
+                    ErrorKind::Connection,
+                    "Connection Error",
+                    summary,
+                    error.message,
+                );
             }
         }
     }
 
+    // The query pool learns the server too, so time limits use the flavor's own syntax.
+    pub(super) fn apply_server_capabilities(&mut self, server: Option<ServerCapabilities>) {
+        self.data_backend = self
+            .data_backend
+            .take()
+            .map(|backend| backend.with_server_capabilities(server.clone()));
+        self.server_capabilities = server;
+    }
+
     pub(super) fn apply_connected_profile(
         &mut self,
         profile: ConnectionProfile,
+        password: &ResolvedPassword,
         connect_latency: Duration,
         databases: Vec<String>,
         warning: Option<String>,
-    ) {
-        self.last_connection_latency = Some(connect_latency);
-
+    ) -> bool {
         // Keep query execution and schema cache on separate pools so runtime-bound
         // schema refreshes cannot invalidate the active query pool.
-        let data_backend = MysqlDataBackend::from_profile(&profile);
-        let schema_backend = MysqlDataBackend::from_profile(&profile);
+        let backends = MysqlDataBackend::from_resolved(&profile, password).and_then(|data_backend| {
+            MysqlDataBackend::from_resolved(&profile, password)
+                .map(|schema_backend| (data_backend, schema_backend))
+        });
+        let (data_backend, schema_backend) = match backends {
+            Ok(backends) => backends,
+            Err(error) => {
+                self.status_line = format!("Connect failed: {error}");
+                self.open_error_panel(
+                    ErrorKind::Connection,
+                    "Connection Error",
+                    "Connection attempt failed",
+                    error.to_string(),
+                );
+                return false;
+            }
+        };
+
+        self.prepare_workspace_for(&profile.name);
+        self.last_connection_latency = Some(connect_latency);
+        self.latency_history = LatencyHistory::default();
+        self.latency_history.push(connect_latency);
+        self.last_keepalive_at = Some(Instant::now());
+        self.connection_stale = false;
+        let transaction_lost = self.discard_open_transaction("connection was re-established");
         let schema_cache = SchemaCacheService::new(schema_backend, Duration::from_secs(10));
 
         let mut active_database = profile.database.clone();
@@ -184,6 +344,7 @@ impl TuiApp {
         }
 
         self.active_connection_profile = Some(profile.clone());
+        self.enforce_environment_safe_mode();
         self.last_connect_profile = Some(profile.clone());
         self.wizard_form = wizard_form_from_profile(&profile);
         self.data_backend = Some(data_backend);
@@ -226,6 +387,9 @@ impl TuiApp {
         if let Some(warning) = warning {
             notes.push(warning);
         }
+        if transaction_lost {
+            notes.push("the open transaction was rolled back".to_string());
+        }
 
         match self.profile_store.as_mut() {
             Some(store) => {
@@ -262,5 +426,6 @@ impl TuiApp {
             status.push(')');
         }
         self.status_line = status;
+        true
     }
 }
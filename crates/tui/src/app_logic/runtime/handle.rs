@@ -12,11 +12,21 @@ impl TuiApp {
             return;
         }
 
+        if self.transaction_prompt.is_some() {
+            self.handle_transaction_prompt_input(msg);
+            return;
+        }
+
         if self.error_panel.is_some() {
             self.handle_error_panel_input(msg);
             return;
         }
 
+        if self.parameter_form.is_some() {
+            self.handle_parameter_form_input(msg);
+            return;
+        }
+
         if self.results_search_mode {
             match msg {
                 Msg::InputChar(ch) => {
@@ -40,6 +50,7 @@ impl TuiApp {
                 }
                 Msg::TogglePalette => {
                     self.results_search_mode = false;
+                    self.results_search_scan = None;
                     self.status_line = "Results search canceled".to_string();
                     return;
                 }
@@ -48,9 +59,7 @@ impl TuiApp {
         }
 
         match msg {
-            Msg::Quit => {
-                self.should_quit = true;
-            }
+            Msg::Quit => self.request_quit(),
             Msg::GoConnectionWizard => {
                 self.set_active_pane(Pane::ConnectionWizard);
                 if self.wizard_form.editing {
@@ -62,6 +71,7 @@ impl TuiApp {
             Msg::GoProfileBookmarkManager => {
                 self.open_profile_bookmark_manager();
             }
+            Msg::NextConnection => self.next_connection(),
             Msg::ToggleHelp => self.show_help = !self.show_help,
             Msg::NextPane => {
                 if self.pane == Pane::ConnectionWizard && self.wizard_form.editing {
@@ -92,6 +102,10 @@ impl TuiApp {
                     self.cancel_manager_rename();
                     return;
                 }
+                if self.typing_confirmation() {
+                    self.cancel_typed_confirmation();
+                    return;
+                }
                 self.show_palette = !self.show_palette;
                 if self.show_palette {
                     self.palette_query.clear();
@@ -113,6 +127,15 @@ impl TuiApp {
             }
             Msg::ToggleSafeMode => {
                 let next_enabled = !self.safe_mode_guard.is_enabled();
+                if let Some(profile) = self
+                    .active_connection_profile
+                    .as_ref()
+                    .filter(|profile| !next_enabled && profile.requires_safe_mode())
+                {
+                    self.status_line =
+                        format!("Safe mode stays on for prod profile `{}`", profile.name);
+                    return;
+                }
                 self.safe_mode_guard.set_enabled(next_enabled);
                 self.pending_confirmation = None;
                 self.status_line = if next_enabled {
@@ -127,7 +150,7 @@ impl TuiApp {
             Msg::CancelQuery => {
                 if !self.query_running {
                     if self.exit_confirmation {
-                        self.should_quit = true;
+                        self.request_quit();
                     } else {
                         self.exit_confirmation = true;
                         self.status_line = "No active query. Exit myr? Press Ctrl+C again to confirm, F10 to exit now, Esc to cancel.".to_string();
@@ -182,9 +205,7 @@ impl TuiApp {
                 self.set_active_pane(Pane::ConnectionWizard);
                 self.status_line = "Returned to Connection Wizard".to_string();
             }
-            Msg::Quit => {
-                self.should_quit = true;
-            }
+            Msg::Quit => self.request_quit(),
             _ => {
                 self.status_line =
                     "Error panel active: 1 primary action | F5 reconnect | F6 wizard | Esc close"
@@ -198,6 +219,8 @@ impl TuiApp {
         self.pane_flash_ticks = self.pane_flash_ticks.saturating_sub(1);
         self.poll_connect_result();
         self.poll_query_result();
+        self.poll_keepalive_result();
+        self.schedule_keepalive();
 
         if self.query_running && self.data_backend.is_none() {
             if self.query_ticks_remaining == 0 {
@@ -208,14 +231,18 @@ impl TuiApp {
                 self.inflight_query_sql = None;
                 self.last_failed_query = None;
                 self.finalize_pagination_after_query();
-                self.append_audit_event(
+                let boundary = self.apply_transaction_boundary(&audit_sql);
+                let mut record = self.audit_record(
                     AuditOutcome::Succeeded,
                     &audit_sql,
                     Some(self.results.len() as u64),
                     None,
                     None,
                 );
+                record.transaction = boundary;
+                self.append_audit_record(&record);
                 self.status_line = "Query completed".to_string();
+                self.finish_transaction_exit(boundary);
             } else {
                 self.query_ticks_remaining = self.query_ticks_remaining.saturating_sub(1);
             }
@@ -236,6 +263,17 @@ impl TuiApp {
                 format!("Running query... {spinner}")
             };
         }
+
+        self.continue_results_search();
+        // Spilled rows are read back while rendering, so a failed read is picked up here
+        // and reported once instead of leaving blank rows unexplained.
+        let spill_error = self.results.spill_error();
+        if spill_error != self.reported_spill_error {
+            if let Some(error) = &spill_error {
+                self.status_line = format!("Warning: {error}");
+            }
+            self.reported_spill_error = spill_error;
+        }
     }
 
     pub(super) fn record_render(&mut self, elapsed: Duration) {
@@ -0,0 +1,145 @@
+fn keepalive_due(
+    profile: Option<&ConnectionProfile>,
+    last_keepalive_at: Option<Instant>,
+    now: Instant,
+) -> Option<Duration> {
+    let profile = profile?;
+    let interval = profile.keepalive_interval()?;
+    last_keepalive_at
+        .is_none_or(|last| now.saturating_duration_since(last) >= interval)
+        .then(|| profile.connect_timeout())
+}
+
+impl TuiApp {
+    // Pings every open connection on its own interval. Running queries and connects own
+    // the active session, so keepalive waits for them instead of competing for it.
+    fn schedule_keepalive(&mut self) {
+        if self.keepalive_rx.is_some() || self.query_running || self.connect_requested {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut targets = Vec::new();
+        if let (Some(timeout), Some(backend), Some(profile_name)) = (
+            keepalive_due(
+                self.active_connection_profile.as_ref(),
+                self.last_keepalive_at,
+                now,
+            ),
+            self.data_backend.as_ref(),
+            self.connected_profile.as_ref(),
+        ) {
+            targets.push((profile_name.clone(), backend.clone(), timeout));
+            self.last_keepalive_at = Some(now);
+        }
+        for workspace in &mut self.parked_workspaces {
+            if let (Some(timeout), Some(backend), Some(profile_name)) = (
+                keepalive_due(
+                    workspace.active_connection_profile.as_ref(),
+                    workspace.last_keepalive_at,
+                    now,
+                ),
+                workspace.data_backend.as_ref(),
+                workspace.connected_profile.as_ref(),
+            ) {
+                targets.push((profile_name.clone(), backend.clone(), timeout));
+                workspace.last_keepalive_at = Some(now);
+            }
+        }
+        if targets.is_empty() {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.keepalive_rx = Some(rx);
+        let _keepalive_worker = thread::spawn(move || {
+            let _ = tx.send(run_keepalive_worker(targets));
+        });
+    }
+
+    pub(super) fn poll_keepalive_result(&mut self) {
+        let probes = match self.keepalive_rx.as_ref().map(Receiver::try_recv) {
+            Some(Ok(probes)) => probes,
+            Some(Err(TryRecvError::Disconnected)) => {
+                self.keepalive_rx = None;
+                return;
+            }
+            Some(Err(TryRecvError::Empty)) | None => return,
+        };
+        self.keepalive_rx = None;
+
+        for probe in probes {
+            if self.connected_profile.as_deref() == Some(probe.profile_name.as_str()) {
+                self.apply_active_keepalive(probe.outcome);
+            } else if let Some(workspace) = self
+                .parked_workspaces
+                .iter_mut()
+                .find(|workspace| workspace.connected_profile.as_ref() == Some(&probe.profile_name))
+            {
+                match probe.outcome {
+                    Ok(latency) => {
+                        workspace.latency_history.push(latency);
+                        workspace.last_connection_latency = Some(latency);
+                        workspace.connection_stale = false;
+                    }
+                    Err(_) => workspace.connection_stale = true,
+                }
+            }
+        }
+    }
+
+    fn apply_active_keepalive(&mut self, outcome: Result<Duration, String>) {
+        match outcome {
+            Ok(latency) => {
+                self.latency_history.push(latency);
+                self.last_connection_latency = Some(latency);
+                self.connection_stale = false;
+            }
+            Err(error) => {
+                self.connection_stale = true;
+                self.status_line = format!("Keepalive failed: {error}");
+                if !self.transaction_open {
+                    self.reconnect_stale_connection();
+                    return;
+                }
+                // The ping may only have timed out because the session is busy, and a
+                // reconnect would discard the open transaction, so the user decides.
+                if let (None, Some(profile)) = (
+                    self.transaction_prompt.as_ref(),
+                    self.active_connection_profile.clone(),
+                ) {
+                    self.prompt_transaction_exit(TransactionExitIntent::Connect(profile));
+                    self.status_line = format!(
+                        "Keepalive failed: {error} | Transaction open: C commit and reconnect | R roll back and reconnect | Esc keep transaction open"
+                    );
+                }
+            }
+        }
+    }
+
+    // Reconnects before the next query would have found the dead session. An open
+    // transaction lives on that session, so it is never replaced without asking.
+    fn reconnect_stale_connection(&mut self) -> bool {
+        if !self.connection_stale
+            || self.query_running
+            || self.connect_requested
+            || self.transaction_open
+        {
+            return false;
+        }
+        let Some(profile) = self.active_connection_profile.clone() else {
+            return false;
+        };
+        let max_attempts = profile.retry_policy().max_reconnect_attempts;
+        if self.reconnect_attempts >= max_attempts {
+            self.status_line.push_str(&format!(
+                " | Auto-reconnect gave up after {max_attempts} attempts; reconnect manually"
+            ));
+            return false;
+        }
+
+        self.reconnect_attempts = self.reconnect_attempts.saturating_add(1);
+        self.start_connect_with_profile(profile, ConnectIntent::AutoReconnect);
+        true
+    }
+}
@@ -20,6 +20,18 @@ impl TuiApp {
             }
             Pane::QueryEditor => {
                 if let Some((token, sql)) = self.pending_confirmation.take() {
+                    if let Some(phrase) = self.confirmation_phrase.clone() {
+                        if self.confirmation_input != phrase {
+                            self.pending_confirmation = Some((token, sql));
+                            self.status_line = format!(
+                                "Typed `{}` does not match `{phrase}`; type the profile name or press Esc to cancel",
+                                self.confirmation_input
+                            );
+                            return;
+                        }
+                    }
+                    self.confirmation_phrase = None;
+                    self.confirmation_input.clear();
                     match self.safe_mode_guard.confirm(&token, &sql) {
                         Ok(()) => {
                             self.start_query(sql);
@@ -35,19 +47,25 @@ impl TuiApp {
             Pane::ProfileBookmarks => {
                 self.open_manager_selection();
             }
-            Pane::SchemaExplorer | Pane::Results => {
+            Pane::Results => {
+                if !self.show_full_cell_value() {
+                    self.status_line = "Nothing to submit in this view".to_string();
+                }
+            }
+            Pane::SchemaExplorer => {
                 self.status_line = "Nothing to submit in this view".to_string();
             }
         }
     }
 
     pub(super) fn poll_query_result(&mut self) {
+        self.drain_streamed_rows();
         let outcome = match self.query_result_rx.as_ref() {
             Some(receiver) => match receiver.try_recv() {
                 Ok(outcome) => Some(outcome),
                 Err(TryRecvError::Empty) => None,
                 Err(TryRecvError::Disconnected) => Some(QueryWorkerOutcome::Failure(
-                    "query worker disconnected".to_string(),
+                    WorkerError::new("query worker disconnected", None),
                 )),
             },
             None => None,
@@ -57,7 +75,12 @@ impl TuiApp {
             return;
         };
 
+        let streamed_live = self
+            .stream_progress
+            .take()
+            .is_some_and(|progress| progress.rows_received > 0);
         self.query_result_rx = None;
+        self.query_stream_rx = None;
         self.query_cancellation = None;
         self.query_running = false;
         let audit_sql = self
@@ -72,36 +95,131 @@ impl TuiApp {
                 rows_streamed,
                 was_cancelled,
                 elapsed,
+                columns,
+                status,
+                additional_result_sets,
+                server_cancellation,
+                session_state_lost,
             } => {
+                let is_statement = columns.is_empty();
+                let spill_error = results.spill_error();
                 self.results = results;
+                self.parked_primary_results = None;
+                if columns.is_empty() {
+                    self.result_column_metadata.clear();
+                } else {
+                    self.result_columns = columns.iter().map(|column| column.name.clone()).collect();
+                    self.result_column_metadata = columns;
+                }
+                self.result_sets.clear();
+                self.active_result_set = 0;
+                if !additional_result_sets.is_empty() {
+                    self.result_sets.push(ResultSetCapture {
+                        columns: self.result_column_metadata.clone(),
+                        rows: ResultsRingBuffer::new(self.results.capacity()),
+                        rows_streamed,
+                        status: status.clone(),
+                    });
+                    self.result_sets.extend(additional_result_sets);
+                }
                 self.has_results = !self.results.is_empty();
-                self.results_cursor = 0;
-                self.results_column_cursor = 0;
+                if streamed_live {
+                    // Rows were already on screen, so keep the user's place in them.
+                    self.results_cursor = self
+                        .results_cursor
+                        .min(self.results.len().saturating_sub(1));
+                } else {
+                    self.results_cursor = 0;
+                    self.results_column_cursor = 0;
+                    self.reset_results_column_focus();
+                }
                 self.results_search_mode = false;
                 self.results_search_query.clear();
-                self.reset_results_column_focus();
                 self.query_retry_attempts = 0;
                 self.reconnect_attempts = 0;
                 self.inflight_query_sql = None;
                 self.last_failed_query = None;
                 self.finalize_pagination_after_query();
+                let transaction_lost =
+                    session_state_lost && self.discard_open_transaction("pinned session reconnected");
+                let boundary = if was_cancelled {
+                    None
+                } else {
+                    self.apply_transaction_boundary(&audit_sql)
+                };
                 let audit_outcome = if was_cancelled {
                     AuditOutcome::Cancelled
                 } else {
                     AuditOutcome::Succeeded
                 };
-                self.append_audit_event(
+                let kill_error = match &server_cancellation {
+                    Some(failed @ ServerCancellation::KillFailed { .. }) => Some(failed.describe()),
+                    _ => None,
+                };
+                let mut record = self.audit_record(
                     audit_outcome,
                     &audit_sql,
                     Some(rows_streamed),
                     Some(elapsed),
-                    None,
+                    kill_error.as_deref(),
                 );
+                if !was_cancelled {
+                    record.affected_rows = Some(status.affected_rows);
+                    record.last_insert_id = status.last_insert_id;
+                    record.warning_count = Some(status.warning_count);
+                    record.info = status.info.clone();
+                    record.transaction = boundary;
+                }
+                self.append_audit_record(&record);
                 self.status_line = if was_cancelled {
-                    format!("Query cancelled after {rows_streamed} rows in {elapsed:.1?}")
+                    let server_note = server_cancellation
+                        .as_ref()
+                        .map(|outcome| format!(" ({})", outcome.describe()))
+                        .unwrap_or_default();
+                    format!("Query cancelled after {rows_streamed} rows in {elapsed:.1?}{server_note}")
+                } else if is_statement {
+                    format!("Query OK, {} in {elapsed:.1?}", status.outcome())
+                } else if status.warning_count > 0 {
+                    format!(
+                        "Query returned {rows_streamed} rows in {elapsed:.1?} ({} warning{})",
+                        status.warning_count,
+                        if status.warning_count == 1 { "" } else { "s" }
+                    )
                 } else {
                     format!("Query returned {rows_streamed} rows in {elapsed:.1?}")
                 };
+                if self.result_sets.len() > 1 {
+                    self.status_line
+                        .push_str(&format!(" [result set 1 of {}]", self.result_sets.len()));
+                }
+                if session_state_lost {
+                    self.status_line.push_str(
+                        " | Warning: pinned session reconnected, session variables/USE/temporary tables were reset",
+                    );
+                }
+                if transaction_lost {
+                    self.status_line
+                        .push_str(" | Warning: the open transaction was rolled back by the reconnect");
+                }
+                if let Some(error) = spill_error {
+                    self.status_line.push_str(&format!(" | Warning: {error}"));
+                    self.reported_spill_error = Some(error);
+                }
+                self.finish_transaction_exit(boundary);
+                if !status.warnings.is_empty() {
+                    let detail = status
+                        .warnings
+                        .iter()
+                        .map(|warning| format!("{} {}: {}", warning.level, warning.code, warning.message))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    self.open_error_panel(
+                        ErrorKind::Warnings,
+                        "Query Warnings",
+                        format!("{} warning(s) reported by the server", status.warnings.len()),
+                        detail,
+                    );
+                }
             }
             QueryWorkerOutcome::Failure(error) => {
                 self.pending_page_transition = None;
@@ -113,26 +231,43 @@ impl TuiApp {
                 } else {
                     Some(audit_sql.clone())
                 };
-                let transient = is_transient_query_error(&error);
-                let connection_loss = is_connection_lost_error(&error);
-                self.append_audit_event(AuditOutcome::Failed, &audit_sql, None, None, Some(&error));
+                let class = error.class();
+                let policy = self.retry_policy();
+                self.append_audit_event(
+                    AuditOutcome::Failed,
+                    &audit_sql,
+                    None,
+                    None,
+                    Some(&error.message),
+                );
 
-                if transient && !self.cancel_requested && self.query_retry_attempts < QUERY_RETRY_LIMIT {
-                    if let Some(sql) = query_sql.clone() {
-                        self.query_retry_attempts = self.query_retry_attempts.saturating_add(1);
+                // Re-running a statement outside the transaction it was issued in would
+                // silently autocommit it, so retries are off while a transaction is open.
+                let may_retry = !self.transaction_open && !self.cancel_requested;
+                if class == ErrorClass::Transient && may_retry {
+                    let attempt = self.query_retry_attempts.saturating_add(1);
+                    if let (Some(sql), Some(delay)) =
+                        (query_sql.clone(), policy.retry_delay(class, attempt))
+                    {
+                        self.query_retry_attempts = attempt;
+                        self.start_query_internal(sql, Some(delay));
                         self.status_line = format!(
-                            "Transient query failure; retrying ({}/{})...",
-                            self.query_retry_attempts, QUERY_RETRY_LIMIT
+                            "Transient query failure (error {}); retrying in {delay:.1?} ({attempt}/{})...",
+                            error.code.unwrap_or_default(),
+                            policy.max_query_retries
                         );
-                        self.start_query_internal(sql, true);
                         self.cancel_requested = false;
                         return;
                     }
                 }
 
-                if connection_loss
-                    && !self.cancel_requested
-                    && self.reconnect_attempts < AUTO_RECONNECT_LIMIT
+                let rerun_allowed = query_sql
+                    .as_deref()
+                    .is_some_and(|sql| class.allows_rerun(sql));
+                if class == ErrorClass::ConnectionLost
+                    && may_retry
+                    && rerun_allowed
+                    && self.reconnect_attempts < policy.max_reconnect_attempts
                 {
                     if let Some(profile) = self
                         .active_connection_profile
@@ -144,7 +279,7 @@ impl TuiApp {
                         self.start_connect_with_profile(profile, ConnectIntent::AutoReconnect);
                         self.status_line = format!(
                             "Connection dropped; reconnecting ({}/{})...",
-                            self.reconnect_attempts, AUTO_RECONNECT_LIMIT
+                            self.reconnect_attempts, policy.max_reconnect_attempts
                         );
                         self.cancel_requested = false;
                         return;
@@ -157,15 +292,56 @@ impl TuiApp {
                 self.last_failed_query = query_sql;
                 self.pending_retry_query = None;
                 self.status_line = format!("Query failed: {error}");
+                if class == ErrorClass::ConnectionLost && may_retry && !rerun_allowed {
+                    self.status_line.push_str(
+                        " | Not retried: the server may already have applied the statement",
+                    );
+                }
+                if class == ErrorClass::ConnectionLost
+                    && self.discard_open_transaction("connection lost")
+                {
+                    self.status_line
+                        .push_str(" | Warning: the open transaction was rolled back by the server");
+                }
+                self.finish_transaction_exit(None);
                 self.open_error_panel(
                     ErrorKind::Query,
                     "Query Error",
                     "Query execution failed".to_string(),
-                    error,
+                    error.message,
                 );
             }
         }
 
         self.cancel_requested = false;
     }
+
+    fn drain_streamed_rows(&mut self) {
+        let Some(receiver) = self.query_stream_rx.as_ref() else {
+            return;
+        };
+        let batches = receiver.try_iter().collect::<Vec<_>>();
+        for batch in batches {
+            let Some(progress) = self.stream_progress else {
+                return;
+            };
+            if progress.rows_received == 0 && !batch.columns.is_empty() {
+                self.result_columns = batch.columns.iter().map(|column| column.name.clone()).collect();
+                self.result_column_metadata = batch.columns;
+                self.result_sets.clear();
+                self.active_result_set = 0;
+                self.results_cursor = 0;
+                self.results_column_cursor = 0;
+                self.reset_results_column_focus();
+            }
+            self.stream_progress = Some(StreamProgress {
+                rows_received: progress.rows_received + batch.rows.len() as u64,
+                ..progress
+            });
+            for row in batch.rows {
+                self.results.push(row);
+            }
+        }
+        self.has_results = !self.results.is_empty();
+    }
 }
@@ -0,0 +1,173 @@
+impl TuiApp {
+    pub(super) fn open_connection_count(&self) -> usize {
+        self.parked_workspaces.len() + usize::from(self.connected_profile.is_some())
+    }
+
+    pub(super) fn connection_tabs(&self) -> Vec<ConnectionTab<'_>> {
+        let mut tabs = self
+            .parked_workspaces
+            .iter()
+            .map(|workspace| workspace.tab(false))
+            .collect::<Vec<_>>();
+        if let Some(profile_name) = self.connected_profile.as_deref() {
+            tabs.insert(
+                self.active_workspace_slot.min(tabs.len()),
+                ConnectionTab {
+                    profile_name,
+                    environment: self
+                        .active_connection_profile
+                        .as_ref()
+                        .and_then(environment_badge),
+                    read_only: self.current_profile_read_only(),
+                    transaction_open: self.transaction_open,
+                    active: true,
+                },
+            );
+        }
+        tabs
+    }
+
+    fn connection_tab_index(&self, profile_name: &str) -> Option<usize> {
+        self.connection_tabs()
+            .iter()
+            .position(|tab| tab.profile_name == profile_name)
+    }
+
+    // Query and connect workers deliver into whichever workspace is active, so tabs
+    // stay put until they finish.
+    fn workspace_switch_blocker(&self) -> Option<&'static str> {
+        if self.query_running {
+            Some("Finish or cancel the running query before switching connections")
+        } else if self.connect_requested {
+            Some("Wait for the pending connection before switching connections")
+        } else {
+            None
+        }
+    }
+
+    pub(super) fn next_connection(&mut self) {
+        let count = self.open_connection_count();
+        if count < 2 {
+            self.status_line = "No other connection is open".to_string();
+            return;
+        }
+        if let Some(blocker) = self.workspace_switch_blocker() {
+            self.status_line = blocker.to_string();
+            return;
+        }
+
+        self.activate_workspace((self.active_workspace_slot + 1) % count);
+        self.status_line = format!(
+            "Switched to connection `{}`",
+            self.connected_profile.as_deref().unwrap_or("-")
+        );
+        self.reconnect_stale_connection();
+    }
+
+    fn activate_workspace(&mut self, index: usize) {
+        if index == self.active_workspace_slot || index > self.parked_workspaces.len() {
+            return;
+        }
+
+        let mut active = ConnectionWorkspace::default();
+        active.swap_with(self);
+        self.parked_workspaces
+            .insert(self.active_workspace_slot, active);
+        let mut target = self.parked_workspaces.remove(index);
+        target.swap_with(self);
+        self.active_workspace_slot = index;
+        self.reset_workspace_transients();
+    }
+
+    // A different profile gets its own tab; reconnecting an open profile reuses its tab.
+    fn prepare_workspace_for(&mut self, profile_name: &str) {
+        if self
+            .connected_profile
+            .as_deref()
+            .is_none_or(|active| active == profile_name)
+        {
+            return;
+        }
+        if let Some(index) = self.connection_tab_index(profile_name) {
+            self.activate_workspace(index);
+            return;
+        }
+
+        let mut active = ConnectionWorkspace::default();
+        active.swap_with(self);
+        self.parked_workspaces
+            .insert(self.active_workspace_slot, active);
+        self.active_workspace_slot = self.parked_workspaces.len();
+        self.reset_workspace_transients();
+    }
+
+    pub(super) fn close_active_connection(&mut self) {
+        let Some(closed) = self.connected_profile.clone() else {
+            self.status_line = "No connection to close".to_string();
+            return;
+        };
+        if let Some(blocker) = self.workspace_switch_blocker() {
+            self.status_line = blocker.to_string();
+            return;
+        }
+        if self.transaction_open {
+            self.prompt_transaction_exit(TransactionExitIntent::CloseConnection);
+            return;
+        }
+
+        let mut closed_workspace = if self.parked_workspaces.is_empty() {
+            ConnectionWorkspace::default()
+        } else {
+            let index = self
+                .active_workspace_slot
+                .min(self.parked_workspaces.len() - 1);
+            self.active_workspace_slot = index;
+            self.parked_workspaces.remove(index)
+        };
+        closed_workspace.swap_with(self);
+        drop(closed_workspace);
+        self.reset_workspace_transients();
+
+        self.status_line = match self.connected_profile.as_deref() {
+            Some(next) => format!("Closed connection `{closed}`; switched to `{next}`"),
+            None => {
+                self.active_workspace_slot = 0;
+                self.set_active_pane(Pane::ConnectionWizard);
+                format!("Closed connection `{closed}`")
+            }
+        };
+    }
+
+    // Quitting drops every connection, so a transaction left open in another tab gets
+    // the same commit/rollback prompt as one in the active tab.
+    fn focus_parked_transaction(&mut self) -> bool {
+        let Some(index) = self
+            .connection_tabs()
+            .iter()
+            .position(|tab| tab.transaction_open && !tab.active)
+        else {
+            return false;
+        };
+        if self.transaction_open || self.workspace_switch_blocker().is_some() {
+            return false;
+        }
+
+        self.activate_workspace(index);
+        true
+    }
+
+    fn reset_workspace_transients(&mut self) {
+        self.pending_confirmation = None;
+        self.pending_page_transition = None;
+        self.pending_retry_query = None;
+        self.results_search_mode = false;
+        self.results_search_scan = None;
+        self.reconnect_attempts = 0;
+        self.query_retry_attempts = 0;
+        self.enforce_environment_safe_mode();
+        if let Some(profile) = self.active_connection_profile.clone() {
+            self.wizard_form = wizard_form_from_profile(&profile);
+            self.last_connect_profile = Some(profile);
+        }
+    }
+}
@@ -1,6 +1,9 @@
-use std::io::{self, Stdout};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Stdout, Write};
 use std::path::PathBuf;
-use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::OnceLock;
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -14,22 +17,35 @@ use crossterm::terminal::{
 };
 use myr_adapters::export::{
     export_rows_to_csv, export_rows_to_csv_with_options, export_rows_to_json,
-    export_rows_to_json_with_options, ExportCompression, JsonExportFormat,
+    export_rows_to_json_with_options, ExportCompression, JsonExportFormat, ValueEncoding,
 };
-use myr_adapters::mysql::{MysqlConnectionBackend, MysqlDataBackend};
+use myr_adapters::mysql::{MysqlConnectionBackend, MysqlDataBackend, ResolvedPassword};
 use myr_core::actions_engine::{
     ActionContext, ActionId, ActionInvocation, ActionsEngine, AppView, SchemaSelection,
 };
 use myr_core::audit_trail::{unix_timestamp_millis, AuditOutcome, AuditRecord, FileAuditTrail};
 use myr_core::bookmarks::{FileBookmarksStore, SavedBookmark};
-use myr_core::connection_manager::ConnectionManager;
-use myr_core::profiles::{ConnectionProfile, FileProfilesStore, PasswordSource, TlsMode};
-use myr_core::query_runner::{CancellationToken, QueryRow, QueryRunner};
+use myr_core::connection_manager::{ConnectionManager, LatencyHistory};
+use myr_core::profiles::{
+    import_option_file, ConnectionProfile, FileProfilesStore, ImportConflict, PasswordSource,
+    ProfileEnvironment, SshKnownHosts, TlsMode, DEFAULT_QUERY_TIMEOUT_SECS,
+};
+use myr_core::query_params::ParameterizedQuery;
+use myr_core::query_runner::{
+    run_with_deadline, CancellationToken, QueryRow, QueryRunner, QueryValue, ResultColumn,
+    ResultSetCapture, ServerCancellation, StatementStatus,
+};
 use myr_core::results_buffer::ResultsRingBuffer;
-use myr_core::safe_mode::{assess_sql_safety, ConfirmationToken, GuardDecision, SafeModeGuard};
+use myr_core::results_store::{ResultsStore, SpillingResultsStore};
+use myr_core::retry_policy::{ErrorClass, RetryPolicy, CR_CONN_HOST_ERROR};
+use myr_core::safe_mode::{
+    assess_sql_safety, transaction_boundary, ConfirmationToken, GuardDecision, SafeModeGuard,
+    TransactionBoundary,
+};
 use myr_core::schema_cache::{
     ColumnSchema, RelationshipDirection, SchemaCacheService, TableRelationship,
 };
+use myr_core::server_capabilities::ServerCapabilities;
 use myr_core::sql_generator::{
     keyset_first_page_sql, keyset_page_sql, offset_page_sql, PaginationDirection, SqlTarget,
 };
@@ -45,12 +61,16 @@ const TICK_RATE: Duration = Duration::from_millis(120);
 const QUERY_DURATION_TICKS: u8 = 10;
 const FOOTER_ACTIONS_LIMIT: usize = 7;
 const RESULT_BUFFER_CAPACITY: usize = 2_000;
+const RESULTS_SCAN_CHUNK: usize = 500;
+const RESULTS_SEARCH_ROWS_PER_TICK: usize = 5_000;
+const RESULT_MEMORY_BUDGET_BYTES: usize = 64 * 1024 * 1024;
+const RESULT_CELL_PREVIEW_BYTES: usize = 4 * 1024;
+const FULL_VALUE_PANEL_MAX_CHARS: usize = 64 * 1024;
+const STREAM_BATCH_ROWS: usize = 250;
+const STREAM_BATCH_INTERVAL: Duration = Duration::from_millis(100);
 const PREVIEW_PAGE_SIZE: usize = 200;
-const CONNECT_TIMEOUT: Duration = Duration::from_secs(8);
-const QUERY_TIMEOUT: Duration = Duration::from_secs(20);
-const QUERY_RETRY_LIMIT: u8 = 1;
-const AUTO_RECONNECT_LIMIT: u8 = 2;
 const PANE_FLASH_DURATION_TICKS: u8 = 8;
+const LATENCY_SPARKLINE_WIDTH: usize = 12;
 const AUDIT_SQL_MAX_CHARS: usize = 1_000;
 const AUDIT_ERROR_MAX_CHARS: usize = 400;
 const BOOKMARK_NAME_MAX_CHARS: usize = 64;
@@ -144,6 +164,12 @@ fn run_loop(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<(), Tui
             last_tick = Instant::now();
         }
 
+        if let Some(text) = app.clipboard_request.take() {
+            let backend = terminal.backend_mut();
+            backend.write_all(osc52_copy_sequence(&text).as_bytes())?;
+            backend.flush()?;
+        }
+
         if app.should_quit {
             break;
         }
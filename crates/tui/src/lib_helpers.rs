@@ -60,6 +60,8 @@ pub(crate) fn parse_password_source(value: &str) -> Option<PasswordSource> {
     match value.trim().to_ascii_lowercase().as_str() {
         "env" | "env_var" | "envvar" | "environment" | "" => Some(PasswordSource::EnvVar),
         "keyring" | "secure_store" | "secure-store" => Some(PasswordSource::Keyring),
+        "command" | "cmd" => Some(PasswordSource::Command),
+        "file" => Some(PasswordSource::File),
         _ => None,
     }
 }
@@ -76,6 +78,86 @@ pub(crate) fn parse_tls_mode(value: &str) -> Option<TlsMode> {
     }
 }
 
+pub(crate) fn parse_ssh_known_hosts(value: &str) -> Option<SshKnownHosts> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "strict" | "yes" | "" => Some(SshKnownHosts::Strict),
+        "accept_new" | "accept-new" | "tofu" => Some(SshKnownHosts::AcceptNew),
+        "off" | "no" | "insecure" => Some(SshKnownHosts::Off),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_ssh_agent_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "y" | "on" | "" => Some(true),
+        "0" | "false" | "no" | "n" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+pub(crate) struct SshJump {
+    pub(crate) host: String,
+    pub(crate) port: Option<u16>,
+    pub(crate) user: Option<String>,
+}
+
+// Accepts the `[user@]host[:port]` shape ssh users already type, with `[v6]:port` brackets.
+pub(crate) fn parse_ssh_jump(value: &str) -> Option<Option<SshJump>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Some(None);
+    }
+
+    let (user, address) = match value.rsplit_once('@') {
+        Some((user, address)) if !user.is_empty() => (Some(user.to_string()), address),
+        Some(_) => return None,
+        None => (None, value),
+    };
+    let (host, port) = if let Some(bracketed) = address.strip_prefix('[') {
+        let (host, rest) = bracketed.split_once(']')?;
+        let port = match rest.strip_prefix(':') {
+            Some(port) => Some(port.parse::<u16>().ok()?),
+            None if rest.is_empty() => None,
+            None => return None,
+        };
+        (host, port)
+    } else {
+        match address.split_once(':') {
+            Some((host, port)) => (host, Some(port.parse::<u16>().ok()?)),
+            None => (address, None),
+        }
+    };
+    if host.is_empty() || host.contains(char::is_whitespace) {
+        return None;
+    }
+
+    Some(Some(SshJump {
+        host: host.to_string(),
+        port,
+        user,
+    }))
+}
+
+pub(crate) fn format_ssh_jump(profile: &ConnectionProfile) -> String {
+    let Some(host) = profile.ssh_jump_host() else {
+        return String::new();
+    };
+    let mut jump = String::new();
+    if let Some(user) = profile.ssh_user.as_deref().filter(|user| !user.is_empty()) {
+        jump.push_str(user);
+        jump.push('@');
+    }
+    if host.contains(':') {
+        jump.push_str(&format!("[{host}]"));
+    } else {
+        jump.push_str(host);
+    }
+    if let Some(port) = profile.ssh_port {
+        jump.push_str(&format!(":{port}"));
+    }
+    jump
+}
+
 pub(crate) fn parse_read_only_flag(value: &str) -> Option<bool> {
     match value.trim().to_ascii_lowercase().as_str() {
         "1" | "true" | "yes" | "y" | "on" | "ro" | "read-only" => Some(true),
@@ -130,6 +212,16 @@ pub(crate) fn default_profile_store() -> Option<FileProfilesStore> {
     FileProfilesStore::load_default().ok()
 }
 
+#[cfg(test)]
+pub(crate) fn default_option_file_location() -> Option<PathBuf> {
+    None
+}
+
+#[cfg(not(test))]
+pub(crate) fn default_option_file_location() -> Option<PathBuf> {
+    myr_core::profiles::default_option_file_path()
+}
+
 pub(crate) fn previous_char_boundary(text: &str, index: usize) -> usize {
     let clamped = index.min(text.len());
     if clamped == 0 {
@@ -163,39 +255,70 @@ pub(crate) fn run_connect_worker(profile: ConnectionProfile) -> ConnectWorkerOut
     {
         Ok(runtime) => runtime,
         Err(error) => {
-            return ConnectWorkerOutcome::Failure(format!("failed to create runtime: {error}"));
+            return ConnectWorkerOutcome::Failure(WorkerError::new(
+                format!("failed to create runtime: {error}"),
+                None,
+            ));
         }
     };
 
+    let connect_timeout = profile.connect_timeout();
     runtime.block_on(async move {
-        let mut manager = ConnectionManager::new(MysqlConnectionBackend);
+        // Resolved once here; the connection check and every pool opened for this connect
+        // reuse it.
+        let password = match ResolvedPassword::resolve(&profile).await {
+            Ok(password) => password,
+            Err(error) => {
+                return ConnectWorkerOutcome::Failure(WorkerError::new(
+                    error.to_string(),
+                    error.code(),
+                ));
+            }
+        };
+        let mut manager =
+            ConnectionManager::new(MysqlConnectionBackend::with_password(password.clone()));
         let connect_latency =
-            match tokio::time::timeout(CONNECT_TIMEOUT, manager.connect(profile.clone())).await {
+            match tokio::time::timeout(connect_timeout, manager.connect(profile.clone())).await {
                 Ok(Ok(latency)) => latency,
-                Ok(Err(error)) => return ConnectWorkerOutcome::Failure(error.to_string()),
+                Ok(Err(error)) => {
+                    return ConnectWorkerOutcome::Failure(WorkerError::new(
+                        error.to_string(),
+                        error.code(),
+                    ));
+                }
+                // libmysqlclient reports a connect timeout as "can't connect", not as fatal.
                 Err(_) => {
-                    return ConnectWorkerOutcome::Failure(format!(
-                        "connect timed out after {:.1?}",
-                        CONNECT_TIMEOUT
+                    return ConnectWorkerOutcome::Failure(WorkerError::new(
+                        format!("connect timed out after {connect_timeout:.1?}"),
+                        Some(CR_CONN_HOST_ERROR),
                     ));
                 }
             };
 
+        let server = manager.server_capabilities().cloned();
         let mut warnings = Vec::new();
-        match tokio::time::timeout(CONNECT_TIMEOUT, manager.disconnect()).await {
+        match tokio::time::timeout(connect_timeout, manager.disconnect()).await {
             Ok(Ok(())) => {}
             Ok(Err(error)) => warnings.push(format!("disconnect warning: {error}")),
             Err(_) => warnings.push(format!(
                 "disconnect timed out after {:.1?}",
-                CONNECT_TIMEOUT
+                connect_timeout
             )),
         }
 
-        let data_backend = MysqlDataBackend::from_profile(&profile);
+        let data_backend = match MysqlDataBackend::from_resolved(&profile, &password) {
+            Ok(backend) => backend,
+            Err(error) => {
+                return ConnectWorkerOutcome::Failure(WorkerError::new(
+                    error.to_string(),
+                    error.code(),
+                ));
+            }
+        };
         let mut schema_cache =
             SchemaCacheService::new(data_backend.clone(), Duration::from_secs(10));
         let databases =
-            match tokio::time::timeout(CONNECT_TIMEOUT, schema_cache.list_databases()).await {
+            match tokio::time::timeout(connect_timeout, schema_cache.list_databases()).await {
                 Ok(Ok(databases)) => databases,
                 Ok(Err(error)) => {
                     warnings.push(format!("schema fetch failed: {error}"));
@@ -204,7 +327,7 @@ pub(crate) fn run_connect_worker(profile: ConnectionProfile) -> ConnectWorkerOut
                 Err(_) => {
                     warnings.push(format!(
                         "schema fetch timed out after {:.1?}",
-                        CONNECT_TIMEOUT
+                        connect_timeout
                     ));
                     Vec::new()
                 }
@@ -216,85 +339,188 @@ pub(crate) fn run_connect_worker(profile: ConnectionProfile) -> ConnectWorkerOut
 
         ConnectWorkerOutcome::Success {
             profile,
+            password,
             connect_latency,
+            server,
             databases,
             warning: (!warnings.is_empty()).then(|| warnings.join("; ")),
         }
     })
 }
 
+// Query connections (including a pinned session) are bound to the runtime that opened them,
+// so every query worker shares one long-lived runtime instead of building its own.
+fn query_runtime() -> Result<&'static tokio::runtime::Runtime, String> {
+    static QUERY_RUNTIME: OnceLock<Result<tokio::runtime::Runtime, String>> = OnceLock::new();
+    QUERY_RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .enable_all()
+                .build()
+                .map_err(|error| error.to_string())
+        })
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
 pub(crate) fn run_query_worker(
     backend: MysqlDataBackend,
     sql: String,
+    params: Vec<QueryValue>,
+    timeout: Option<Duration>,
     cancellation: CancellationToken,
+    row_batches: Option<&Sender<QueryRowBatch>>,
+    retry_delay: Duration,
 ) -> QueryWorkerOutcome {
-    let runtime = match tokio::runtime::Builder::new_current_thread()
-        .enable_all()
-        .build()
-    {
+    let runtime = match query_runtime() {
         Ok(runtime) => runtime,
         Err(error) => {
-            return QueryWorkerOutcome::Failure(format!("failed to create runtime: {error}"));
+            return QueryWorkerOutcome::Failure(WorkerError::new(
+                format!("failed to create runtime: {error}"),
+                None,
+            ));
         }
     };
 
     let runner = QueryRunner::new(backend);
-    let mut results = ResultsRingBuffer::new(RESULT_BUFFER_CAPACITY);
-    match runtime.block_on(async {
-        tokio::time::timeout(
-            QUERY_TIMEOUT,
-            runner.execute_streaming(&sql, &mut results, &cancellation),
-        )
-        .await
-    }) {
-        Ok(Ok(summary)) => QueryWorkerOutcome::Success {
-            results,
+    let mut results = SpillingResultsStore::new(RESULT_BUFFER_CAPACITY)
+        .with_byte_budget(RESULT_MEMORY_BUDGET_BYTES, RESULT_CELL_PREVIEW_BYTES);
+    let mut pending_rows = Vec::new();
+    let mut last_batch_at = Instant::now();
+    // Rows go out in batches so a fast query does not flood the UI thread, while the
+    // interval check still lets the first rows of a slow query show up right away.
+    let mut forward_row = |columns: &[ResultColumn], row: &QueryRow| {
+        let Some(row_batches) = row_batches else {
+            return;
+        };
+        pending_rows.push(row.clone());
+        if pending_rows.len() >= STREAM_BATCH_ROWS
+            || last_batch_at.elapsed() >= STREAM_BATCH_INTERVAL
+        {
+            let _ = row_batches.send(QueryRowBatch {
+                columns: columns.to_vec(),
+                rows: std::mem::take(&mut pending_rows),
+            });
+            last_batch_at = Instant::now();
+        }
+    };
+    // A retry backs off first; cancelling during the wait must not run the statement.
+    if !retry_delay.is_zero()
+        && runtime
+            .block_on(cancellation.run_until_cancelled(tokio::time::sleep(retry_delay)))
+            .is_none()
+    {
+        return QueryWorkerOutcome::Success {
+            results: Box::new(results),
+            rows_streamed: 0,
+            was_cancelled: true,
+            elapsed: Duration::ZERO,
+            columns: Vec::new(),
+            status: StatementStatus::default(),
+            additional_result_sets: Vec::new(),
+            server_cancellation: None,
+            session_state_lost: false,
+        };
+    }
+    let (result, timed_out) = runtime.block_on(async {
+        let execution =
+            runner.execute_observed(&sql, &params, &mut results, &cancellation, &mut forward_row);
+        match timeout {
+            Some(timeout) => {
+                run_with_deadline(&cancellation, tokio::time::sleep(timeout), execution).await
+            }
+            None => (execution.await, false),
+        }
+    });
+    match result {
+        // The statement already used its whole time budget, so a timeout is not retried.
+        Ok(summary) if timed_out && summary.was_cancelled => {
+            let mut message = format!("query timed out after {:.1?}", timeout.unwrap_or_default());
+            if let Some(cancellation) = &summary.server_cancellation {
+                message.push_str(&format!("; {}", cancellation.describe()));
+            }
+            QueryWorkerOutcome::Failure(WorkerError::new(message, None))
+        }
+        Ok(summary) => QueryWorkerOutcome::Success {
+            results: Box::new(results),
             rows_streamed: summary.rows_streamed,
             was_cancelled: summary.was_cancelled,
             elapsed: summary.elapsed,
+            columns: summary.columns,
+            status: StatementStatus {
+                affected_rows: summary.affected_rows,
+                last_insert_id: summary.last_insert_id,
+                info: summary.info,
+                warning_count: summary.warning_count,
+                warnings: summary.warnings,
+            },
+            additional_result_sets: summary.additional_result_sets,
+            server_cancellation: summary.server_cancellation,
+            session_state_lost: summary.session_state_lost,
         },
-        Ok(Err(error)) => QueryWorkerOutcome::Failure(error.to_string()),
-        Err(_) => {
-            cancellation.cancel();
-            QueryWorkerOutcome::Failure(format!("query timed out after {:.1?}", QUERY_TIMEOUT))
+        Err(error) => {
+            QueryWorkerOutcome::Failure(WorkerError::new(error.to_string(), error.code()))
         }
     }
 }
 
-pub(crate) fn is_transient_query_error(message: &str) -> bool {
-    let normalized = message.to_ascii_lowercase();
-    [
-        "timed out",
-        "timeout",
-        "temporary",
-        "connection reset",
-        "connection refused",
-        "connection closed",
-        "broken pipe",
-        "server has gone away",
-        "lost connection",
-        "pool was disconnect",
-        "i/o error",
-        "io error",
-    ]
-    .iter()
-    .any(|needle| normalized.contains(needle))
-}
-
-pub(crate) fn is_connection_lost_error(message: &str) -> bool {
-    let normalized = message.to_ascii_lowercase();
-    [
-        "pool was disconnect",
-        "server has gone away",
-        "lost connection",
-        "connection reset",
-        "connection refused",
-        "connection closed",
-        "broken pipe",
-        "not connected",
-    ]
-    .iter()
-    .any(|needle| normalized.contains(needle))
+// Runs on the query runtime because a pinned session's connection belongs to it.
+pub(crate) fn run_keepalive_worker(
+    targets: Vec<(String, MysqlDataBackend, Duration)>,
+) -> Vec<KeepaliveProbe> {
+    let runtime = match query_runtime() {
+        Ok(runtime) => runtime,
+        Err(error) => {
+            return targets
+                .into_iter()
+                .map(|(profile_name, _, _)| KeepaliveProbe {
+                    profile_name,
+                    outcome: Err(format!("failed to create runtime: {error}")),
+                })
+                .collect();
+        }
+    };
+
+    runtime.block_on(async move {
+        let mut probes = Vec::with_capacity(targets.len());
+        for (profile_name, backend, timeout) in targets {
+            let outcome = match tokio::time::timeout(timeout, backend.ping()).await {
+                Ok(Ok(latency)) => Ok(latency),
+                Ok(Err(error)) => Err(error.to_string()),
+                Err(_) => Err(format!("keepalive ping timed out after {timeout:.1?}")),
+            };
+            probes.push(KeepaliveProbe {
+                profile_name,
+                outcome,
+            });
+        }
+        probes
+    })
+}
+
+// OSC 52 asks the terminal itself to set the clipboard, which also works over SSH
+// where no local clipboard tool can be reached.
+pub(crate) fn osc52_copy_sequence(text: &str) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(text.len().div_ceil(3) * 4);
+    for chunk in text.as_bytes().chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let triple = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for position in 0..4 {
+            if position <= chunk.len() {
+                let index = (triple >> (18 - 6 * position)) & 0x3F;
+                encoded.push(char::from(ALPHABET[index as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    format!("\x1b]52;c;{encoded}\x07")
 }
 
 pub(crate) fn quote_identifier(identifier: &str) -> String {
@@ -316,7 +542,7 @@ pub(crate) fn candidate_key_column(columns: &[String]) -> Option<String> {
 }
 
 pub(crate) fn extract_key_bounds(
-    results: &ResultsRingBuffer<QueryRow>,
+    results: &dyn ResultsStore<QueryRow>,
     columns: &[String],
     key_column: &str,
 ) -> (Option<String>, Option<String>) {
@@ -327,16 +553,18 @@ pub(crate) fn extract_key_bounds(
         return (None, None);
     };
 
-    let first = results
-        .get(0)
-        .and_then(|row| row.values.get(key_index))
-        .cloned();
+    let key_text = |row: QueryRow| {
+        row.values
+            .get(key_index)
+            .filter(|value| !value.is_null())
+            .map(ToString::to_string)
+    };
+    let first = results.row(0).and_then(key_text);
     let last = results
         .len()
         .checked_sub(1)
-        .and_then(|index| results.get(index))
-        .and_then(|row| row.values.get(key_index))
-        .cloned();
+        .and_then(|index| results.row(index))
+        .and_then(key_text);
     (first, last)
 }
 
@@ -417,6 +645,7 @@ pub(crate) fn map_key_event(key: KeyEvent) -> Option<Msg> {
         KeyCode::F(5) => Some(Msg::Connect),
         KeyCode::F(6) => Some(Msg::GoConnectionWizard),
         KeyCode::F(7) => Some(Msg::GoProfileBookmarkManager),
+        KeyCode::F(8) => Some(Msg::NextConnection),
         KeyCode::F(10) => Some(Msg::Quit),
         KeyCode::F(2) => Some(Msg::TogglePerfOverlay),
         KeyCode::F(3) => Some(Msg::ToggleSafeMode),
@@ -444,3 +673,21 @@ pub(crate) fn map_key_event(key: KeyEvent) -> Option<Msg> {
 pub(crate) fn suggest_limit_in_editor(query: &str) -> Option<String> {
     myr_core::actions_engine::suggest_preview_limit(query, 200)
 }
+
+// `environment_color` accepts anything ratatui can parse (`red`, `light-blue`, `#ff5f00`);
+// an unknown color falls back to the environment's default rather than hiding the tag.
+pub(crate) fn environment_badge(profile: &ConnectionProfile) -> Option<(String, Color)> {
+    let environment = profile.environment.as_ref()?;
+    let default_color = match environment {
+        ProfileEnvironment::Dev => Color::Green,
+        ProfileEnvironment::Staging => Color::Yellow,
+        ProfileEnvironment::Prod => Color::Red,
+        ProfileEnvironment::Custom(_) => Color::Magenta,
+    };
+    let color = profile
+        .environment_color
+        .as_deref()
+        .and_then(|color| color.trim().parse::<Color>().ok())
+        .unwrap_or(default_color);
+    Some((environment.label(), color))
+}
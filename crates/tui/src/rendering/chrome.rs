@@ -1,15 +1,31 @@
 use super::super::*;
-use super::support::pulse_char;
+use super::support::{latency_sparkline, pulse_char};
 use super::{connection_badge_and_marker, spinner_char};
 
 pub(super) fn render_runtime_bar(frame: &mut Frame<'_>, app: &TuiApp, area: Rect) {
-    let latency_text = app
+    let mut latency_text = app
         .last_connection_latency
         .map_or("n/a".to_string(), |latency| format!("{latency:.1?}"));
+    if app.latency_history.len() > 1 {
+        latency_text.push(' ');
+        latency_text.push_str(&latency_sparkline(
+            &app.latency_history,
+            LATENCY_SPARKLINE_WIDTH,
+        ));
+    }
     let profile_mode = app
         .active_connection_profile
         .as_ref()
-        .map_or("-", |profile| if profile.read_only { "RO" } else { "RW" });
+        .map_or(
+            "-",
+            |profile| {
+                if profile.is_read_only() {
+                    "RO"
+                } else {
+                    "RW"
+                }
+            },
+        );
     let tls_mode = app
         .active_connection_profile
         .as_ref()
@@ -19,6 +35,10 @@ pub(super) fn render_runtime_bar(frame: &mut Frame<'_>, app: &TuiApp, area: Rect
             TlsMode::Require => "require",
             TlsMode::VerifyIdentity => "verify",
         });
+    let server_text = app
+        .server_capabilities
+        .as_ref()
+        .map_or_else(String::new, |server| format!(" ({})", server.label()));
     let heartbeat = spinner_char(app.loading_tick);
     let loading_text =
         if app.connect_requested && app.connect_intent == ConnectIntent::AutoReconnect {
@@ -53,7 +73,7 @@ pub(super) fn render_runtime_bar(frame: &mut Frame<'_>, app: &TuiApp, area: Rect
         _ => Color::Red,
     };
 
-    let runtime_bar = Paragraph::new(Line::from(vec![
+    let mut spans = vec![
         Span::styled(
             format!(" APP {heartbeat} "),
             Style::default()
@@ -68,6 +88,26 @@ pub(super) fn render_runtime_bar(frame: &mut Frame<'_>, app: &TuiApp, area: Rect
                 .fg(runtime_state_color)
                 .add_modifier(Modifier::BOLD),
         ),
+    ];
+    if let Some((label, color)) = app
+        .active_connection_profile
+        .as_ref()
+        .and_then(environment_badge)
+    {
+        spans.push(Span::raw(" | "));
+        spans.push(environment_span(label, color));
+    }
+    if app.transaction_open {
+        spans.push(Span::raw(" | "));
+        spans.push(Span::styled(
+            " TX OPEN ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans.extend([
         Span::raw(" | "),
         Span::raw("DB: "),
         Span::styled(
@@ -78,9 +118,30 @@ pub(super) fn render_runtime_bar(frame: &mut Frame<'_>, app: &TuiApp, area: Rect
         ),
         Span::raw(" | "),
         Span::raw(format!(
-            "Profile: {}",
+            "Profile: {}{server_text}",
             app.connected_profile.as_deref().unwrap_or("not connected")
         )),
+    ]);
+    // Only profiles with replicas route queries, so only they show where the last one ran.
+    if let Some(backend) = app
+        .data_backend
+        .as_ref()
+        .filter(|backend| backend.has_replicas())
+    {
+        spans.push(Span::raw(" | "));
+        spans.push(Span::raw(format!(
+            "Served: {}",
+            backend
+                .last_endpoint()
+                .map_or_else(|| "-".to_string(), |endpoint| endpoint.to_string())
+        )));
+        let skipped = backend.skipped_replicas();
+        if !skipped.is_empty() {
+            spans.push(Span::raw(" | "));
+            spans.push(Span::raw(format!("Skipped: {}", skipped.join("; "))));
+        }
+    }
+    spans.extend([
         Span::raw(" | "),
         Span::raw(format!("Mode: {profile_mode}")),
         Span::raw(" | "),
@@ -99,12 +160,24 @@ pub(super) fn render_runtime_bar(frame: &mut Frame<'_>, app: &TuiApp, area: Rect
         )),
         Span::raw(" | "),
         Span::raw(format!("Load: {loading_text}")),
-    ]))
-    .block(Block::default().borders(Borders::ALL).title("Runtime"));
+    ]);
+
+    let runtime_bar = Paragraph::new(Line::from(spans))
+        .block(Block::default().borders(Borders::ALL).title("Runtime"));
 
     frame.render_widget(runtime_bar, area);
 }
 
+fn environment_span(label: String, color: Color) -> Span<'static> {
+    Span::styled(
+        format!(" {label} "),
+        Style::default()
+            .fg(Color::Black)
+            .bg(color)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
 pub(super) fn render_tabs_bar(frame: &mut Frame<'_>, app: &TuiApp, area: Rect) {
     let tab_focus_marker = pulse_char(app.loading_tick);
     let tabs_title = if app.pane_flash_ticks > 0 {
@@ -150,13 +223,100 @@ pub(super) fn render_tabs_bar(frame: &mut Frame<'_>, app: &TuiApp, area: Rect) {
             .add_modifier(Modifier::BOLD)
     };
 
+    // The environment leads the title so a prod session is obvious from any pane.
+    let mut title_spans = Vec::new();
+    if let Some((label, color)) = app
+        .active_connection_profile
+        .as_ref()
+        .and_then(environment_badge)
+    {
+        title_spans.push(environment_span(label, color));
+        title_spans.push(Span::raw(" "));
+    }
+    title_spans.push(Span::raw(tabs_title));
     let tabs = Tabs::new(tab_labels)
         .select(app.pane_tab_index())
         .style(Style::default().fg(Color::DarkGray))
         .highlight_style(tab_highlight_style)
         .divider(" | ")
-        .block(Block::default().borders(Borders::ALL).title(tabs_title));
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Line::from(title_spans)),
+        );
+
+    let connection_tabs = app.connection_tabs();
+    if connection_tabs.len() < 2 {
+        frame.render_widget(tabs, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(area);
+    frame.render_widget(tabs, chunks[0]);
+    render_connection_tabs(frame, &connection_tabs, chunks[1]);
+}
+
+fn render_connection_tabs(
+    frame: &mut Frame<'_>,
+    connection_tabs: &[ConnectionTab<'_>],
+    area: Rect,
+) {
+    let selected = connection_tabs
+        .iter()
+        .position(|tab| tab.active)
+        .unwrap_or(0);
+    let labels = connection_tabs
+        .iter()
+        .enumerate()
+        .map(|(index, tab)| {
+            let (badge, badge_color) = if tab.read_only {
+                ("RO", Color::Green)
+            } else {
+                ("RW", Color::Red)
+            };
+            let mut spans = vec![Span::raw(format!("{}:{} ", index + 1, tab.profile_name))];
+            if let Some((label, color)) = &tab.environment {
+                spans.push(environment_span(label.clone(), *color));
+                spans.push(Span::raw(" "));
+            }
+            spans.push(Span::styled(
+                badge,
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(badge_color)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            if tab.transaction_open {
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(
+                    "TX",
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect::<Vec<_>>();
 
+    let tabs = Tabs::new(labels)
+        .select(selected)
+        .style(Style::default().fg(Color::DarkGray))
+        .highlight_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .divider(" | ")
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Connections (F8 next)"),
+        );
     frame.render_widget(tabs, area);
 }
 
@@ -164,7 +324,7 @@ pub(super) fn footer_line(app: &TuiApp) -> String {
     if app.pane == Pane::ConnectionWizard {
         "F5: connect | E/Enter: edit | Enter: save edit | Esc: cancel edit | F10: quit".to_string()
     } else if app.pane == Pane::ProfileBookmarks {
-        "F5: connect | Enter: open/save | Del: delete | r:rename d:default q:quick | F6/F7"
+        "F5: connect | Enter: open | Del: delete | r:rename d:default q:quick u:url i:import | F6/F7"
             .to_string()
     } else {
         let actions = app
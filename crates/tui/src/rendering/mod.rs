@@ -66,8 +66,12 @@ pub(super) fn render(frame: &mut Frame<'_>, app: &TuiApp) {
         .alignment(Alignment::Left);
     frame.render_widget(body, body_area);
 
-    let overlays_visible =
-        app.show_palette || app.show_help || app.exit_confirmation || app.error_panel.is_some();
+    let overlays_visible = app.show_palette
+        || app.show_help
+        || app.exit_confirmation
+        || app.error_panel.is_some()
+        || app.parameter_form.is_some()
+        || app.transaction_prompt.is_some();
     if !overlays_visible {
         if let Some((x, y)) = query_cursor_screen_position {
             frame.set_cursor_position((x, y));
@@ -81,6 +85,9 @@ pub(super) fn render(frame: &mut Frame<'_>, app: &TuiApp) {
     .block(Block::default().borders(Borders::ALL).title("Next Actions"));
     frame.render_widget(footer, chunks[2]);
 
+    if app.show_perf_overlay {
+        overlays::render_perf_overlay(frame, app);
+    }
     if app.show_palette {
         overlays::render_palette_popup(frame, app);
     }
@@ -90,7 +97,13 @@ pub(super) fn render(frame: &mut Frame<'_>, app: &TuiApp) {
     if app.exit_confirmation {
         overlays::render_exit_popup(frame);
     }
+    if app.parameter_form.is_some() {
+        overlays::render_parameter_popup(frame, app);
+    }
     if app.error_panel.is_some() {
         overlays::render_error_popup(frame, app);
     }
+    if app.transaction_prompt.is_some() {
+        overlays::render_transaction_popup(frame, app);
+    }
 }
@@ -9,10 +9,13 @@ pub(super) fn render_help_popup(frame: &mut Frame<'_>) {
         Line::from("F10: quit immediately"),
         Line::from("F6: go to connection wizard"),
         Line::from("F7: open profiles/bookmarks manager"),
+        Line::from("F8: next connection tab"),
         Line::from("?: toggle help"),
         Line::from("Tab: cycle panes"),
         Line::from("Connection wizard: E/Enter edit, F5 connect"),
-        Line::from("Profiles manager: F5 connect, r rename, d default, q quick reconnect"),
+        Line::from(
+            "Profiles manager: F5 connect, r rename, d default, q quick reconnect, u copy URL, i/o import ~/.my.cnf",
+        ),
         Line::from("Query editor: Enter run, Ctrl+Enter newline"),
         Line::from("Query editor: Left/Right cursor, Up/Down history"),
         Line::from("Palette: use 'health' / 'doctor' to run diagnostics"),
@@ -46,6 +49,87 @@ pub(super) fn render_exit_popup(frame: &mut Frame<'_>) {
     frame.render_widget(content, area);
 }
 
+pub(super) fn render_perf_overlay(frame: &mut Frame<'_>, app: &TuiApp) {
+    let mut lines = vec![
+        Line::from(format!("Render: {:.1} ms", app.last_render_ms)),
+        Line::from(format!("FPS: {:.1}", app.fps)),
+    ];
+    if let Some(usage) = app.results.budget_usage() {
+        lines.push(Line::from(format!(
+            "Results: {} / {}",
+            format_bytes(usage.used_bytes),
+            format_bytes(usage.max_bytes)
+        )));
+        lines.push(Line::from(format!(
+            "Rows in memory: {}",
+            usage.rows_in_memory
+        )));
+        lines.push(Line::from(format!(
+            "Truncated cells: {}",
+            usage.truncated_cells
+        )));
+    }
+
+    let screen = frame.area();
+    let width = 34.min(screen.width);
+    let height = (lines.len() as u16 + 2).min(screen.height);
+    let area = Rect::new(screen.x + screen.width - width, screen.y, width, height);
+    frame.render_widget(Clear, area);
+    let content = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Perf"));
+    frame.render_widget(content, area);
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+pub(super) fn render_transaction_popup(frame: &mut Frame<'_>, app: &TuiApp) {
+    let Some(intent) = app.transaction_prompt.as_ref() else {
+        return;
+    };
+
+    let next_step = match intent {
+        TransactionExitIntent::Quit => "exit myr".to_string(),
+        TransactionExitIntent::Connect(profile) => format!("connect to `{}`", profile.name),
+        TransactionExitIntent::CloseConnection => format!(
+            "close `{}`",
+            app.connected_profile.as_deref().unwrap_or("connection")
+        ),
+    };
+    let area = centered_rect(50, 25, frame.area());
+    frame.render_widget(Clear, area);
+    let content = Paragraph::new(vec![
+        Line::from(Span::styled(
+            "A transaction is still open",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(format!("C: commit and {next_step}")),
+        Line::from(format!("R: roll back and {next_step}")),
+        Line::from("Esc: keep the transaction open"),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Open Transaction"),
+    )
+    .alignment(Alignment::Center);
+    frame.render_widget(content, area);
+}
+
 pub(super) fn render_error_popup(frame: &mut Frame<'_>, app: &TuiApp) {
     let Some(panel) = app.error_panel.as_ref() else {
         return;
@@ -62,11 +146,30 @@ pub(super) fn render_error_popup(frame: &mut Frame<'_>, app: &TuiApp) {
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
-        Line::from(format!("Detail: {}", panel.detail)),
-        Line::from(""),
-        Line::from("Recovery actions:"),
     ];
 
+    if matches!(panel.kind, ErrorKind::Warnings | ErrorKind::CellValue) {
+        lines.extend(
+            panel
+                .detail
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+        );
+        lines.push(Line::from(""));
+        lines.push(Line::from("Enter or Esc: dismiss panel"));
+        let warnings_panel = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(panel.title.as_str()),
+        );
+        frame.render_widget(warnings_panel, area);
+        return;
+    }
+
+    lines.push(Line::from(format!("Detail: {}", panel.detail)));
+    lines.push(Line::from(""));
+    lines.push(Line::from("Recovery actions:"));
+
     if panel.kind == ErrorKind::Query && app.last_failed_query.is_some() {
         lines.push(Line::from("1 or Enter: retry last query"));
     }
@@ -84,6 +187,53 @@ pub(super) fn render_error_popup(frame: &mut Frame<'_>, app: &TuiApp) {
     frame.render_widget(error_panel, area);
 }
 
+pub(super) fn render_parameter_popup(frame: &mut Frame<'_>, app: &TuiApp) {
+    let Some(form) = app.parameter_form.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(70, 50, frame.area());
+    frame.render_widget(Clear, area);
+
+    let mut lines = vec![
+        Line::from(format!("Prepared statement: {}", form.query.sql())),
+        Line::from(""),
+    ];
+    for (index, (name, value)) in form
+        .query
+        .parameter_names()
+        .iter()
+        .zip(&form.values)
+        .enumerate()
+    {
+        if index == form.active_index {
+            lines.push(Line::from(Span::styled(
+                format!("> {name} = {value}_"),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        } else {
+            lines.push(Line::from(format!("  {name} = {value}")));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(
+        "Integers bind as numbers, NULL as SQL NULL, 'quoted' as text",
+    ));
+    lines.push(Line::from(
+        "Enter run | Tab/Up/Down switch | Ctrl+U clear | Esc cancel",
+    ));
+
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Query Parameters"),
+    );
+    frame.render_widget(popup, area);
+}
+
 pub(super) fn render_palette_popup(frame: &mut Frame<'_>, app: &TuiApp) {
     let area = centered_rect(70, 60, frame.area());
     frame.render_widget(Clear, area);
@@ -2,6 +2,11 @@ use super::super::*;
 
 pub(super) fn body_lines(app: &TuiApp) -> Vec<Line<'static>> {
     let fields = [
+        (
+            WizardField::Url,
+            "Paste URL (mysql://user@host:port/db?ssl-mode=...)",
+            app.wizard_form.url.as_str(),
+        ),
         (
             WizardField::ProfileName,
             "Profile",
@@ -9,12 +14,22 @@ pub(super) fn body_lines(app: &TuiApp) -> Vec<Line<'static>> {
         ),
         (WizardField::Host, "Host", app.wizard_form.host.as_str()),
         (WizardField::Port, "Port", app.wizard_form.port.as_str()),
+        (
+            WizardField::SocketPath,
+            "Socket path (blank = TCP)",
+            app.wizard_form.socket_path.as_str(),
+        ),
         (WizardField::User, "User", app.wizard_form.user.as_str()),
         (
             WizardField::PasswordSource,
-            "Password source (env/keyring)",
+            "Password source (env/keyring/command/file)",
             app.wizard_form.password_source.as_str(),
         ),
+        (
+            WizardField::PasswordFrom,
+            "Password from (env var / command / file path)",
+            app.wizard_form.password_from.as_str(),
+        ),
         (
             WizardField::Database,
             "Database",
@@ -30,6 +45,31 @@ pub(super) fn body_lines(app: &TuiApp) -> Vec<Line<'static>> {
             "Read-only (yes/no)",
             app.wizard_form.read_only.as_str(),
         ),
+        (
+            WizardField::Environment,
+            "Environment (dev/staging/prod/custom, blank = none)",
+            app.wizard_form.environment.as_str(),
+        ),
+        (
+            WizardField::SshJump,
+            "SSH jump (user@host:port, blank = direct)",
+            app.wizard_form.ssh_jump.as_str(),
+        ),
+        (
+            WizardField::SshKeyPath,
+            "SSH key path",
+            app.wizard_form.ssh_key_path.as_str(),
+        ),
+        (
+            WizardField::SshKnownHosts,
+            "SSH known_hosts (strict/accept_new/off)",
+            app.wizard_form.ssh_known_hosts.as_str(),
+        ),
+        (
+            WizardField::SshAgent,
+            "SSH agent (yes/no)",
+            app.wizard_form.ssh_agent.as_str(),
+        ),
     ];
 
     let mut lines = vec![
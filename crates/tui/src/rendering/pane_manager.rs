@@ -8,7 +8,9 @@ pub(super) fn body_lines(app: &TuiApp, body_area: Rect) -> Vec<Line<'static>> {
     let mut lines = vec![
         Line::from("Profiles & Bookmarks"),
         Line::from("Left/Right: lane focus | Up/Down: selection | Enter: open/save | Del: delete"),
-        Line::from("Shortcuts: r rename | d default profile | q quick reconnect | F5 connect"),
+        Line::from(
+            "Shortcuts: r rename | d default | q quick reconnect | u copy URL | i import | F5 connect",
+        ),
         Line::from(format!(
             "Focus lane: {} | Profiles: {} | Bookmarks: {}",
             app.manager_lane.label(),
@@ -157,9 +159,18 @@ fn append_selected_details(
             lines.push(Line::from(format!(
                 "  Database: {} | Mode: {} | TLS: {:?}",
                 profile.database.as_deref().unwrap_or("-"),
-                if profile.read_only { "RO" } else { "RW" },
+                if profile.is_read_only() { "RO" } else { "RW" },
                 profile.tls_mode
             )));
+            if let Some(environment) = &profile.environment {
+                lines.push(Line::from(format!(
+                    "  Environment: {environment}{}",
+                    profile
+                        .environment_color
+                        .as_deref()
+                        .map_or_else(String::new, |color| format!(" ({color})"))
+                )));
+            }
             lines.push(Line::from(format!(
                 "  Default: {} | Quick reconnect: {}",
                 if profile.is_default { "yes" } else { "no" },
@@ -209,6 +220,9 @@ fn render_profile_summary(profile: &ConnectionProfile) -> String {
     if profile.quick_reconnect {
         markers.push("quick");
     }
+    if let Some(environment) = &profile.environment {
+        markers.push(environment.name());
+    }
     let marker_text = if markers.is_empty() {
         String::new()
     } else {
@@ -222,7 +236,7 @@ fn render_profile_summary(profile: &ConnectionProfile) -> String {
         profile.host,
         profile.port,
         profile.database.as_deref().unwrap_or("-"),
-        if profile.read_only { "RO" } else { "RW" }
+        if profile.is_read_only() { "RO" } else { "RW" }
     )
 }
 
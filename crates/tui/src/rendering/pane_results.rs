@@ -3,7 +3,8 @@ use super::super::*;
 pub(super) fn body_lines(app: &TuiApp, body_area: Rect) -> Vec<Line<'static>> {
     let visible_limit = usize::from(body_area.height.saturating_sub(8)).max(1);
     let window_start = app.results_cursor.saturating_sub(visible_limit / 2);
-    let rows = app.results.visible_rows(window_start, visible_limit);
+    let visible_rows = app.results.rows(window_start, visible_limit);
+    let rows = visible_rows.iter().collect::<Vec<_>>();
     let no_rows = rows.is_empty();
 
     let mut lines = vec![
@@ -22,6 +23,9 @@ pub(super) fn body_lines(app: &TuiApp, body_area: Rect) -> Vec<Line<'static>> {
     } else {
         lines.push(Line::from("Press 7 to search buffered rows."));
     }
+    if let Some(progress) = app.stream_progress.filter(|_| app.query_running) {
+        lines.push(Line::from(stream_progress_line(progress)));
+    }
 
     if let Some(state) = &app.pagination_state {
         let strategy = match &state.plan {
@@ -37,6 +41,13 @@ pub(super) fn body_lines(app: &TuiApp, body_area: Rect) -> Vec<Line<'static>> {
             state.page_size
         )));
     }
+    if app.result_sets.len() > 1 {
+        lines.push(Line::from(format!(
+            "Result set {} of {} (Next result set action cycles)",
+            app.active_result_set + 1,
+            app.result_sets.len()
+        )));
+    }
     lines.push(Line::from(""));
 
     if no_rows {
@@ -49,6 +60,7 @@ pub(super) fn body_lines(app: &TuiApp, body_area: Rect) -> Vec<Line<'static>> {
         let table_width = usize::from(body_area.width.saturating_sub(3));
         let table_lines = build_aligned_results_rows(
             &app.result_columns,
+            &app.result_column_metadata,
             &rows,
             app.results_cursor,
             app.results_column_cursor,
@@ -61,8 +73,22 @@ pub(super) fn body_lines(app: &TuiApp, body_area: Rect) -> Vec<Line<'static>> {
     lines
 }
 
+fn stream_progress_line(progress: StreamProgress) -> String {
+    let elapsed = progress.started_at.elapsed();
+    let rows_per_sec = if elapsed.as_secs_f64() > 0.0 {
+        progress.rows_received as f64 / elapsed.as_secs_f64()
+    } else {
+        0.0
+    };
+    format!(
+        "Streaming: {} rows | {elapsed:.1?} elapsed | {rows_per_sec:.0} rows/s | Ctrl+C stops and keeps rows",
+        progress.rows_received
+    )
+}
+
 fn build_aligned_results_rows(
     headers: &[String],
+    metadata: &[ResultColumn],
     rows: &[&QueryRow],
     selected_row: usize,
     selected_column: usize,
@@ -71,29 +97,44 @@ fn build_aligned_results_rows(
 ) -> Vec<Line<'static>> {
     let prefix_width = 6_usize;
     let available_width = table_width.saturating_sub(prefix_width).max(8);
-    let Some(layout) = compute_results_layout(headers, rows, selected_column, available_width)
+    let labels = decorated_headers(headers, metadata);
+    let Some(layout) = compute_results_layout(&labels, rows, selected_column, available_width)
     else {
         return vec![Line::from("Rows have no visible columns.")];
     };
+    let right_aligned = (layout.column_start..layout.column_end)
+        .map(|column_index| {
+            metadata
+                .get(column_index)
+                .is_some_and(|column| column.sql_type.is_numeric())
+        })
+        .collect::<Vec<_>>();
 
     let active_column_label = column_label(headers, layout.selected_column);
+    let active_column_detail = metadata
+        .get(layout.selected_column)
+        .map(|column| format!(" ({})", column.describe()))
+        .unwrap_or_default();
     let mut lines = vec![Line::from(format!(
-        "Columns {}-{} / {} | Active col {}: {}",
+        "Columns {}-{} / {} | Active col {}: {}{}",
         layout.column_start + 1,
         layout.column_end,
         layout.column_count,
         layout.selected_column + 1,
-        active_column_label
+        active_column_label,
+        active_column_detail
     ))];
 
     let header_cells = (layout.column_start..layout.column_end)
-        .map(|column_index| column_label(headers, column_index))
+        .map(|column_index| column_label(&labels, column_index))
         .collect::<Vec<_>>();
-    let header_row = format_aligned_cells(
+    let header_row = aligned_cell_texts(
         &header_cells,
         &layout.widths,
         Some(layout.selected_relative_column),
-    );
+        &right_aligned,
+    )
+    .join(" | ");
     lines.push(Line::from(Span::styled(
         format!("      {header_row}"),
         Style::default()
@@ -112,29 +153,50 @@ fn build_aligned_results_rows(
         } else {
             " "
         };
-        let row_cells = (layout.column_start..layout.column_end)
-            .map(|column_index| {
-                row.values
-                    .get(column_index)
-                    .cloned()
-                    .unwrap_or_else(String::new)
-            })
+        let row_values = (layout.column_start..layout.column_end)
+            .map(|column_index| row.values.get(column_index))
             .collect::<Vec<_>>();
-        let row_text = format_aligned_cells(
+        let row_cells = row_values
+            .iter()
+            .map(|value| value.map(ToString::to_string).unwrap_or_default())
+            .collect::<Vec<_>>();
+        let padded_cells = aligned_cell_texts(
             &row_cells,
             &layout.widths,
             Some(layout.selected_relative_column),
+            &right_aligned,
         );
-        let row_line = format!("{marker}{:04} {row_text}", absolute_index + 1);
+        let row_prefix = format!("{marker}{:04} ", absolute_index + 1);
         if absolute_index == selected_row {
             lines.push(Line::from(Span::styled(
-                row_line,
+                format!("{row_prefix}{}", padded_cells.join(" | ")),
                 Style::default()
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
             )));
         } else {
-            lines.push(Line::from(row_line));
+            let mut spans = vec![Span::raw(row_prefix)];
+            for (index, cell) in padded_cells.into_iter().enumerate() {
+                if index > 0 {
+                    spans.push(Span::raw(" | "));
+                }
+                if row_values
+                    .get(index)
+                    .copied()
+                    .flatten()
+                    .is_some_and(QueryValue::is_null)
+                {
+                    spans.push(Span::styled(
+                        cell,
+                        Style::default()
+                            .fg(Color::DarkGray)
+                            .add_modifier(Modifier::ITALIC),
+                    ));
+                } else {
+                    spans.push(Span::raw(cell));
+                }
+            }
+            lines.push(Line::from(spans));
         }
     }
 
@@ -179,7 +241,7 @@ fn compute_results_layout(
             let value_len = rows
                 .iter()
                 .filter_map(|row| row.values.get(column_index))
-                .map(|value| char_len(value))
+                .map(|value| char_len(&value.to_string()))
                 .max()
                 .unwrap_or(0);
             header_len.max(value_len).clamp(3, 28)
@@ -262,6 +324,20 @@ fn shrink_widths_to_fit(widths: &mut [usize], available_width: usize) {
     }
 }
 
+fn decorated_headers(headers: &[String], metadata: &[ResultColumn]) -> Vec<String> {
+    let column_count = headers.len().max(metadata.len());
+    (0..column_count)
+        .map(|index| {
+            let label = column_label(headers, index);
+            if metadata.get(index).is_some_and(|column| column.primary_key) {
+                format!("*{label}")
+            } else {
+                label
+            }
+        })
+        .collect()
+}
+
 fn column_label(headers: &[String], index: usize) -> String {
     headers
         .get(index)
@@ -269,33 +345,30 @@ fn column_label(headers: &[String], index: usize) -> String {
         .unwrap_or_else(|| format!("col{}", index + 1))
 }
 
-fn format_aligned_cells(
+fn aligned_cell_texts(
     cells: &[String],
     widths: &[usize],
     selected_column: Option<usize>,
-) -> String {
+    right_aligned: &[bool],
+) -> Vec<String> {
     widths
         .iter()
         .enumerate()
         .map(|(index, width)| {
             let value = cells.get(index).map_or("", std::string::String::as_str);
-            if Some(index) == selected_column {
+            let cell = if Some(index) == selected_column {
                 emphasize_cell(value, *width)
             } else {
-                pad_cell(value, *width)
+                truncate_cell(value, *width)
+            };
+            let padding = " ".repeat(width.saturating_sub(char_len(&cell)));
+            if right_aligned.get(index).copied().unwrap_or(false) {
+                format!("{padding}{cell}")
+            } else {
+                format!("{cell}{padding}")
             }
         })
-        .collect::<Vec<_>>()
-        .join(" | ")
-}
-
-fn pad_cell(value: &str, width: usize) -> String {
-    let mut cell = truncate_cell(value, width);
-    let padding = width.saturating_sub(char_len(&cell));
-    if padding > 0 {
-        cell.push_str(&" ".repeat(padding));
-    }
-    cell
+        .collect()
 }
 
 fn emphasize_cell(value: &str, width: usize) -> String {
@@ -311,12 +384,7 @@ fn emphasize_cell(value: &str, width: usize) -> String {
 
     let inner_width = width.saturating_sub(2);
     let inner = truncate_cell(value, inner_width);
-    let mut cell = format!("[{inner}]");
-    let padding = width.saturating_sub(char_len(&cell));
-    if padding > 0 {
-        cell.push_str(&" ".repeat(padding));
-    }
-    cell
+    format!("[{inner}]")
 }
 
 fn truncate_cell(value: &str, width: usize) -> String {
@@ -340,7 +408,9 @@ fn char_len(value: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use super::{compute_results_layout, format_aligned_cells};
+    use myr_core::query_runner::{ResultColumn, SqlType};
+
+    use super::{aligned_cell_texts, compute_results_layout, decorated_headers};
     use crate::QueryRow;
 
     #[test]
@@ -374,7 +444,24 @@ mod tests {
     fn selected_column_is_emphasized_in_aligned_output() {
         let cells = vec!["id".to_string(), "value".to_string(), "created".to_string()];
         let widths = vec![4, 7, 7];
-        let rendered = format_aligned_cells(&cells, &widths, Some(1));
+        let rendered = aligned_cell_texts(&cells, &widths, Some(1), &[]).join(" | ");
         assert!(rendered.contains("[value]"));
     }
+
+    #[test]
+    fn numeric_columns_right_align_and_key_columns_are_marked() {
+        let mut id = ResultColumn::new("id", SqlType::BigInt);
+        id.primary_key = true;
+        let metadata = vec![id, ResultColumn::new("name", SqlType::VarChar)];
+        let headers = vec!["id".to_string(), "name".to_string()];
+
+        assert_eq!(
+            decorated_headers(&headers, &metadata),
+            vec!["*id".to_string(), "name".to_string()]
+        );
+
+        let cells = vec!["7".to_string(), "ada".to_string()];
+        let rendered = aligned_cell_texts(&cells, &[4, 5], None, &[true, false]);
+        assert_eq!(rendered, vec!["   7".to_string(), "ada  ".to_string()]);
+    }
 }
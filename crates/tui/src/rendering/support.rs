@@ -25,6 +25,26 @@ pub(super) fn spinner_char(tick: usize) -> char {
     FRAMES[tick % FRAMES.len()]
 }
 
+// Scales against the slowest sample shown, so a steady connection reads as a flat line.
+pub(super) fn latency_sparkline(history: &LatencyHistory, width: usize) -> String {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let samples = history
+        .samples()
+        .skip(history.len().saturating_sub(width))
+        .collect::<Vec<_>>();
+    let Some(peak) = samples.iter().max().filter(|peak| !peak.is_zero()) else {
+        return LEVELS[0].to_string().repeat(samples.len());
+    };
+
+    samples
+        .iter()
+        .map(|sample| {
+            let level = sample.as_secs_f64() / peak.as_secs_f64() * (LEVELS.len() - 1) as f64;
+            LEVELS[(level.round() as usize).min(LEVELS.len() - 1)]
+        })
+        .collect()
+}
+
 pub(super) fn pulse_char(tick: usize) -> char {
     const FRAMES: [char; 4] = ['.', 'o', 'O', 'o'];
     FRAMES[tick % FRAMES.len()]
@@ -1,8 +1,13 @@
 use super::super::*;
-use super::navigation::{ManagerLane, Pane, SchemaColumnViewMode, SchemaLane};
+use super::navigation::{ManagerLane, Pane, ResultsSearchScan, SchemaColumnViewMode, SchemaLane};
 use super::pagination::{PageTransition, PaginationState};
-use super::runtime::{ConnectIntent, ConnectWorkerOutcome, ErrorPanel, QueryWorkerOutcome};
+use super::parameters::{BoundQuery, QueryParameterForm};
+use super::runtime::{
+    ConnectIntent, ConnectWorkerOutcome, ErrorPanel, KeepaliveProbe, QueryRowBatch,
+    QueryWorkerOutcome, StreamProgress, TransactionExitIntent,
+};
 use super::wizard::ConnectionWizardForm;
+use super::workspace::ConnectionWorkspace;
 
 pub(crate) struct TuiApp {
     pub(crate) actions: ActionsEngine,
@@ -10,6 +15,13 @@ pub(crate) struct TuiApp {
     pub(crate) wizard_form: ConnectionWizardForm,
     pub(crate) connected_profile: Option<String>,
     pub(crate) last_connection_latency: Option<Duration>,
+    pub(crate) latency_history: LatencyHistory,
+    pub(crate) last_keepalive_at: Option<Instant>,
+    pub(crate) connection_stale: bool,
+    pub(crate) server_capabilities: Option<ServerCapabilities>,
+    pub(crate) keepalive_rx: Option<Receiver<Vec<KeepaliveProbe>>>,
+    pub(crate) parked_workspaces: Vec<ConnectionWorkspace>,
+    pub(crate) active_workspace_slot: usize,
     pub(crate) data_backend: Option<MysqlDataBackend>,
     pub(crate) schema_cache: Option<SchemaCacheService<MysqlDataBackend>>,
     pub(crate) schema_databases: Vec<String>,
@@ -42,13 +54,26 @@ pub(crate) struct TuiApp {
     pub(crate) query_ticks_remaining: u8,
     pub(crate) safe_mode_guard: SafeModeGuard,
     pub(crate) pending_confirmation: Option<(ConfirmationToken, String)>,
+    pub(crate) confirmation_phrase: Option<String>,
+    pub(crate) confirmation_input: String,
+    pub(crate) parameter_form: Option<QueryParameterForm>,
+    pub(crate) bound_query: Option<BoundQuery>,
+    pub(crate) transaction_open: bool,
+    pub(crate) transaction_prompt: Option<TransactionExitIntent>,
+    pub(crate) deferred_transaction_exit: Option<TransactionExitIntent>,
     pub(crate) has_results: bool,
     pub(crate) result_columns: Vec<String>,
+    pub(crate) result_column_metadata: Vec<ResultColumn>,
+    pub(crate) result_sets: Vec<ResultSetCapture>,
+    pub(crate) active_result_set: usize,
     pub(crate) results_cursor: usize,
     pub(crate) results_column_cursor: usize,
     pub(crate) results_search_mode: bool,
     pub(crate) results_search_query: String,
-    pub(crate) results: ResultsRingBuffer<QueryRow>,
+    pub(crate) results_search_scan: Option<ResultsSearchScan>,
+    pub(crate) results: Box<dyn ResultsStore<QueryRow> + Send>,
+    pub(crate) parked_primary_results: Option<Box<dyn ResultsStore<QueryRow> + Send>>,
+    pub(crate) reported_spill_error: Option<String>,
     pub(crate) pagination_state: Option<PaginationState>,
     pub(crate) pending_page_transition: Option<PageTransition>,
     pub(crate) cancel_requested: bool,
@@ -57,11 +82,13 @@ pub(crate) struct TuiApp {
     pub(crate) connect_result_rx: Option<Receiver<ConnectWorkerOutcome>>,
     pub(crate) query_result_rx: Option<Receiver<QueryWorkerOutcome>>,
     pub(crate) query_cancellation: Option<CancellationToken>,
+    pub(crate) query_stream_rx: Option<Receiver<QueryRowBatch>>,
+    pub(crate) stream_progress: Option<StreamProgress>,
     pub(crate) active_connection_profile: Option<ConnectionProfile>,
     pub(crate) last_connect_profile: Option<ConnectionProfile>,
     pub(crate) pending_retry_query: Option<String>,
-    pub(crate) reconnect_attempts: u8,
-    pub(crate) query_retry_attempts: u8,
+    pub(crate) reconnect_attempts: u32,
+    pub(crate) query_retry_attempts: u32,
     pub(crate) inflight_query_sql: Option<String>,
     pub(crate) last_failed_query: Option<String>,
     pub(crate) error_panel: Option<ErrorPanel>,
@@ -72,12 +99,14 @@ pub(crate) struct TuiApp {
     pub(crate) audit_trail: Option<FileAuditTrail>,
     pub(crate) bookmark_store: Option<FileBookmarksStore>,
     pub(crate) profile_store: Option<FileProfilesStore>,
+    pub(crate) option_file_path: Option<PathBuf>,
     pub(crate) bookmark_cycle_index: usize,
     pub(crate) manager_lane: ManagerLane,
     pub(crate) manager_profile_cursor: usize,
     pub(crate) manager_bookmark_cursor: usize,
     pub(crate) manager_rename_mode: bool,
     pub(crate) manager_rename_buffer: String,
+    pub(crate) clipboard_request: Option<String>,
     pub(crate) query_editor_text: String,
     pub(crate) query_cursor: usize,
     pub(crate) query_history: Vec<String>,
@@ -1,15 +1,20 @@
 mod app;
 mod navigation;
 mod pagination;
+mod parameters;
 mod runtime;
 mod wizard;
+mod workspace;
 
 pub(crate) use app::TuiApp;
 pub(crate) use navigation::{
-    DirectionKey, ManagerLane, Msg, Pane, SchemaColumnViewMode, SchemaLane,
+    DirectionKey, ManagerLane, Msg, Pane, ResultsSearchScan, SchemaColumnViewMode, SchemaLane,
 };
 pub(crate) use pagination::{PageTransition, PaginationPlan, PaginationState};
+pub(crate) use parameters::{BoundQuery, QueryParameterForm};
 pub(crate) use runtime::{
-    ConnectIntent, ConnectWorkerOutcome, ErrorKind, ErrorPanel, QueryWorkerOutcome,
+    ConnectIntent, ConnectWorkerOutcome, ErrorKind, ErrorPanel, KeepaliveProbe, QueryRowBatch,
+    QueryWorkerOutcome, StreamProgress, TransactionExitIntent, WorkerError,
 };
 pub(crate) use wizard::{ConnectionWizardForm, WizardField};
+pub(crate) use workspace::{ConnectionTab, ConnectionWorkspace};
@@ -19,6 +19,15 @@ impl Pane {
     }
 }
 
+// A results search in progress: the lowercased needle, the row it started from and how
+// many rows have been scanned so far, so large results can be searched a slice per tick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ResultsSearchScan {
+    pub(crate) needle: String,
+    pub(crate) start: usize,
+    pub(crate) scanned: usize,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum SchemaLane {
     Databases,
@@ -113,6 +122,7 @@ pub(crate) enum Msg {
     Quit,
     GoConnectionWizard,
     GoProfileBookmarkManager,
+    NextConnection,
     ToggleHelp,
     NextPane,
     TogglePalette,
@@ -0,0 +1,56 @@
+use super::super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QueryParameterForm {
+    pub(crate) sql: String,
+    pub(crate) query: ParameterizedQuery,
+    pub(crate) values: Vec<String>,
+    pub(crate) active_index: usize,
+}
+
+impl QueryParameterForm {
+    pub(crate) fn new(
+        sql: String,
+        query: ParameterizedQuery,
+        known: &BTreeMap<String, String>,
+    ) -> Self {
+        let values = query
+            .parameter_names()
+            .iter()
+            .map(|name| known.get(name).cloned().unwrap_or_default())
+            .collect();
+        Self {
+            sql,
+            query,
+            values,
+            active_index: 0,
+        }
+    }
+
+    pub(crate) fn active_name(&self) -> &str {
+        self.query
+            .parameter_names()
+            .get(self.active_index)
+            .map_or("", String::as_str)
+    }
+
+    pub(crate) fn active_value_mut(&mut self) -> Option<&mut String> {
+        self.values.get_mut(self.active_index)
+    }
+
+    pub(crate) fn raw_values(&self) -> BTreeMap<String, String> {
+        self.query
+            .parameter_names()
+            .iter()
+            .cloned()
+            .zip(self.values.iter().cloned())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BoundQuery {
+    pub(crate) sql: String,
+    pub(crate) query: ParameterizedQuery,
+    pub(crate) values: BTreeMap<String, String>,
+}
@@ -6,10 +6,20 @@ pub(crate) enum ConnectIntent {
     AutoReconnect,
 }
 
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum TransactionExitIntent {
+    Quit,
+    Connect(ConnectionProfile),
+    CloseConnection,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ErrorKind {
     Connection,
     Query,
+    Warnings,
+    CellValue,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,25 +30,78 @@ pub(crate) struct ErrorPanel {
     pub(crate) detail: String,
 }
 
+// Worker failures keep the MySQL error number next to the message so retries can be
+// decided by `ErrorClass` instead of by what the message happens to say.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WorkerError {
+    pub(crate) message: String,
+    pub(crate) code: Option<u16>,
+}
+
+impl WorkerError {
+    pub(crate) fn new(message: impl Into<String>, code: Option<u16>) -> Self {
+        Self {
+            message: message.into(),
+            code,
+        }
+    }
+
+    pub(crate) fn class(&self) -> ErrorClass {
+        ErrorClass::from_code(self.code)
+    }
+}
+
+impl fmt::Display for WorkerError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.message)
+    }
+}
+
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum ConnectWorkerOutcome {
     Success {
         profile: ConnectionProfile,
+        password: ResolvedPassword,
         connect_latency: Duration,
+        server: Option<ServerCapabilities>,
         databases: Vec<String>,
         warning: Option<String>,
     },
-    Failure(String),
+    Failure(WorkerError),
 }
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub(crate) enum QueryWorkerOutcome {
     Success {
-        results: ResultsRingBuffer<QueryRow>,
+        results: Box<dyn ResultsStore<QueryRow> + Send>,
         rows_streamed: u64,
         was_cancelled: bool,
         elapsed: Duration,
+        columns: Vec<ResultColumn>,
+        status: StatementStatus,
+        additional_result_sets: Vec<ResultSetCapture>,
+        server_cancellation: Option<ServerCancellation>,
+        session_state_lost: bool,
     },
-    Failure(String),
+    Failure(WorkerError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct KeepaliveProbe {
+    pub(crate) profile_name: String,
+    pub(crate) outcome: Result<Duration, String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct QueryRowBatch {
+    pub(crate) columns: Vec<ResultColumn>,
+    pub(crate) rows: Vec<QueryRow>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StreamProgress {
+    pub(crate) started_at: Instant,
+    pub(crate) rows_received: u64,
 }
@@ -1,53 +1,85 @@
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum WizardField {
+    Url,
     ProfileName,
     Host,
     Port,
+    SocketPath,
     User,
     PasswordSource,
+    PasswordFrom,
     Database,
     TlsMode,
     ReadOnly,
+    Environment,
+    SshJump,
+    SshKeyPath,
+    SshKnownHosts,
+    SshAgent,
 }
 
 impl WizardField {
     pub(crate) fn next(self) -> Self {
         match self {
+            Self::Url => Self::ProfileName,
             Self::ProfileName => Self::Host,
             Self::Host => Self::Port,
-            Self::Port => Self::User,
+            Self::Port => Self::SocketPath,
+            Self::SocketPath => Self::User,
             Self::User => Self::PasswordSource,
-            Self::PasswordSource => Self::Database,
+            Self::PasswordSource => Self::PasswordFrom,
+            Self::PasswordFrom => Self::Database,
             Self::Database => Self::TlsMode,
             Self::TlsMode => Self::ReadOnly,
-            Self::ReadOnly => Self::ProfileName,
+            Self::ReadOnly => Self::Environment,
+            Self::Environment => Self::SshJump,
+            Self::SshJump => Self::SshKeyPath,
+            Self::SshKeyPath => Self::SshKnownHosts,
+            Self::SshKnownHosts => Self::SshAgent,
+            Self::SshAgent => Self::Url,
         }
     }
 
     pub(crate) fn label(self) -> &'static str {
         match self {
+            Self::Url => "Paste URL",
             Self::ProfileName => "Profile",
             Self::Host => "Host",
             Self::Port => "Port",
+            Self::SocketPath => "Socket path",
             Self::User => "User",
             Self::PasswordSource => "Password source",
+            Self::PasswordFrom => "Password from",
             Self::Database => "Database",
             Self::TlsMode => "TLS mode",
             Self::ReadOnly => "Read-only",
+            Self::Environment => "Environment",
+            Self::SshJump => "SSH jump",
+            Self::SshKeyPath => "SSH key path",
+            Self::SshKnownHosts => "SSH known_hosts",
+            Self::SshAgent => "SSH agent",
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct ConnectionWizardForm {
+    pub(crate) url: String,
     pub(crate) profile_name: String,
     pub(crate) host: String,
     pub(crate) port: String,
+    pub(crate) socket_path: String,
     pub(crate) user: String,
     pub(crate) password_source: String,
+    pub(crate) password_from: String,
     pub(crate) database: String,
     pub(crate) tls_mode: String,
     pub(crate) read_only: String,
+    pub(crate) environment: String,
+    pub(crate) ssh_jump: String,
+    pub(crate) ssh_key_path: String,
+    pub(crate) ssh_known_hosts: String,
+    pub(crate) ssh_agent: String,
     pub(crate) active_field: WizardField,
     pub(crate) editing: bool,
     pub(crate) edit_buffer: String,
@@ -56,14 +88,22 @@ pub(crate) struct ConnectionWizardForm {
 impl Default for ConnectionWizardForm {
     fn default() -> Self {
         Self {
+            url: String::new(),
             profile_name: "local-dev".to_string(),
             host: "127.0.0.1".to_string(),
             port: "3306".to_string(),
+            socket_path: String::new(),
             user: "root".to_string(),
             password_source: "env".to_string(),
+            password_from: String::new(),
             database: "app".to_string(),
             tls_mode: "prefer".to_string(),
             read_only: "no".to_string(),
+            environment: String::new(),
+            ssh_jump: String::new(),
+            ssh_key_path: String::new(),
+            ssh_known_hosts: "strict".to_string(),
+            ssh_agent: "yes".to_string(),
             active_field: WizardField::ProfileName,
             editing: false,
             edit_buffer: String::new(),
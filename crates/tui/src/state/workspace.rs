@@ -0,0 +1,219 @@
+use std::mem;
+
+use super::super::*;
+use super::app::TuiApp;
+use super::navigation::SchemaLane;
+use super::pagination::PaginationState;
+use super::parameters::BoundQuery;
+
+// Everything that belongs to one open connection. The active connection lives in the
+// matching `TuiApp` fields; the other tabs are parked here and swapped back on switch.
+pub(crate) struct ConnectionWorkspace {
+    pub(crate) connected_profile: Option<String>,
+    pub(crate) active_connection_profile: Option<ConnectionProfile>,
+    pub(crate) last_connection_latency: Option<Duration>,
+    pub(crate) latency_history: LatencyHistory,
+    pub(crate) last_keepalive_at: Option<Instant>,
+    pub(crate) connection_stale: bool,
+    pub(crate) server_capabilities: Option<ServerCapabilities>,
+    pub(crate) data_backend: Option<MysqlDataBackend>,
+    pub(crate) schema_cache: Option<SchemaCacheService<MysqlDataBackend>>,
+    pub(crate) schema_databases: Vec<String>,
+    pub(crate) selected_database_index: usize,
+    pub(crate) active_database: Option<String>,
+    pub(crate) schema_tables: Vec<String>,
+    pub(crate) selected_table_index: usize,
+    pub(crate) schema_columns: Vec<String>,
+    pub(crate) schema_column_schemas: Vec<ColumnSchema>,
+    pub(crate) selected_column_index: usize,
+    pub(crate) schema_relationships: Vec<TableRelationship>,
+    pub(crate) selected_relationship_index: usize,
+    pub(crate) schema_lane: SchemaLane,
+    pub(crate) schema_database_filter: String,
+    pub(crate) schema_table_filter: String,
+    pub(crate) schema_column_filter: String,
+    pub(crate) selection: SchemaSelection,
+    pub(crate) transaction_open: bool,
+    pub(crate) bound_query: Option<BoundQuery>,
+    pub(crate) has_results: bool,
+    pub(crate) result_columns: Vec<String>,
+    pub(crate) result_column_metadata: Vec<ResultColumn>,
+    pub(crate) result_sets: Vec<ResultSetCapture>,
+    pub(crate) active_result_set: usize,
+    pub(crate) results_cursor: usize,
+    pub(crate) results_column_cursor: usize,
+    pub(crate) results_search_query: String,
+    pub(crate) results: Box<dyn ResultsStore<QueryRow> + Send>,
+    pub(crate) parked_primary_results: Option<Box<dyn ResultsStore<QueryRow> + Send>>,
+    pub(crate) pagination_state: Option<PaginationState>,
+    pub(crate) last_failed_query: Option<String>,
+    pub(crate) query_editor_text: String,
+    pub(crate) query_cursor: usize,
+    pub(crate) query_history: Vec<String>,
+    pub(crate) query_history_index: Option<usize>,
+    pub(crate) query_history_draft: Option<String>,
+}
+
+pub(crate) struct ConnectionTab<'a> {
+    pub(crate) profile_name: &'a str,
+    pub(crate) environment: Option<(String, Color)>,
+    pub(crate) read_only: bool,
+    pub(crate) transaction_open: bool,
+    pub(crate) active: bool,
+}
+
+impl Default for ConnectionWorkspace {
+    fn default() -> Self {
+        Self {
+            connected_profile: None,
+            active_connection_profile: None,
+            last_connection_latency: None,
+            latency_history: LatencyHistory::default(),
+            last_keepalive_at: None,
+            connection_stale: false,
+            server_capabilities: None,
+            data_backend: None,
+            schema_cache: None,
+            schema_databases: Vec::new(),
+            selected_database_index: 0,
+            active_database: None,
+            schema_tables: Vec::new(),
+            selected_table_index: 0,
+            schema_columns: Vec::new(),
+            schema_column_schemas: Vec::new(),
+            selected_column_index: 0,
+            schema_relationships: Vec::new(),
+            selected_relationship_index: 0,
+            schema_lane: SchemaLane::Databases,
+            schema_database_filter: String::new(),
+            schema_table_filter: String::new(),
+            schema_column_filter: String::new(),
+            selection: SchemaSelection::default(),
+            transaction_open: false,
+            bound_query: None,
+            has_results: false,
+            result_columns: Vec::new(),
+            result_column_metadata: Vec::new(),
+            result_sets: Vec::new(),
+            active_result_set: 0,
+            results_cursor: 0,
+            results_column_cursor: 0,
+            results_search_query: String::new(),
+            results: Box::new(ResultsRingBuffer::new(RESULT_BUFFER_CAPACITY)),
+            parked_primary_results: None,
+            pagination_state: None,
+            last_failed_query: None,
+            query_editor_text: String::new(),
+            query_cursor: 0,
+            query_history: Vec::new(),
+            query_history_index: None,
+            query_history_draft: None,
+        }
+    }
+}
+
+impl ConnectionWorkspace {
+    pub(crate) fn tab(&self, active: bool) -> ConnectionTab<'_> {
+        ConnectionTab {
+            profile_name: self.connected_profile.as_deref().unwrap_or("-"),
+            environment: self
+                .active_connection_profile
+                .as_ref()
+                .and_then(environment_badge),
+            read_only: self
+                .active_connection_profile
+                .as_ref()
+                .is_some_and(ConnectionProfile::is_read_only),
+            transaction_open: self.transaction_open,
+            active,
+        }
+    }
+
+    pub(crate) fn swap_with(&mut self, app: &mut TuiApp) {
+        mem::swap(&mut self.connected_profile, &mut app.connected_profile);
+        mem::swap(
+            &mut self.active_connection_profile,
+            &mut app.active_connection_profile,
+        );
+        mem::swap(
+            &mut self.last_connection_latency,
+            &mut app.last_connection_latency,
+        );
+        mem::swap(&mut self.latency_history, &mut app.latency_history);
+        mem::swap(&mut self.last_keepalive_at, &mut app.last_keepalive_at);
+        mem::swap(&mut self.connection_stale, &mut app.connection_stale);
+        mem::swap(&mut self.server_capabilities, &mut app.server_capabilities);
+        mem::swap(&mut self.data_backend, &mut app.data_backend);
+        mem::swap(&mut self.schema_cache, &mut app.schema_cache);
+        mem::swap(&mut self.schema_databases, &mut app.schema_databases);
+        mem::swap(
+            &mut self.selected_database_index,
+            &mut app.selected_database_index,
+        );
+        mem::swap(&mut self.active_database, &mut app.active_database);
+        mem::swap(&mut self.schema_tables, &mut app.schema_tables);
+        mem::swap(
+            &mut self.selected_table_index,
+            &mut app.selected_table_index,
+        );
+        mem::swap(&mut self.schema_columns, &mut app.schema_columns);
+        mem::swap(
+            &mut self.schema_column_schemas,
+            &mut app.schema_column_schemas,
+        );
+        mem::swap(
+            &mut self.selected_column_index,
+            &mut app.selected_column_index,
+        );
+        mem::swap(
+            &mut self.schema_relationships,
+            &mut app.schema_relationships,
+        );
+        mem::swap(
+            &mut self.selected_relationship_index,
+            &mut app.selected_relationship_index,
+        );
+        mem::swap(&mut self.schema_lane, &mut app.schema_lane);
+        mem::swap(
+            &mut self.schema_database_filter,
+            &mut app.schema_database_filter,
+        );
+        mem::swap(&mut self.schema_table_filter, &mut app.schema_table_filter);
+        mem::swap(
+            &mut self.schema_column_filter,
+            &mut app.schema_column_filter,
+        );
+        mem::swap(&mut self.selection, &mut app.selection);
+        mem::swap(&mut self.transaction_open, &mut app.transaction_open);
+        mem::swap(&mut self.bound_query, &mut app.bound_query);
+        mem::swap(&mut self.has_results, &mut app.has_results);
+        mem::swap(&mut self.result_columns, &mut app.result_columns);
+        mem::swap(
+            &mut self.result_column_metadata,
+            &mut app.result_column_metadata,
+        );
+        mem::swap(&mut self.result_sets, &mut app.result_sets);
+        mem::swap(&mut self.active_result_set, &mut app.active_result_set);
+        mem::swap(&mut self.results_cursor, &mut app.results_cursor);
+        mem::swap(
+            &mut self.results_column_cursor,
+            &mut app.results_column_cursor,
+        );
+        mem::swap(
+            &mut self.results_search_query,
+            &mut app.results_search_query,
+        );
+        mem::swap(&mut self.results, &mut app.results);
+        mem::swap(
+            &mut self.parked_primary_results,
+            &mut app.parked_primary_results,
+        );
+        mem::swap(&mut self.pagination_state, &mut app.pagination_state);
+        mem::swap(&mut self.last_failed_query, &mut app.last_failed_query);
+        mem::swap(&mut self.query_editor_text, &mut app.query_editor_text);
+        mem::swap(&mut self.query_cursor, &mut app.query_cursor);
+        mem::swap(&mut self.query_history, &mut app.query_history);
+        mem::swap(&mut self.query_history_index, &mut app.query_history_index);
+        mem::swap(&mut self.query_history_draft, &mut app.query_history_draft);
+    }
+}
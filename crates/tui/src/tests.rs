@@ -1,11 +1,21 @@
 use std::fs;
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use myr_core::actions_engine::CopyTarget;
+use myr_core::audit_trail::FileAuditTrail;
 use myr_core::bookmarks::{FileBookmarksStore, SavedBookmark};
-use myr_core::profiles::{ConnectionProfile, FileProfilesStore, PasswordSource, TlsMode};
+use myr_core::profiles::{
+    ConnectionProfile, FileProfilesStore, PasswordSource, ProfileEnvironment, ReplicaEndpoint,
+    SshKnownHosts, TlsMode,
+};
+use myr_core::query_runner::{
+    QueryValue, QueryWarning, ResultColumn, ResultSetCapture, SqlType, StatementStatus,
+};
+use myr_core::retry_policy::{ErrorClass, RetryPolicy};
+use myr_core::safe_mode::TransactionBoundary;
+use myr_core::server_capabilities::{ServerCapabilities, ServerFlavor, ServerVersion};
 use ratatui::backend::TestBackend;
 use ratatui::buffer::Buffer;
 use ratatui::Terminal;
@@ -13,12 +23,14 @@ use tempfile::TempDir;
 
 use super::{
     bookmark_base_name, candidate_key_column, centered_rect, connection_badge_and_marker,
-    extract_key_bounds, is_connection_lost_error, is_transient_query_error, map_key_event,
-    next_bookmark_name, parse_password_source, parse_read_only_flag, parse_tls_mode,
-    quote_identifier, render, suggest_limit_in_editor, wizard_form_from_profile, ActionId,
-    ActionInvocation, AppView, ConnectIntent, DirectionKey, ErrorKind, ManagerLane, Msg,
-    MysqlDataBackend, PaginationPlan, Pane, QueryRow, QueryWorkerOutcome, ResultsRingBuffer,
-    SchemaColumnViewMode, SchemaLane, TuiApp, WizardField, QUERY_DURATION_TICKS, QUERY_RETRY_LIMIT,
+    extract_key_bounds, map_key_event, next_bookmark_name, osc52_copy_sequence,
+    parse_password_source, parse_read_only_flag, parse_ssh_jump, parse_ssh_known_hosts,
+    parse_tls_mode, quote_identifier, render, suggest_limit_in_editor, wizard_form_from_profile,
+    ActionId, ActionInvocation, AppView, ConnectIntent, ConnectWorkerOutcome, DirectionKey,
+    ErrorKind, KeepaliveProbe, ManagerLane, Msg, MysqlDataBackend, PaginationPlan, Pane, QueryRow,
+    QueryRowBatch, QueryWorkerOutcome, ResolvedPassword, ResultsRingBuffer, ResultsStore,
+    SchemaColumnViewMode, SchemaLane, SpillingResultsStore, StreamProgress, TransactionExitIntent,
+    TuiApp, WizardField, WorkerError, QUERY_DURATION_TICKS,
 };
 
 fn app_in_pane(pane: Pane) -> TuiApp {
@@ -233,17 +245,29 @@ fn runtime_and_connection_labels_reflect_state() {
         "127.0.0.1".to_string(),
         "root".to_string(),
     );
-    app.data_backend = Some(MysqlDataBackend::from_profile(&profile));
+    app.data_backend =
+        Some(MysqlDataBackend::from_profile(&profile).expect("backend should build"));
     assert_eq!(app.connection_state_label(), "CONNECTED");
 }
 
 #[test]
-fn query_error_classification_detects_transient_and_disconnect_signals() {
-    assert!(is_transient_query_error("query timed out after 20s"));
-    assert!(is_transient_query_error("Connection reset by peer"));
-    assert!(is_connection_lost_error("Pool was disconnected"));
-    assert!(is_connection_lost_error("server has gone away"));
-    assert!(!is_connection_lost_error("syntax error near `FROM`"));
+fn worker_errors_are_classified_by_error_code_not_message() {
+    assert_eq!(
+        WorkerError::new("Deadlock found when trying to get lock", Some(1213)).class(),
+        ErrorClass::Transient
+    );
+    assert_eq!(
+        WorkerError::new("Pool was disconnected", Some(2006)).class(),
+        ErrorClass::ConnectionLost
+    );
+    assert_eq!(
+        WorkerError::new("server has gone away", None).class(),
+        ErrorClass::Permanent
+    );
+    assert_eq!(
+        WorkerError::new("You have an error in your SQL syntax", Some(1064)).class(),
+        ErrorClass::Permanent
+    );
 }
 
 #[test]
@@ -370,6 +394,14 @@ fn password_source_parser_accepts_common_values() {
         parse_password_source("KEYRING"),
         Some(PasswordSource::Keyring)
     ));
+    assert!(matches!(
+        parse_password_source("command"),
+        Some(PasswordSource::Command)
+    ));
+    assert!(matches!(
+        parse_password_source("file"),
+        Some(PasswordSource::File)
+    ));
     assert_eq!(parse_password_source("vault"), None);
 }
 
@@ -412,6 +444,150 @@ fn wizard_form_conversion_uses_profile_values() {
     assert!(form.edit_buffer.is_empty());
 }
 
+#[test]
+fn wizard_profile_keeps_saved_settings_the_wizard_does_not_edit() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::ConnectionWizard, &temp_dir);
+    let mut saved = ConnectionProfile::new("analytics", "10.0.0.8", "reporter");
+    saved.query_timeout_secs = Some(600);
+    saved.max_execution_time_hint = true;
+    app.profile_store
+        .as_mut()
+        .expect("profile store")
+        .upsert_profile(saved.clone());
+
+    app.wizard_form = wizard_form_from_profile(&saved);
+    app.wizard_form.host = "10.0.0.9".to_string();
+    let profile = app.wizard_profile().expect("wizard profile should build");
+
+    assert_eq!(profile.host, "10.0.0.9");
+    assert_eq!(profile.query_timeout(), Some(Duration::from_secs(600)));
+    assert!(profile.max_execution_time_hint);
+}
+
+#[test]
+fn wizard_password_from_field_follows_the_password_source() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::ConnectionWizard, &temp_dir);
+    let mut saved = ConnectionProfile::new("vault", "10.0.0.8", "app");
+    saved.password_source = PasswordSource::File;
+    saved.password_file = Some("~/.config/myr/vault.pass".to_string());
+    app.profile_store
+        .as_mut()
+        .expect("profile store")
+        .upsert_profile(saved.clone());
+
+    app.wizard_form = wizard_form_from_profile(&saved);
+    assert_eq!(app.wizard_form.password_source, "file");
+    assert_eq!(app.wizard_form.password_from, "~/.config/myr/vault.pass");
+
+    app.wizard_form.password_source = "command".to_string();
+    app.wizard_form.password_from = "pass show db/app".to_string();
+    let profile = app.wizard_profile().expect("wizard profile should build");
+    assert_eq!(profile.password_source, PasswordSource::Command);
+    assert_eq!(
+        profile.password_command.as_deref(),
+        Some("pass show db/app")
+    );
+    assert_eq!(
+        profile.password_file.as_deref(),
+        Some("~/.config/myr/vault.pass")
+    );
+
+    app.wizard_form.password_source = "env".to_string();
+    app.wizard_form.password_from = "APP_DB_PASSWORD".to_string();
+    let profile = app.wizard_profile().expect("wizard profile should build");
+    assert_eq!(profile.password_env_var(), "APP_DB_PASSWORD");
+
+    app.wizard_form.password_source = "command".to_string();
+    app.wizard_form.password_from.clear();
+    assert_eq!(
+        app.wizard_profile().expect_err("command is required"),
+        "Password command is required when password source is command"
+    );
+}
+
+#[test]
+fn ssh_jump_field_accepts_user_host_and_port() {
+    assert!(matches!(parse_ssh_jump("  "), Some(None)));
+    let jump = parse_ssh_jump("ops@bastion.example.com:2222")
+        .flatten()
+        .expect("jump should parse");
+    assert_eq!(jump.host, "bastion.example.com");
+    assert_eq!(jump.port, Some(2222));
+    assert_eq!(jump.user.as_deref(), Some("ops"));
+
+    let jump = parse_ssh_jump("[fd00::1]").flatten().expect("v6 jump");
+    assert_eq!(jump.host, "fd00::1");
+    assert_eq!(jump.port, None);
+    assert!(jump.user.is_none());
+
+    assert!(parse_ssh_jump("bastion:ssh").is_none());
+    assert!(parse_ssh_jump("@bastion").is_none());
+    assert_eq!(
+        parse_ssh_known_hosts("accept-new"),
+        Some(SshKnownHosts::AcceptNew)
+    );
+    assert_eq!(parse_ssh_known_hosts("sometimes"), None);
+}
+
+#[test]
+fn wizard_ssh_fields_round_trip_through_the_profile() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::ConnectionWizard, &temp_dir);
+    app.wizard_form.ssh_jump = "ops@bastion.example.com:2222".to_string();
+    app.wizard_form.ssh_key_path = "~/.ssh/id_ed25519".to_string();
+    app.wizard_form.ssh_known_hosts = "accept_new".to_string();
+    app.wizard_form.ssh_agent = "no".to_string();
+
+    let profile = app.wizard_profile().expect("wizard profile should build");
+    assert_eq!(profile.ssh_jump_host(), Some("bastion.example.com"));
+    assert_eq!(profile.ssh_port(), 2222);
+    assert_eq!(profile.ssh_user.as_deref(), Some("ops"));
+    assert_eq!(profile.ssh_key_path.as_deref(), Some("~/.ssh/id_ed25519"));
+    assert_eq!(profile.ssh_known_hosts, SshKnownHosts::AcceptNew);
+    assert!(profile.ssh_disable_agent);
+
+    let form = wizard_form_from_profile(&profile);
+    assert_eq!(form.ssh_jump, "ops@bastion.example.com:2222");
+    assert_eq!(form.ssh_known_hosts, "accept_new");
+    assert_eq!(form.ssh_agent, "no");
+
+    app.wizard_form.ssh_jump.clear();
+    let direct = app.wizard_profile().expect("direct profile should build");
+    assert_eq!(direct.ssh_jump_host(), None);
+    assert!(direct.ssh_user.is_none());
+
+    app.wizard_form.ssh_jump = "bastion:ssh".to_string();
+    assert_eq!(
+        app.wizard_profile()
+            .expect_err("bad port should be rejected"),
+        "Invalid SSH jump in connection wizard (use user@host:port or leave blank)"
+    );
+}
+
+#[test]
+fn wizard_socket_path_switches_the_profile_transport() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::ConnectionWizard, &temp_dir);
+    app.wizard_form.active_field = WizardField::Port;
+    app.handle(Msg::Navigate(DirectionKey::Down));
+    assert_eq!(app.wizard_form.active_field, WizardField::SocketPath);
+
+    app.wizard_form.socket_path = " /tmp/mysql.sock ".to_string();
+    let profile = app.wizard_profile().expect("wizard profile should build");
+    assert_eq!(profile.socket_path(), Some("/tmp/mysql.sock"));
+    assert_eq!(profile.transport().kind(), "socket");
+    assert_eq!(
+        wizard_form_from_profile(&profile).socket_path,
+        "/tmp/mysql.sock"
+    );
+
+    app.wizard_form.socket_path.clear();
+    let profile = app.wizard_profile().expect("wizard profile should build");
+    assert!(profile.socket_path.is_none());
+}
+
 #[test]
 fn limit_suggestion_is_applied_in_editor_helper() {
     let suggested = suggest_limit_in_editor("SELECT * FROM users");
@@ -626,6 +802,88 @@ fn save_and_open_bookmark_paths_round_trip() {
     assert!(app.status_line.starts_with("Opened bookmark"));
 }
 
+#[test]
+fn placeholder_query_prompts_for_parameters_before_running() {
+    let mut app = app_in_pane(Pane::QueryEditor);
+    let sql = "SELECT * FROM users WHERE id = :id AND email = ?";
+    app.query_editor_text = sql.to_string();
+
+    app.handle(Msg::Submit);
+    assert!(!app.query_running);
+    let form = app
+        .parameter_form
+        .as_ref()
+        .expect("parameter form should open");
+    assert_eq!(form.query.parameter_names(), [":id", "?1"]);
+
+    app.handle(Msg::InvokeActionSlot(3));
+    app.handle(Msg::InvokeActionSlot(1));
+    app.handle(Msg::NextPane);
+    for ch in "a@example.com".chars() {
+        app.handle(Msg::InputChar(ch));
+    }
+    app.handle(Msg::Submit);
+
+    assert!(app.parameter_form.is_none());
+    assert!(app.query_running);
+    assert_eq!(app.query_editor_text, sql);
+    assert_eq!(
+        app.bound_parameters_for(sql),
+        Some((
+            "SELECT * FROM users WHERE id = ? AND email = ?".to_string(),
+            vec![QueryValue::Int(42), QueryValue::from("a@example.com")],
+        ))
+    );
+
+    app.query_running = false;
+    app.pane = Pane::QueryEditor;
+    app.handle(Msg::Submit);
+    assert!(app.parameter_form.is_some());
+    app.handle(Msg::TogglePalette);
+    assert!(app.parameter_form.is_none());
+    assert_eq!(app.status_line, "Query parameters canceled");
+}
+
+#[test]
+fn bookmarked_queries_remember_last_parameter_values() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_bookmark_store(Pane::QueryEditor, &temp_dir);
+    let sql = "SELECT * FROM `app`.`events` WHERE user_id = :user_id";
+    let mut bookmark = SavedBookmark::new("events-by-user");
+    bookmark.query = Some(sql.to_string());
+    {
+        let store = app.bookmark_store.as_mut().expect("bookmark store");
+        store.upsert_bookmark(bookmark);
+        store.persist().expect("persist bookmark store");
+    }
+
+    app.open_next_bookmark();
+    app.handle(Msg::Submit);
+    assert_eq!(
+        app.parameter_form.as_ref().map(|form| form.values.clone()),
+        Some(vec![String::new()])
+    );
+    app.handle(Msg::InputChar('9'));
+    app.handle(Msg::Submit);
+
+    let reloaded =
+        FileBookmarksStore::load_from_path(temp_dir.path().join("bookmarks.toml")).expect("reload");
+    let remembered = &reloaded
+        .bookmark("events-by-user")
+        .expect("bookmark should persist")
+        .parameters;
+    assert_eq!(remembered.get(":user_id").map(String::as_str), Some("9"));
+
+    app.bound_query = None;
+    app.query_running = false;
+    app.pane = Pane::QueryEditor;
+    app.handle(Msg::Submit);
+    assert_eq!(
+        app.parameter_form.as_ref().map(|form| form.values.clone()),
+        Some(vec!["9".to_string()])
+    );
+}
+
 #[test]
 fn manager_can_open_and_delete_profiles_and_bookmarks() {
     let temp_dir = TempDir::new().expect("failed to create temp dir");
@@ -771,6 +1029,74 @@ fn manager_shortcuts_mark_default_and_quick_reconnect_profiles() {
     );
 }
 
+#[test]
+fn manager_copy_url_shortcut_requests_clipboard_without_password() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::SchemaExplorer, &temp_dir);
+
+    let mut alpha = ConnectionProfile::new("alpha", "db.internal", "app");
+    alpha.port = 3307;
+    alpha.database = Some("shop".to_string());
+    alpha.password_source = PasswordSource::Keyring;
+    {
+        let store = app.profile_store.as_mut().expect("profile store");
+        store.upsert_profile(alpha);
+        store.persist().expect("persist profile store");
+    }
+
+    app.handle(Msg::GoProfileBookmarkManager);
+    app.handle(Msg::InputChar('u'));
+
+    assert_eq!(
+        app.clipboard_request.as_deref(),
+        Some("mysql://app@db.internal:3307/shop")
+    );
+    assert!(app.status_line.contains("Copied URL for `alpha`"));
+    assert_eq!(osc52_copy_sequence("hi"), "\x1b]52;c;aGk=\x07");
+}
+
+#[test]
+fn manager_imports_option_file_profiles_skipping_or_overwriting_existing() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::SchemaExplorer, &temp_dir);
+    let option_file = temp_dir.path().join("my.cnf");
+    fs::write(
+        &option_file,
+        "[client]\nuser=app\npassword=secret\n\n[mysqld]\nport=3309\n\n[prod]\nhost=db.prod\n",
+    )
+    .expect("write option file");
+    app.option_file_path = Some(option_file);
+    {
+        let store = app.profile_store.as_mut().expect("profile store");
+        store.upsert_profile(ConnectionProfile::new("prod", "old.prod", "root"));
+        store.persist().expect("persist profile store");
+    }
+
+    app.handle(Msg::GoProfileBookmarkManager);
+    app.handle(Msg::InputChar('i'));
+
+    assert!(app.status_line.contains("1 added, skipped existing prod"));
+    assert!(app.status_line.contains("password not imported"));
+    let store = app.profile_store.as_ref().expect("profile store");
+    assert_eq!(
+        store.profile("client").map(|profile| profile.user.as_str()),
+        Some("app")
+    );
+    assert_eq!(
+        store.profile("prod").map(|profile| profile.host.as_str()),
+        Some("old.prod")
+    );
+
+    app.handle(Msg::InputChar('o'));
+
+    assert!(app.status_line.contains("2 overwritten"));
+    let store = app.profile_store.as_ref().expect("profile store");
+    assert_eq!(
+        store.profile("prod").map(|profile| profile.host.as_str()),
+        Some("db.prod")
+    );
+}
+
 #[test]
 fn manager_connect_prefers_quick_reconnect_profile_from_bookmarks_lane() {
     let temp_dir = TempDir::new().expect("failed to create temp dir");
@@ -875,6 +1201,61 @@ fn destructive_submit_requires_confirmation_then_executes() {
     assert!(app.has_results);
 }
 
+#[test]
+fn prod_profile_forces_safe_mode_and_a_typed_confirmation() {
+    let mut app = app_in_pane(Pane::QueryEditor);
+    app.safe_mode_guard.set_enabled(false);
+    let mut prod = ConnectionProfile::new("prod", "127.0.0.1", "root");
+    prod.environment = Some(ProfileEnvironment::Prod);
+    app.apply_connected_profile(
+        prod.clone(),
+        &ResolvedPassword::default(),
+        Duration::from_millis(1),
+        Vec::new(),
+        None,
+    );
+    assert!(app.safe_mode_guard.is_enabled());
+    assert!(render_snapshot(&app).contains(" PROD "));
+
+    app.handle(Msg::ToggleSafeMode);
+    assert!(app.safe_mode_guard.is_enabled());
+    assert_eq!(
+        app.status_line,
+        "Safe mode stays on for prod profile `prod`"
+    );
+
+    app.pane = Pane::QueryEditor;
+    app.query_editor_text = "DELETE FROM `app`.`users` WHERE id = 1".to_string();
+    app.submit();
+    assert_eq!(
+        app.status_line,
+        "Blocked by read-only profile mode: write/DDL SQL is disabled"
+    );
+
+    prod.allow_writes = true;
+    app.active_connection_profile = Some(prod);
+    app.submit();
+    assert!(app.pending_confirmation.is_some());
+    assert!(app.status_line.contains("Type `prod` and press Enter"));
+
+    app.submit();
+    assert!(!app.query_running);
+    assert!(app.status_line.contains("does not match `prod`"));
+
+    for ch in "prox".chars() {
+        app.handle(Msg::InputChar(ch));
+    }
+    app.handle(Msg::Backspace);
+    app.handle(Msg::InputChar('d'));
+    assert_eq!(
+        app.query_editor_text,
+        "DELETE FROM `app`.`users` WHERE id = 1"
+    );
+    app.submit();
+    assert!(app.query_running);
+    assert!(app.pending_confirmation.is_none());
+}
+
 #[test]
 fn read_only_profile_blocks_destructive_submit() {
     let mut app = app_in_pane(Pane::QueryEditor);
@@ -947,95 +1328,523 @@ fn read_only_profile_blocks_mixed_read_write_submit() {
 }
 
 #[test]
-fn query_failure_retries_once_when_transient() {
+fn statement_success_reports_affected_rows_and_opens_warnings_panel() {
     let mut app = app_in_pane(Pane::QueryEditor);
     app.query_running = true;
-    app.inflight_query_sql = Some("SELECT 1".to_string());
+    app.inflight_query_sql = Some("UPDATE users SET name = 'x'".to_string());
 
     let (tx, rx) = std::sync::mpsc::channel();
     app.query_result_rx = Some(rx);
-    tx.send(QueryWorkerOutcome::Failure(
-        "connection reset by peer".to_string(),
-    ))
-    .expect("send test query failure");
+    tx.send(QueryWorkerOutcome::Success {
+        results: Box::new(ResultsRingBuffer::new(8)),
+        rows_streamed: 0,
+        was_cancelled: false,
+        elapsed: Duration::from_millis(5),
+        columns: Vec::new(),
+        status: StatementStatus {
+            affected_rows: 2,
+            last_insert_id: None,
+            info: Some("Rows matched: 2  Changed: 2  Warnings: 1".to_string()),
+            warning_count: 1,
+            warnings: vec![QueryWarning {
+                level: "Warning".to_string(),
+                code: 1265,
+                message: "Data truncated for column 'name' at row 1".to_string(),
+            }],
+        },
+        additional_result_sets: Vec::new(),
+        server_cancellation: None,
+        session_state_lost: true,
+    })
+    .expect("send statement success");
 
     app.poll_query_result();
 
-    assert!(app.query_running);
-    assert_eq!(app.query_retry_attempts, 1);
-    assert!(app.status_line.starts_with("Running query"));
+    assert!(!app.query_running);
+    assert!(app
+        .status_line
+        .starts_with("Query OK, 2 rows affected, 1 warning, Rows matched: 2"));
+    assert!(app.status_line.contains("pinned session reconnected"));
+    let panel = app
+        .error_panel
+        .as_ref()
+        .expect("warnings panel should open");
+    assert_eq!(panel.kind, ErrorKind::Warnings);
+    assert!(panel.detail.contains("Warning 1265: Data truncated"));
+
+    app.handle(Msg::Submit);
+    assert!(app.error_panel.is_none());
 }
 
 #[test]
-fn query_timeout_failure_retries_once_when_not_cancelled() {
-    let mut app = app_in_pane(Pane::QueryEditor);
+fn streamed_row_batches_show_live_and_survive_cancellation() {
+    let mut app = app_in_pane(Pane::Results);
     app.query_running = true;
-    app.inflight_query_sql = Some("SELECT 1".to_string());
-
+    app.inflight_query_sql = Some("SELECT id FROM events".to_string());
+    app.results = Box::new(ResultsRingBuffer::new(8));
+    app.stream_progress = Some(StreamProgress {
+        started_at: Instant::now(),
+        rows_received: 0,
+    });
+    let (batch_tx, batch_rx) = std::sync::mpsc::channel();
+    app.query_stream_rx = Some(batch_rx);
     let (tx, rx) = std::sync::mpsc::channel();
     app.query_result_rx = Some(rx);
-    tx.send(QueryWorkerOutcome::Failure(
-        "query timed out after 20s".to_string(),
-    ))
-    .expect("send timeout query failure");
 
+    let columns = vec![ResultColumn::new("id", SqlType::BigInt)];
+    batch_tx
+        .send(QueryRowBatch {
+            columns: columns.clone(),
+            rows: vec![
+                QueryRow::new(vec![QueryValue::Int(1)]),
+                QueryRow::new(vec![QueryValue::Int(2)]),
+            ],
+        })
+        .expect("send first batch");
     app.poll_query_result();
 
     assert!(app.query_running);
-    assert_eq!(app.query_retry_attempts, 1);
-    assert!(app.status_line.starts_with("Running query"));
+    assert!(app.has_results);
+    assert_eq!(app.results.len(), 2);
+    assert_eq!(app.result_columns, vec!["id".to_string()]);
+    assert!(render_snapshot(&app).contains("Streaming: 2 rows"));
+
+    batch_tx
+        .send(QueryRowBatch {
+            columns: columns.clone(),
+            rows: vec![QueryRow::new(vec![QueryValue::Int(3)])],
+        })
+        .expect("send second batch");
+    app.poll_query_result();
+    app.results_cursor = 2;
+    assert_eq!(app.stream_progress.map(|p| p.rows_received), Some(3));
+
+    app.handle(Msg::CancelQuery);
+    let mut results = ResultsRingBuffer::new(8);
+    for id in 1..=3 {
+        results.push(QueryRow::new(vec![QueryValue::Int(id)]));
+    }
+    tx.send(QueryWorkerOutcome::Success {
+        results: Box::new(results),
+        rows_streamed: 3,
+        was_cancelled: true,
+        elapsed: Duration::from_millis(40),
+        columns,
+        status: StatementStatus::default(),
+        additional_result_sets: Vec::new(),
+        server_cancellation: None,
+        session_state_lost: false,
+    })
+    .expect("send cancelled outcome");
+    app.poll_query_result();
+
+    assert!(!app.query_running);
+    assert!(app.stream_progress.is_none());
+    assert!(app.query_stream_rx.is_none());
+    assert_eq!(app.results.len(), 3);
+    assert_eq!(app.results_cursor, 2);
+    assert!(app.status_line.starts_with("Query cancelled after 3 rows"));
+    assert!(!render_snapshot(&app).contains("Streaming:"));
 }
 
 #[test]
-fn query_failure_does_not_retry_when_cancel_was_requested() {
-    let mut app = app_in_pane(Pane::QueryEditor);
+fn spilled_results_stay_reachable_for_search_and_export() {
+    let mut app = app_in_pane(Pane::Results);
     app.query_running = true;
-    app.cancel_requested = true;
-    app.inflight_query_sql = Some("SELECT 1".to_string());
-
+    app.inflight_query_sql = Some("SELECT id, label FROM events".to_string());
+
+    let mut results = SpillingResultsStore::new(4);
+    for id in 1..=10 {
+        results.push(QueryRow::new(vec![
+            QueryValue::Int(id),
+            QueryValue::from(format!("event-{id}")),
+        ]));
+    }
     let (tx, rx) = std::sync::mpsc::channel();
     app.query_result_rx = Some(rx);
-    tx.send(QueryWorkerOutcome::Failure(
-        "connection reset by peer".to_string(),
-    ))
-    .expect("send query failure while cancelling");
-
+    tx.send(QueryWorkerOutcome::Success {
+        results: Box::new(results),
+        rows_streamed: 10,
+        was_cancelled: false,
+        elapsed: Duration::from_millis(5),
+        columns: vec![
+            ResultColumn::new("id", SqlType::BigInt),
+            ResultColumn::new("label", SqlType::VarChar),
+        ],
+        status: StatementStatus::default(),
+        additional_result_sets: Vec::new(),
+        server_cancellation: None,
+        session_state_lost: false,
+    })
+    .expect("send spilled outcome");
     app.poll_query_result();
 
-    assert!(!app.query_running);
-    assert_eq!(app.query_retry_attempts, 0);
-    assert_eq!(app.status_line, "Query failed: connection reset by peer");
+    assert_eq!(app.results.len(), 10);
+    assert_eq!(app.results.capacity(), 4);
+    assert_eq!(app.status_line, "Query returned 10 rows in 5.0ms");
+
+    app.results_cursor = 9;
+    app.apply_invocation(
+        ActionId::SearchResults,
+        ActionInvocation::SearchBufferedResults,
+    );
+    for ch in "event-2".chars() {
+        app.handle(Msg::InputChar(ch));
+    }
+    assert_eq!(app.results_cursor, 1);
+    app.handle(Msg::TogglePalette);
+
+    app.export_results(myr_core::actions_engine::ExportFormat::Csv);
+    assert!(
+        app.status_line.starts_with("Exported 10 rows"),
+        "unexpected export status: {}",
+        app.status_line
+    );
 }
 
 #[test]
-fn query_failure_starts_auto_reconnect_when_connection_is_lost() {
-    let mut app = app_in_pane(Pane::QueryEditor);
-    let profile = ConnectionProfile::new(
-        "local".to_string(),
-        "127.0.0.1".to_string(),
-        "root".to_string(),
-    );
-    app.data_backend = Some(MysqlDataBackend::from_profile(&profile));
-    app.active_connection_profile = Some(profile.clone());
-    app.last_connect_profile = Some(profile);
+fn truncated_cells_open_full_value_panel_and_report_budget() {
+    let mut app = app_in_pane(Pane::Results);
     app.query_running = true;
-    app.query_retry_attempts = QUERY_RETRY_LIMIT;
-    app.inflight_query_sql = Some("SELECT 1".to_string());
-
+    app.inflight_query_sql = Some("SELECT id, body FROM documents".to_string());
+
+    let body = "lorem ipsum ".repeat(64);
+    let mut results = SpillingResultsStore::new(8).with_byte_budget(64 * 1024, 32);
+    results.push(QueryRow::new(vec![
+        QueryValue::Int(1),
+        QueryValue::from(body.clone()),
+    ]));
     let (tx, rx) = std::sync::mpsc::channel();
     app.query_result_rx = Some(rx);
-    tx.send(QueryWorkerOutcome::Failure(
-        "Pool was disconnected".to_string(),
-    ))
-    .expect("send disconnect failure");
-
+    tx.send(QueryWorkerOutcome::Success {
+        results: Box::new(results),
+        rows_streamed: 1,
+        was_cancelled: false,
+        elapsed: Duration::from_millis(5),
+        columns: vec![
+            ResultColumn::new("id", SqlType::BigInt),
+            ResultColumn::new("body", SqlType::VarChar),
+        ],
+        status: StatementStatus::default(),
+        additional_result_sets: Vec::new(),
+        server_cancellation: None,
+        session_state_lost: false,
+    })
+    .expect("send truncated outcome");
     app.poll_query_result();
 
-    assert!(app.connect_requested);
-    assert_eq!(app.connect_intent, ConnectIntent::AutoReconnect);
+    let shown = app.results.row(0).expect("row should be buffered");
+    assert!(shown.values[1].is_truncated());
+
+    app.handle(Msg::Submit);
+    assert!(app.error_panel.is_none());
+    assert_eq!(app.status_line, "Nothing to submit in this view");
+
+    app.results_column_cursor = 1;
+    app.handle(Msg::Submit);
+    let panel = app.error_panel.as_ref().expect("full value panel");
+    assert_eq!(panel.kind, ErrorKind::CellValue);
+    assert_eq!(panel.title, "Full value: body");
+    assert_eq!(panel.detail, body);
+
+    app.handle(Msg::Submit);
+    assert!(app.error_panel.is_none());
+    assert_eq!(app.status_line, "Full value panel dismissed");
+
+    app.show_perf_overlay = true;
+    let screen = render_snapshot(&app);
+    assert!(screen.contains("Truncated cells: 1"), "{screen}");
+    assert!(screen.contains("Rows in memory: 1"), "{screen}");
+}
+
+#[test]
+fn next_result_set_action_cycles_through_captured_result_sets() {
+    let mut app = app_in_pane(Pane::Results);
+    app.query_running = true;
+    app.inflight_query_sql = Some("CALL list_users()".to_string());
+
+    let mut first_rows = ResultsRingBuffer::new(8);
+    first_rows.push(QueryRow::new(vec![QueryValue::Int(1)]));
+    let mut second_rows = ResultsRingBuffer::new(8);
+    second_rows.push(QueryRow::new(vec!["a@example.com"]));
+    second_rows.push(QueryRow::new(vec!["b@example.com"]));
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.query_result_rx = Some(rx);
+    tx.send(QueryWorkerOutcome::Success {
+        results: Box::new(first_rows),
+        rows_streamed: 1,
+        was_cancelled: false,
+        elapsed: Duration::from_millis(5),
+        columns: vec![ResultColumn::new("id", SqlType::BigInt)],
+        status: StatementStatus::default(),
+        additional_result_sets: vec![
+            ResultSetCapture {
+                columns: vec![ResultColumn::new("email", SqlType::VarChar)],
+                rows: second_rows,
+                rows_streamed: 2,
+                status: StatementStatus::default(),
+            },
+            ResultSetCapture {
+                columns: Vec::new(),
+                rows: ResultsRingBuffer::new(8),
+                rows_streamed: 0,
+                status: StatementStatus {
+                    affected_rows: 0,
+                    ..StatementStatus::default()
+                },
+            },
+        ],
+        server_cancellation: None,
+        session_state_lost: false,
+    })
+    .expect("send multi result set success");
+
+    app.poll_query_result();
+    assert_eq!(app.result_sets.len(), 3);
+    assert!(app.status_line.ends_with("[result set 1 of 3]"));
+    assert!(app.action_context().has_multiple_result_sets);
+
+    app.invoke_action(ActionId::NextResultSet);
+    assert_eq!(app.active_result_set, 1);
+    assert_eq!(app.result_columns, vec!["email".to_string()]);
+    assert_eq!(app.results.len(), 2);
+    assert_eq!(app.status_line, "Result set 2 of 3: 2 rows");
+
+    app.invoke_action(ActionId::NextResultSet);
+    assert!(app.result_columns.is_empty());
+    assert!(!app.has_results);
+    assert_eq!(app.status_line, "Result set 3 of 3: 0 rows affected");
+
+    app.invoke_action(ActionId::NextResultSet);
+    assert_eq!(app.active_result_set, 0);
+    assert_eq!(app.result_columns, vec!["id".to_string()]);
+    assert_eq!(
+        app.results.row(0).map(|row| row.values[0].clone()),
+        Some(QueryValue::Int(1))
+    );
+}
+
+#[test]
+fn transaction_workflow_commits_before_quitting_and_audits_boundaries() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let audit_path = temp_dir.path().join("audit.ndjson");
+    let mut app = app_in_pane(Pane::QueryEditor);
+    app.audit_trail = Some(FileAuditTrail::from_path(&audit_path));
+
+    app.apply_invocation(
+        ActionId::BeginTransaction,
+        ActionInvocation::TransactionControl(TransactionBoundary::Begin),
+    );
+    assert_eq!(app.query_editor_text, "START TRANSACTION");
+    drive_demo_query_to_completion(&mut app);
+    assert!(app.transaction_open);
+    assert!(app.action_context().transaction_open);
+    assert!(render_snapshot(&app).contains("TX OPEN"));
+
+    app.handle(Msg::Quit);
+    assert!(!app.should_quit);
+    assert!(app.transaction_prompt.is_some());
+    assert!(render_snapshot(&app).contains("Open Transaction"));
+
+    app.handle(Msg::InputChar('c'));
+    assert!(app.transaction_prompt.is_none());
+    assert_eq!(app.inflight_query_sql.as_deref(), Some("COMMIT"));
+    drive_demo_query_to_completion(&mut app);
+    assert!(!app.transaction_open);
+    assert!(app.should_quit);
+    assert!(!render_snapshot(&app).contains("TX OPEN"));
+
+    let audit = fs::read_to_string(&audit_path).expect("audit file should exist");
+    let boundaries = audit
+        .lines()
+        .filter(|line| line.contains("\"outcome\":\"succeeded\""))
+        .map(|line| {
+            line.contains("\"transaction\":\"begin\"")
+                || line.contains("\"transaction\":\"commit\"")
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(boundaries, vec![true, true]);
+}
+
+#[test]
+fn profile_switch_with_open_transaction_prompts_for_rollback() {
+    let mut app = app_in_pane(Pane::ConnectionWizard);
+    app.wizard_form.host = "127.0.0.1".to_string();
+    app.wizard_form.port = "1".to_string();
+    app.wizard_form.user = "root".to_string();
+    app.transaction_open = true;
+
+    app.handle(Msg::Connect);
+    assert!(!app.connect_requested);
+    assert!(app.transaction_prompt.is_some());
+
+    app.handle(Msg::TogglePalette);
+    assert!(app.transaction_prompt.is_none());
+    assert!(app.transaction_open);
+    assert_eq!(app.status_line, "Transaction still open");
+
+    app.handle(Msg::Connect);
+    app.handle(Msg::InputChar('r'));
+    assert_eq!(app.inflight_query_sql.as_deref(), Some("ROLLBACK"));
+    drive_demo_query_to_completion(&mut app);
+    assert!(!app.transaction_open);
+    assert!(app.connect_requested);
+
+    drive_connect_to_completion(&mut app);
+    assert!(app.status_line.starts_with("Connect failed:"));
+}
+
+#[test]
+fn query_failure_retries_once_when_transient() {
+    let mut app = app_in_pane(Pane::QueryEditor);
+    app.query_running = true;
+    app.inflight_query_sql = Some("SELECT 1".to_string());
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.query_result_rx = Some(rx);
+    tx.send(QueryWorkerOutcome::Failure(WorkerError::new(
+        "Deadlock found when trying to get lock",
+        Some(1213),
+    )))
+    .expect("send test query failure");
+
+    app.poll_query_result();
+
+    assert!(app.query_running);
+    assert_eq!(app.query_retry_attempts, 1);
+    assert!(app
+        .status_line
+        .starts_with("Transient query failure (error 1213); retrying in"));
+    assert!(app.status_line.ends_with("(1/1)..."));
+}
+
+#[test]
+fn transient_query_failure_respects_the_profile_retry_budget() {
+    let mut app = app_in_pane(Pane::QueryEditor);
+    let mut profile = ConnectionProfile::new("local", "127.0.0.1", "root");
+    profile.query_retries = Some(0);
+    app.active_connection_profile = Some(profile);
+    app.query_running = true;
+    app.inflight_query_sql = Some("SELECT 1".to_string());
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.query_result_rx = Some(rx);
+    tx.send(QueryWorkerOutcome::Failure(WorkerError::new(
+        "Lock wait timeout exceeded",
+        Some(1205),
+    )))
+    .expect("send lock wait failure");
+
+    app.poll_query_result();
+
+    assert!(!app.query_running);
+    assert_eq!(app.query_retry_attempts, 0);
+    assert_eq!(app.status_line, "Query failed: Lock wait timeout exceeded");
+}
+
+#[test]
+fn query_timeout_failure_is_not_retried() {
+    let mut app = app_in_pane(Pane::QueryEditor);
+    app.query_running = true;
+    app.inflight_query_sql = Some("SELECT 1".to_string());
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.query_result_rx = Some(rx);
+    tx.send(QueryWorkerOutcome::Failure(WorkerError::new(
+        "query timed out after 20s",
+        None,
+    )))
+    .expect("send timeout query failure");
+
+    app.poll_query_result();
+
+    assert!(!app.query_running);
+    assert_eq!(app.query_retry_attempts, 0);
+    assert_eq!(app.status_line, "Query failed: query timed out after 20s");
+}
+
+#[test]
+fn query_failure_does_not_retry_when_cancel_was_requested() {
+    let mut app = app_in_pane(Pane::QueryEditor);
+    app.query_running = true;
+    app.cancel_requested = true;
+    app.inflight_query_sql = Some("SELECT 1".to_string());
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.query_result_rx = Some(rx);
+    tx.send(QueryWorkerOutcome::Failure(WorkerError::new(
+        "connection reset by peer",
+        Some(2013),
+    )))
+    .expect("send query failure while cancelling");
+
+    app.poll_query_result();
+
+    assert!(!app.query_running);
+    assert_eq!(app.query_retry_attempts, 0);
+    assert_eq!(app.status_line, "Query failed: connection reset by peer");
+}
+
+#[test]
+fn query_failure_starts_auto_reconnect_when_connection_is_lost() {
+    let mut app = app_in_pane(Pane::QueryEditor);
+    let profile = ConnectionProfile::new(
+        "local".to_string(),
+        "127.0.0.1".to_string(),
+        "root".to_string(),
+    );
+    app.data_backend =
+        Some(MysqlDataBackend::from_profile(&profile).expect("backend should build"));
+    app.active_connection_profile = Some(profile.clone());
+    app.last_connect_profile = Some(profile);
+    app.query_running = true;
+    app.inflight_query_sql = Some("SELECT 1".to_string());
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.query_result_rx = Some(rx);
+    tx.send(QueryWorkerOutcome::Failure(WorkerError::new(
+        "Pool was disconnected",
+        Some(2006),
+    )))
+    .expect("send disconnect failure");
+
+    app.poll_query_result();
+
+    assert!(app.connect_requested);
+    assert_eq!(app.connect_intent, ConnectIntent::AutoReconnect);
     assert_eq!(app.pending_retry_query.as_deref(), Some("SELECT 1"));
 }
 
+#[test]
+fn lost_connection_does_not_rerun_a_write() {
+    let mut app = app_in_pane(Pane::QueryEditor);
+    let profile = ConnectionProfile::new("local", "127.0.0.1", "root");
+    app.data_backend =
+        Some(MysqlDataBackend::from_profile(&profile).expect("backend should build"));
+    app.active_connection_profile = Some(profile.clone());
+    app.last_connect_profile = Some(profile);
+    app.query_running = true;
+    app.inflight_query_sql = Some("INSERT INTO orders (id) VALUES (1)".to_string());
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.query_result_rx = Some(rx);
+    tx.send(QueryWorkerOutcome::Failure(WorkerError::new(
+        "Lost connection to MySQL server during query",
+        Some(2013),
+    )))
+    .expect("send lost connection failure");
+
+    app.poll_query_result();
+
+    assert!(!app.connect_requested);
+    assert!(app.pending_retry_query.is_none());
+    assert_eq!(
+        app.status_line,
+        "Query failed: Lost connection to MySQL server during query | Not retried: the server may already have applied the statement"
+    );
+}
+
 #[test]
 fn query_worker_disconnection_opens_query_error_panel() {
     let mut app = app_in_pane(Pane::QueryEditor);
@@ -1092,7 +1901,7 @@ fn connect_from_wizard_rejects_invalid_password_source() {
 
     assert_eq!(
         app.status_line,
-        "Invalid password source in connection wizard (use env/keyring)"
+        "Invalid password source in connection wizard (use env/keyring/command/file)"
     );
 }
 
@@ -1135,6 +1944,39 @@ fn wizard_input_updates_active_field() {
     assert_eq!(app.status_line, "Saved Host");
 }
 
+#[test]
+fn wizard_paste_url_fills_discrete_fields() {
+    let mut app = app_in_pane(Pane::ConnectionWizard);
+    app.wizard_form.active_field = WizardField::Url;
+
+    app.handle(Msg::Submit);
+    for ch in "mysql://reporter@replica.local:3310/analytics".chars() {
+        app.handle(Msg::InputChar(ch));
+    }
+    app.handle(Msg::Submit);
+
+    assert_eq!(app.wizard_form.host, "replica.local");
+    assert_eq!(app.wizard_form.port, "3310");
+    assert_eq!(app.wizard_form.user, "reporter");
+    assert_eq!(app.wizard_form.database, "analytics");
+    assert_eq!(
+        app.status_line,
+        "Filled host, port, user, database and TLS mode from the URL"
+    );
+
+    app.handle(Msg::Submit);
+    for _ in 0..60 {
+        app.handle(Msg::Backspace);
+    }
+    for ch in "postgres://x@y/z".chars() {
+        app.handle(Msg::InputChar(ch));
+    }
+    app.handle(Msg::Submit);
+
+    assert_eq!(app.wizard_form.host, "replica.local");
+    assert!(app.status_line.starts_with("Invalid connection URL"));
+}
+
 #[test]
 fn wizard_backspace_updates_active_field() {
     let mut app = app_in_pane(Pane::ConnectionWizard);
@@ -1384,6 +2226,102 @@ fn results_search_mode_finds_and_cycles_matches() {
     assert_eq!(app.status_line, "Results search canceled");
 }
 
+#[test]
+fn results_search_scans_spilled_rows_a_slice_per_tick() {
+    let mut app = app_in_pane(Pane::Results);
+    let mut results = SpillingResultsStore::new(100);
+    for id in 1..=12_000 {
+        let label = if id == 11_000 {
+            "zebra".to_string()
+        } else {
+            format!("row-{id}")
+        };
+        results.push(QueryRow::new(vec![
+            QueryValue::Int(id),
+            QueryValue::from(label),
+        ]));
+    }
+    app.results = Box::new(results);
+    app.has_results = true;
+
+    app.apply_invocation(
+        ActionId::SearchResults,
+        ActionInvocation::SearchBufferedResults,
+    );
+    app.handle(Msg::InputChar('z'));
+    assert_eq!(
+        app.status_line,
+        "Searching for `z`: 5000 / 12000 rows scanned (Esc cancel)"
+    );
+
+    app.on_tick();
+    assert!(
+        app.status_line.contains("10000 / 12000"),
+        "{}",
+        app.status_line
+    );
+    app.on_tick();
+    assert_eq!(app.results_cursor, 10_999);
+    assert!(app
+        .status_line
+        .starts_with("Search matched row 11000 / 12000"));
+    assert!(app.results_search_scan.is_none());
+
+    app.handle(Msg::InputChar('q'));
+    assert!(app.results_search_scan.is_some());
+    app.handle(Msg::TogglePalette);
+    assert!(app.results_search_scan.is_none());
+    app.on_tick();
+    assert_eq!(app.status_line, "Results search canceled");
+}
+
+#[derive(Debug)]
+struct UnreadableSpill;
+
+impl ResultsStore<QueryRow> for UnreadableSpill {
+    fn capacity(&self) -> usize {
+        1
+    }
+
+    fn len(&self) -> usize {
+        1
+    }
+
+    fn total_rows_seen(&self) -> u64 {
+        1
+    }
+
+    fn push(&mut self, _row: QueryRow) {}
+
+    fn row(&self, _index: usize) -> Option<QueryRow> {
+        None
+    }
+
+    fn rows(&self, _start: usize, _limit: usize) -> Vec<QueryRow> {
+        Vec::new()
+    }
+
+    fn spill_error(&self) -> Option<String> {
+        Some("could not read spilled rows from row 1 (unexpected end of file)".to_string())
+    }
+}
+
+#[test]
+fn failed_spill_reads_are_reported_once_in_the_status_line() {
+    let mut app = app_in_pane(Pane::Results);
+    app.results = Box::new(UnreadableSpill);
+
+    app.on_tick();
+    assert_eq!(
+        app.status_line,
+        "Warning: could not read spilled rows from row 1 (unexpected end of file)"
+    );
+
+    app.status_line = "Results cursor moved".to_string();
+    app.on_tick();
+    assert_eq!(app.status_line, "Results cursor moved");
+}
+
 #[test]
 fn palette_input_and_selection_paths_update_state() {
     let mut app = app_in_pane(Pane::QueryEditor);
@@ -1665,6 +2603,39 @@ fn connect_worker_disconnection_opens_connection_error_panel() {
     assert_eq!(panel.detail, "connect worker disconnected");
 }
 
+#[test]
+fn auto_reconnect_stops_on_a_permanent_connect_error() {
+    let mut app = app_in_pane(Pane::QueryEditor);
+    let profile = ConnectionProfile::new("local", "127.0.0.1", "root");
+    app.active_connection_profile = Some(profile.clone());
+    app.last_connect_profile = Some(profile);
+    app.connect_requested = true;
+    app.connect_intent = ConnectIntent::AutoReconnect;
+    app.reconnect_attempts = 1;
+    app.pending_retry_query = Some("SELECT 1".to_string());
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.connect_result_rx = Some(rx);
+    tx.send(ConnectWorkerOutcome::Failure(WorkerError::new(
+        "Access denied for user 'root'",
+        Some(1045),
+    )))
+    .expect("send access denied failure");
+
+    app.on_tick();
+
+    assert!(!app.connect_requested);
+    assert_eq!(
+        app.reconnect_attempts,
+        RetryPolicy::default().max_reconnect_attempts
+    );
+    assert!(app.pending_retry_query.is_none());
+    assert_eq!(
+        app.status_line,
+        "Connect failed: Access denied for user 'root'"
+    );
+}
+
 #[test]
 fn apply_connected_profile_resets_schema_filters() {
     let profile = ConnectionProfile::new("local-dev", "127.0.0.1", "root");
@@ -1675,6 +2646,7 @@ fn apply_connected_profile_resets_schema_filters() {
 
     app.apply_connected_profile(
         profile,
+        &ResolvedPassword::default(),
         Duration::from_millis(1),
         vec!["myr_bench".to_string()],
         None,
@@ -1685,6 +2657,289 @@ fn apply_connected_profile_resets_schema_filters() {
     assert!(app.schema_column_filter.is_empty());
 }
 
+#[test]
+fn detected_server_is_shown_per_connection_and_gates_explain_analyze() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::QueryEditor, &temp_dir);
+    let staging = ConnectionProfile::new("staging", "127.0.0.1", "root");
+    let prod = ConnectionProfile::new("prod", "127.0.0.1", "root");
+
+    app.apply_connected_profile(
+        staging,
+        &ResolvedPassword::default(),
+        Duration::from_millis(1),
+        Vec::new(),
+        None,
+    );
+    app.apply_server_capabilities(Some(ServerCapabilities::new(
+        ServerFlavor::MariaDb,
+        ServerVersion::new(10, 11, 6),
+    )));
+    app.pane = Pane::QueryEditor;
+    app.query_editor_text = "SELECT * FROM users".to_string();
+    assert!(render_snapshot(&app).contains("Profile: staging (MariaDB 10.11.6)"));
+    assert_eq!(
+        app.actions
+            .invoke(ActionId::ExplainAnalyzeQuery, &app.action_context())
+            .expect("MariaDB supports ANALYZE"),
+        ActionInvocation::RunSql("ANALYZE SELECT * FROM users".to_string())
+    );
+
+    app.apply_connected_profile(
+        prod,
+        &ResolvedPassword::default(),
+        Duration::from_millis(1),
+        Vec::new(),
+        None,
+    );
+    app.apply_server_capabilities(Some(ServerCapabilities::new(
+        ServerFlavor::MySql,
+        ServerVersion::new(5, 7, 44),
+    )));
+    app.pane = Pane::QueryEditor;
+    app.query_editor_text = "SELECT * FROM users".to_string();
+    assert!(render_snapshot(&app).contains("Profile: prod (MySQL 5.7.44)"));
+    assert!(app
+        .actions
+        .invoke(ActionId::ExplainAnalyzeQuery, &app.action_context())
+        .is_err());
+
+    app.next_connection();
+    assert_eq!(app.connected_profile.as_deref(), Some("staging"));
+    assert_eq!(
+        app.server_capabilities
+            .as_ref()
+            .map(ServerCapabilities::label),
+        Some("MariaDB 10.11.6".to_string())
+    );
+}
+
+#[test]
+fn runtime_bar_shows_the_serving_endpoint_only_for_profiles_with_replicas() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::QueryEditor, &temp_dir);
+    let single = ConnectionProfile::new("single", "127.0.0.1", "root");
+    app.apply_connected_profile(
+        single,
+        &ResolvedPassword::default(),
+        Duration::from_millis(1),
+        Vec::new(),
+        None,
+    );
+    assert!(!render_snapshot(&app).contains("Served:"));
+
+    let mut shop = ConnectionProfile::new("shop", "db-primary", "root");
+    shop.replicas = vec![ReplicaEndpoint::new("db-r1", None)];
+    app.apply_connected_profile(
+        shop,
+        &ResolvedPassword::default(),
+        Duration::from_millis(1),
+        Vec::new(),
+        None,
+    );
+    assert!(render_snapshot(&app).contains("Profile: shop | Served: -"));
+}
+
+#[test]
+fn second_profile_opens_its_own_connection_workspace() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::SchemaExplorer, &temp_dir);
+    let staging = ConnectionProfile::new("staging", "127.0.0.1", "root");
+    let mut prod = ConnectionProfile::new("prod", "127.0.0.1", "root");
+    prod.read_only = true;
+
+    app.apply_connected_profile(
+        staging.clone(),
+        &ResolvedPassword::default(),
+        Duration::from_millis(1),
+        vec!["staging_db".to_string()],
+        None,
+    );
+    app.query_editor_text = "SELECT 'staging'".to_string();
+    app.transaction_open = true;
+    assert_eq!(app.open_connection_count(), 1);
+    assert!(!render_snapshot(&app).contains("Connections (F8 next)"));
+
+    app.apply_connected_profile(
+        prod,
+        &ResolvedPassword::default(),
+        Duration::from_millis(1),
+        vec!["prod_db".to_string()],
+        None,
+    );
+    assert_eq!(app.open_connection_count(), 2);
+    assert_eq!(app.connected_profile.as_deref(), Some("prod"));
+    assert_eq!(app.schema_databases, vec!["prod_db".to_string()]);
+    assert!(!app.transaction_open);
+    assert_ne!(app.query_editor_text, "SELECT 'staging'");
+    let rendered = render_snapshot(&app);
+    assert!(rendered.contains("1:staging RW TX"));
+    assert!(rendered.contains("2:prod RO"));
+
+    assert!(matches!(
+        map_key_event(KeyEvent::new(KeyCode::F(8), KeyModifiers::NONE)),
+        Some(Msg::NextConnection)
+    ));
+    app.query_running = true;
+    app.handle(Msg::NextConnection);
+    assert_eq!(app.connected_profile.as_deref(), Some("prod"));
+    assert!(app.status_line.starts_with("Finish or cancel"));
+    app.query_running = false;
+
+    app.handle(Msg::NextConnection);
+    assert_eq!(app.connected_profile.as_deref(), Some("staging"));
+    assert_eq!(app.query_editor_text, "SELECT 'staging'");
+    assert_eq!(app.schema_databases, vec!["staging_db".to_string()]);
+    assert!(app.transaction_open);
+    assert_eq!(app.wizard_form.profile_name, "staging");
+
+    // Reconnecting an open profile reuses its tab instead of adding another.
+    app.apply_connected_profile(
+        ConnectionProfile::new("prod", "127.0.0.1", "root"),
+        &ResolvedPassword::default(),
+        Duration::from_millis(1),
+        vec!["prod_db".to_string()],
+        None,
+    );
+    assert_eq!(app.open_connection_count(), 2);
+    assert_eq!(app.connected_profile.as_deref(), Some("prod"));
+
+    app.invoke_action(ActionId::CloseConnection);
+    assert_eq!(app.open_connection_count(), 1);
+    assert_eq!(app.connected_profile.as_deref(), Some("staging"));
+    assert_eq!(
+        app.status_line,
+        "Closed connection `prod`; switched to `staging`"
+    );
+
+    app.invoke_action(ActionId::CloseConnection);
+    assert!(app.transaction_prompt.is_some());
+    app.handle(Msg::TogglePalette);
+    app.transaction_open = false;
+    app.invoke_action(ActionId::CloseConnection);
+    assert_eq!(app.open_connection_count(), 0);
+    assert!(app.data_backend.is_none());
+    assert_eq!(app.pane, Pane::ConnectionWizard);
+}
+
+#[test]
+fn keepalive_probes_track_latency_and_reconnect_dead_sessions() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::SchemaExplorer, &temp_dir);
+    for name in ["staging", "prod"] {
+        app.apply_connected_profile(
+            ConnectionProfile::new(name, "127.0.0.1", "root"),
+            &ResolvedPassword::default(),
+            Duration::from_millis(1),
+            Vec::new(),
+            None,
+        );
+    }
+    assert_eq!(app.latency_history.len(), 1);
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.keepalive_rx = Some(rx);
+    tx.send(vec![
+        KeepaliveProbe {
+            profile_name: "prod".to_string(),
+            outcome: Ok(Duration::from_millis(3)),
+        },
+        KeepaliveProbe {
+            profile_name: "staging".to_string(),
+            outcome: Err("server has gone away".to_string()),
+        },
+    ])
+    .expect("send keepalive probes");
+    app.poll_keepalive_result();
+
+    assert!(app.keepalive_rx.is_none());
+    assert_eq!(app.latency_history.len(), 2);
+    assert_eq!(app.last_connection_latency, Some(Duration::from_millis(3)));
+    assert!(!app.connection_stale);
+    assert!(!app.connect_requested);
+
+    let backend = TestBackend::new(240, SNAPSHOT_HEIGHT);
+    let mut terminal = Terminal::new(backend).expect("test terminal");
+    terminal.draw(|frame| render(frame, &app)).expect("render");
+    let rendered = snapshot_from_buffer(terminal.backend().buffer());
+    assert!(rendered.contains("Latency: 3.0ms ▃█"));
+
+    app.handle(Msg::NextConnection);
+    assert_eq!(app.connected_profile.as_deref(), Some("staging"));
+    assert!(app.connection_stale);
+    assert!(app.connect_requested);
+    assert_eq!(app.connect_intent, ConnectIntent::AutoReconnect);
+}
+
+#[test]
+fn failed_keepalive_prompts_instead_of_reconnecting_an_open_transaction() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::QueryEditor, &temp_dir);
+    app.apply_connected_profile(
+        ConnectionProfile::new("prod", "127.0.0.1", "root"),
+        &ResolvedPassword::default(),
+        Duration::from_millis(1),
+        Vec::new(),
+        None,
+    );
+    app.transaction_open = true;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.keepalive_rx = Some(rx);
+    tx.send(vec![KeepaliveProbe {
+        profile_name: "prod".to_string(),
+        outcome: Err("ping timed out".to_string()),
+    }])
+    .expect("send keepalive probe");
+    app.poll_keepalive_result();
+
+    assert!(app.connection_stale);
+    assert!(!app.connect_requested);
+    assert!(app.transaction_open);
+    assert!(matches!(
+        app.transaction_prompt,
+        Some(TransactionExitIntent::Connect(ref profile)) if profile.name == "prod"
+    ));
+    assert!(app
+        .status_line
+        .starts_with("Keepalive failed: ping timed out | Transaction open"));
+
+    app.handle(Msg::TogglePalette);
+    assert!(app.transaction_prompt.is_none());
+    assert!(!app.connect_requested);
+    assert!(app.transaction_open);
+}
+
+#[test]
+fn keepalive_stops_reconnecting_once_the_attempts_are_spent() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let mut app = app_with_manager_stores(Pane::QueryEditor, &temp_dir);
+    app.apply_connected_profile(
+        ConnectionProfile::new("prod", "127.0.0.1", "root"),
+        &ResolvedPassword::default(),
+        Duration::from_millis(1),
+        Vec::new(),
+        None,
+    );
+    app.reconnect_attempts = RetryPolicy::default().max_reconnect_attempts;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.keepalive_rx = Some(rx);
+    tx.send(vec![KeepaliveProbe {
+        profile_name: "prod".to_string(),
+        outcome: Err("server has gone away".to_string()),
+    }])
+    .expect("send keepalive probe");
+    app.poll_keepalive_result();
+
+    assert!(app.connection_stale);
+    assert!(!app.connect_requested);
+    assert_eq!(
+        app.status_line,
+        "Keepalive failed: server has gone away | Auto-reconnect gave up after 2 attempts; reconnect manually"
+    );
+}
+
 #[test]
 fn apply_connected_profile_preserves_default_and_quick_markers() {
     let temp_dir = TempDir::new().expect("failed to create temp dir");
@@ -1702,6 +2957,7 @@ fn apply_connected_profile_preserves_default_and_quick_markers() {
     let incoming = ConnectionProfile::new("local-dev", "127.0.0.1", "root");
     app.apply_connected_profile(
         incoming,
+        &ResolvedPassword::default(),
         Duration::from_millis(1),
         vec!["myr_bench".to_string()],
         None,
@@ -1728,7 +2984,8 @@ fn mysql_query_path_streams_rows_when_enabled() {
     let profile = mysql_integration_profile(Some(&database));
 
     let mut app = app_in_pane(Pane::QueryEditor);
-    app.data_backend = Some(MysqlDataBackend::from_profile(&profile));
+    app.data_backend =
+        Some(MysqlDataBackend::from_profile(&profile).expect("backend should build"));
     app.selection.database = Some(database.clone());
     app.selection.table = Some("events".to_string());
     app.selection.column = Some("id".to_string());
@@ -1768,6 +3025,7 @@ fn mysql_query_path_survives_schema_cache_activity_when_enabled() {
     let mut app = app_in_pane(Pane::SchemaExplorer);
     app.apply_connected_profile(
         profile.clone(),
+        &ResolvedPassword::default(),
         Duration::from_millis(1),
         vec![database],
         None,